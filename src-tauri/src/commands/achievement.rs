@@ -1,5 +1,5 @@
 use tauri::State;
-use crate::domain::{Achievement, DbState, DbGeminiModel};
+use crate::domain::{Achievement, AchievementOptions, DbState, DbGeminiModel};
 use crate::services;
 use crate::domain::Result;
 
@@ -14,6 +14,7 @@ pub async fn generate_achievement(
     force_retry: bool,
     overwrite: bool,
     target_model: Option<String>,
+    options: Option<AchievementOptions>,
 ) -> Result<Achievement> {
     services::achievement::generate_achievement(
         &state.pool,
@@ -25,6 +26,7 @@ pub async fn generate_achievement(
         force_retry,
         overwrite,
         target_model,
+        options.unwrap_or_default(),
     ).await
 }
 
@@ -0,0 +1,14 @@
+use tauri::State;
+use crate::domain::{DbState, EstimationAccuracyReport};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn get_estimation_accuracy(
+    state: State<'_, DbState>,
+    workspace_id: i64,
+    start_date: String, // "YYYY-MM-DD"
+    end_date: String,   // "YYYY-MM-DD"
+) -> Result<EstimationAccuracyReport> {
+    services::analytics::get_estimation_accuracy(&state.pool, workspace_id, &start_date, &end_date).await
+}
@@ -0,0 +1,34 @@
+use tauri::State;
+use crate::domain::{DbState, ChecklistItem, ChecklistItemInput, UpdateChecklistItemInput};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn get_checklist(state: State<'_, DbState>, task_id: i64) -> Result<Vec<ChecklistItem>> {
+    services::checklist::get_checklist(&state.pool, task_id).await
+}
+
+#[tauri::command]
+pub async fn add_checklist_item(state: State<'_, DbState>, input: ChecklistItemInput) -> Result<ChecklistItem> {
+    services::checklist::add_checklist_item(&state.pool, input).await
+}
+
+#[tauri::command]
+pub async fn update_checklist_item(state: State<'_, DbState>, input: UpdateChecklistItemInput) -> Result<()> {
+    services::checklist::update_checklist_item(&state.pool, input).await
+}
+
+#[tauri::command]
+pub async fn toggle_checklist_item(state: State<'_, DbState>, id: i64, checked: bool) -> Result<()> {
+    services::checklist::toggle_checklist_item(&state.pool, id, checked).await
+}
+
+#[tauri::command]
+pub async fn delete_checklist_item(state: State<'_, DbState>, id: i64) -> Result<()> {
+    services::checklist::delete_checklist_item(&state.pool, id).await
+}
+
+#[tauri::command]
+pub async fn reorder_checklist(state: State<'_, DbState>, task_id: i64, item_ids: Vec<i64>) -> Result<()> {
+    services::checklist::reorder_checklist(&state.pool, task_id, item_ids).await
+}
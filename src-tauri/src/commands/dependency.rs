@@ -0,0 +1,19 @@
+use tauri::State;
+use crate::domain::{DbState, TaskDependency};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn get_task_dependencies(state: State<'_, DbState>, workspace_id: i64) -> Result<Vec<TaskDependency>> {
+    services::dependency::get_task_dependencies(&state.pool, workspace_id).await
+}
+
+#[tauri::command]
+pub async fn add_task_dependency(state: State<'_, DbState>, task_id: i64, blocked_by_task_id: i64) -> Result<()> {
+    services::dependency::add_task_dependency(&state.pool, task_id, blocked_by_task_id).await
+}
+
+#[tauri::command]
+pub async fn remove_task_dependency(state: State<'_, DbState>, task_id: i64, blocked_by_task_id: i64) -> Result<()> {
+    services::dependency::remove_task_dependency(&state.pool, task_id, blocked_by_task_id).await
+}
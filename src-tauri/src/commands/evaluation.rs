@@ -0,0 +1,19 @@
+use tauri::State;
+use crate::domain::{Achievement, Competency, CompetencyInput, DbState, SelfEvaluationInput};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn get_competencies(state: State<'_, DbState>, workspace_id: i64) -> Result<Vec<Competency>> {
+    services::evaluation::get_competencies(&state.pool, workspace_id).await
+}
+
+#[tauri::command]
+pub async fn save_competencies(state: State<'_, DbState>, workspace_id: i64, competencies: Vec<CompetencyInput>) -> Result<Vec<Competency>> {
+    services::evaluation::save_competencies(&state.pool, workspace_id, competencies).await
+}
+
+#[tauri::command]
+pub async fn generate_self_evaluation(state: State<'_, DbState>, input: SelfEvaluationInput) -> Result<Achievement> {
+    services::evaluation::generate_self_evaluation(&state.pool, input).await
+}
@@ -0,0 +1,38 @@
+use tauri::State;
+use crate::domain::{DbState, NotifierState, FocusSession, StartFocusInput, FocusDayStats};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn start_focus_session(state: State<'_, DbState>, notifier: State<'_, NotifierState>, input: StartFocusInput) -> Result<FocusSession> {
+    let session = services::focus::start_focus_session(&state.pool, input).await?;
+    notifier.reschedule();
+    Ok(session)
+}
+
+#[tauri::command]
+pub async fn get_active_focus_session(state: State<'_, DbState>, workspace_id: i64) -> Result<Option<FocusSession>> {
+    services::focus::get_active_focus_session(&state.pool, workspace_id).await
+}
+
+#[tauri::command]
+pub async fn interrupt_focus_session(state: State<'_, DbState>, id: i64) -> Result<FocusSession> {
+    services::focus::interrupt_focus_session(&state.pool, id).await
+}
+
+#[tauri::command]
+pub async fn cancel_focus_session(state: State<'_, DbState>, notifier: State<'_, NotifierState>, id: i64) -> Result<FocusSession> {
+    let session = services::focus::cancel_focus_session(&state.pool, id).await?;
+    notifier.reschedule();
+    Ok(session)
+}
+
+#[tauri::command]
+pub async fn get_focus_stats(
+    state: State<'_, DbState>,
+    workspace_id: i64,
+    start_date: String, // "YYYY-MM-DD"
+    end_date: String,   // "YYYY-MM-DD"
+) -> Result<Vec<FocusDayStats>> {
+    services::focus::get_focus_stats(&state.pool, workspace_id, &start_date, &end_date).await
+}
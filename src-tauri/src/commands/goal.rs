@@ -0,0 +1,64 @@
+use tauri::State;
+use crate::domain::{DbState, Goal, GoalInput, KeyResultInput, KeyResultCheckIn, CheckInInput};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn get_goals(state: State<'_, DbState>, workspace_id: i64) -> Result<Vec<Goal>> {
+    services::goal::get_goals(&state.pool, workspace_id).await
+}
+
+#[tauri::command]
+pub async fn get_goal(state: State<'_, DbState>, id: i64) -> Result<Goal> {
+    services::goal::get_goal(&state.pool, id).await
+}
+
+#[tauri::command]
+pub async fn create_goal(state: State<'_, DbState>, input: GoalInput) -> Result<i64> {
+    services::goal::create_goal(&state.pool, input).await
+}
+
+#[tauri::command]
+pub async fn update_goal(state: State<'_, DbState>, id: i64, input: GoalInput) -> Result<()> {
+    services::goal::update_goal(&state.pool, id, input).await
+}
+
+#[tauri::command]
+pub async fn delete_goal(state: State<'_, DbState>, id: i64) -> Result<()> {
+    services::goal::delete_goal(&state.pool, id).await
+}
+
+#[tauri::command]
+pub async fn create_key_result(state: State<'_, DbState>, input: KeyResultInput) -> Result<i64> {
+    services::goal::create_key_result(&state.pool, input).await
+}
+
+#[tauri::command]
+pub async fn update_key_result(state: State<'_, DbState>, id: i64, input: KeyResultInput) -> Result<()> {
+    services::goal::update_key_result(&state.pool, id, input).await
+}
+
+#[tauri::command]
+pub async fn delete_key_result(state: State<'_, DbState>, id: i64) -> Result<()> {
+    services::goal::delete_key_result(&state.pool, id).await
+}
+
+#[tauri::command]
+pub async fn add_key_result_check_in(state: State<'_, DbState>, input: CheckInInput) -> Result<i64> {
+    services::goal::add_check_in(&state.pool, input).await
+}
+
+#[tauri::command]
+pub async fn get_key_result_check_ins(state: State<'_, DbState>, key_result_id: i64) -> Result<Vec<KeyResultCheckIn>> {
+    services::goal::get_check_ins(&state.pool, key_result_id).await
+}
+
+#[tauri::command]
+pub async fn delete_key_result_check_in(state: State<'_, DbState>, id: i64) -> Result<()> {
+    services::goal::delete_check_in(&state.pool, id).await
+}
+
+#[tauri::command]
+pub async fn set_task_key_result(state: State<'_, DbState>, task_id: i64, key_result_id: Option<i64>) -> Result<()> {
+    services::goal::set_task_key_result(&state.pool, task_id, key_result_id).await
+}
@@ -0,0 +1,9 @@
+use tauri::State;
+use crate::domain::{DbState, InterruptionReport};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn get_interruption_report(state: State<'_, DbState>, workspace_id: i64, start_date: String, end_date: String) -> Result<InterruptionReport> {
+    services::interruption::get_interruption_report(&state.pool, workspace_id, &start_date, &end_date).await
+}
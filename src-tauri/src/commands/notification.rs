@@ -0,0 +1,16 @@
+use tauri::State;
+use crate::domain::{DbState, NotifierState, NotificationSettings, NotificationSettingsInput};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn get_notification_settings(state: State<'_, DbState>, workspace_id: i64) -> Result<NotificationSettings> {
+    services::notification::get_notification_settings(&state.pool, workspace_id).await
+}
+
+#[tauri::command]
+pub async fn update_notification_settings(state: State<'_, DbState>, notifier: State<'_, NotifierState>, workspace_id: i64, input: NotificationSettingsInput) -> Result<NotificationSettings> {
+    let settings = services::notification::update_notification_settings(&state.pool, workspace_id, input).await?;
+    notifier.reschedule();
+    Ok(settings)
+}
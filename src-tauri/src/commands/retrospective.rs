@@ -0,0 +1,24 @@
+use tauri::State;
+use crate::domain::{DbState, Retrospective, SaveRetrospectiveInput};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn save_retrospective(state: State<'_, DbState>, input: SaveRetrospectiveInput) -> Result<Retrospective> {
+    services::retrospective::save_retrospective(&state.pool, input).await
+}
+
+#[tauri::command]
+pub async fn get_retrospective(state: State<'_, DbState>, workspace_id: i64, logical_date: String) -> Result<Option<Retrospective>> {
+    services::retrospective::get_retrospective(&state.pool, workspace_id, &logical_date).await
+}
+
+#[tauri::command]
+pub async fn get_retrospectives(state: State<'_, DbState>, workspace_id: i64, start_date: String, end_date: String) -> Result<Vec<Retrospective>> {
+    services::retrospective::get_retrospectives(&state.pool, workspace_id, &start_date, &end_date).await
+}
+
+#[tauri::command]
+pub async fn delete_retrospective(state: State<'_, DbState>, workspace_id: i64, logical_date: String) -> Result<()> {
+    services::retrospective::delete_retrospective(&state.pool, workspace_id, &logical_date).await
+}
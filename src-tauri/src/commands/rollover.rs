@@ -0,0 +1,21 @@
+use tauri::State;
+use crate::domain::{DbState, NotifierState, RolloverReport};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn run_rollover(state: State<'_, DbState>, notifier: State<'_, NotifierState>, workspace_id: i64) -> Result<Option<RolloverReport>> {
+    let result = services::rollover::run_rollover(&state.pool, workspace_id).await?;
+    notifier.reschedule();
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_rollover_reports(state: State<'_, DbState>, workspace_id: i64, pending_only: Option<bool>) -> Result<Vec<RolloverReport>> {
+    services::rollover::get_rollover_reports(&state.pool, workspace_id, pending_only.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn acknowledge_rollover_report(state: State<'_, DbState>, id: i64) -> Result<()> {
+    services::rollover::acknowledge_rollover_report(&state.pool, id).await
+}
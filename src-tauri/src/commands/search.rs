@@ -0,0 +1,9 @@
+use tauri::State;
+use crate::domain::{DbState, SearchHit, SearchQuery};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn search(state: State<'_, DbState>, query: SearchQuery) -> Result<Vec<SearchHit>> {
+    services::search::search(&state.pool, query).await
+}
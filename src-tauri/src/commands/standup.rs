@@ -0,0 +1,9 @@
+use tauri::State;
+use crate::domain::{DbState, Standup, StandupInput};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn generate_standup(state: State<'_, DbState>, input: StandupInput) -> Result<Standup> {
+    services::standup::generate_standup(&state.pool, input).await
+}
@@ -0,0 +1,19 @@
+use tauri::State;
+use crate::domain::{DbState, Tag, TagStat};
+use crate::services;
+use crate::domain::Result;
+
+#[tauri::command]
+pub async fn get_tags(state: State<'_, DbState>, workspace_id: i64) -> Result<Vec<Tag>> {
+    services::tag::get_tags(&state.pool, workspace_id).await
+}
+
+#[tauri::command]
+pub async fn get_tag_stats(
+    state: State<'_, DbState>,
+    workspace_id: i64,
+    start_date: String, // "YYYY-MM-DD"
+    end_date: String,   // "YYYY-MM-DD"
+) -> Result<Vec<TagStat>> {
+    services::tag::get_tag_stats(&state.pool, workspace_id, &start_date, &end_date).await
+}
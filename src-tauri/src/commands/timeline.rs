@@ -1,5 +1,5 @@
 use tauri::State;
-use crate::domain::{Task, TimeBlock, AddTaskInput, TaskTransitionInput, UpdateTaskInput, DbState};
+use crate::domain::{Task, TimeBlock, AddTaskInput, QuickAddResult, TaskTransitionInput, UpdateTaskInput, DayPlanPreview, TimelineDay, DbState, NotifierState};
 use crate::services;
 use crate::domain::Result;
 
@@ -9,63 +9,132 @@ pub async fn get_today_completed_duration(state: State<'_, DbState>, workspace_i
 }
 
 #[tauri::command]
-pub async fn get_timeline(state: State<'_, DbState>, workspace_id: i64, date: Option<String>) -> Result<Vec<TimeBlock>> {
-    services::timeline::get_timeline(&state.pool, workspace_id, date).await
+pub async fn get_timeline(state: State<'_, DbState>, workspace_id: i64, date: Option<String>, tag: Option<String>) -> Result<Vec<TimeBlock>> {
+    services::timeline::get_timeline(&state.pool, workspace_id, date, tag).await
 }
 
 #[tauri::command]
-pub async fn get_inbox(state: State<'_, DbState>, workspace_id: i64) -> Result<Vec<Task>> {
-    services::timeline::get_inbox(&state.pool, workspace_id).await
+pub async fn get_timeline_range(
+    state: State<'_, DbState>,
+    workspace_id: i64,
+    start_date: String, // "YYYY-MM-DD"
+    end_date: String,   // "YYYY-MM-DD"
+    tag: Option<String>,
+) -> Result<Vec<TimelineDay>> {
+    services::timeline::get_timeline_range(&state.pool, workspace_id, &start_date, &end_date, tag).await
 }
 
 #[tauri::command]
-pub async fn add_task(state: State<'_, DbState>, input: AddTaskInput) -> Result<()> {
-    services::timeline::add_task(&state.pool, input).await
+pub async fn get_inbox(state: State<'_, DbState>, workspace_id: i64, sort_by: Option<String>, tag: Option<String>) -> Result<Vec<Task>> {
+    services::timeline::get_inbox(&state.pool, workspace_id, sort_by, tag).await
 }
 
 #[tauri::command]
-pub async fn update_task(state: State<'_, DbState>, input: UpdateTaskInput) -> Result<()> {
-    services::timeline::update_task(&state.pool, input).await
+pub async fn get_at_risk_tasks(state: State<'_, DbState>, workspace_id: i64) -> Result<Vec<Task>> {
+    services::timeline::get_at_risk_tasks(&state.pool, workspace_id).await
 }
 
 #[tauri::command]
-pub async fn move_to_inbox(state: State<'_, DbState>, block_id: i64) -> Result<()> {
-    services::timeline::move_to_inbox(&state.pool, block_id).await
+pub async fn add_task(state: State<'_, DbState>, notifier: State<'_, NotifierState>, input: AddTaskInput) -> Result<()> {
+    services::timeline::add_task(&state.pool, input).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn move_to_timeline(state: State<'_, DbState>, task_id: i64, workspace_id: i64) -> Result<()> {
-    services::timeline::move_to_timeline(&state.pool, task_id, workspace_id).await
+pub async fn quick_add_task(state: State<'_, DbState>, notifier: State<'_, NotifierState>, workspace_id: i64, text: String) -> Result<QuickAddResult> {
+    let result = services::quick_add::quick_add_task(&state.pool, workspace_id, &text).await?;
+    notifier.reschedule();
+    Ok(result)
 }
 
 #[tauri::command]
-pub async fn move_all_to_timeline(state: State<'_, DbState>, workspace_id: i64) -> Result<()> {
-    services::timeline::move_all_to_timeline(&state.pool, workspace_id).await
+pub async fn update_task(state: State<'_, DbState>, notifier: State<'_, NotifierState>, input: UpdateTaskInput) -> Result<()> {
+    services::timeline::update_task(&state.pool, input).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn delete_task(state: State<'_, DbState>, id: i64) -> Result<()> {
-    services::timeline::delete_task(&state.pool, id).await
+pub async fn move_to_inbox(state: State<'_, DbState>, notifier: State<'_, NotifierState>, block_id: i64) -> Result<()> {
+    services::timeline::move_to_inbox(&state.pool, block_id).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn handle_split_task_deletion(state: State<'_, DbState>, task_id: i64, keep_past: bool) -> Result<()> {
-    services::timeline::handle_split_task_deletion(&state.pool, task_id, keep_past).await
+pub async fn move_to_timeline(state: State<'_, DbState>, notifier: State<'_, NotifierState>, task_id: i64, workspace_id: i64, target_date: Option<String>) -> Result<()> {
+    services::timeline::move_to_timeline(&state.pool, task_id, workspace_id, target_date).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn process_task_transition(state: State<'_, DbState>, input: TaskTransitionInput) -> Result<()> {
-    services::timeline::process_task_transition(&state.pool, input).await
+pub async fn move_all_to_timeline(state: State<'_, DbState>, notifier: State<'_, NotifierState>, workspace_id: i64, target_date: Option<String>) -> Result<()> {
+    services::timeline::move_all_to_timeline(&state.pool, workspace_id, target_date).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn update_block_status(state: State<'_, DbState>, block_id: i64, status: String) -> Result<()> {
-    services::timeline::update_block_status(&state.pool, block_id, status).await
+pub async fn preview_day_plan(state: State<'_, DbState>, workspace_id: i64) -> Result<DayPlanPreview> {
+    services::timeline::preview_day_plan(&state.pool, workspace_id).await
 }
 
 #[tauri::command]
-pub async fn reorder_blocks(state: State<'_, DbState>, workspace_id: i64, block_ids: Vec<i64>) -> Result<()> {
-    services::timeline::reorder_blocks(&state.pool, workspace_id, block_ids).await
+pub async fn apply_day_plan(state: State<'_, DbState>, notifier: State<'_, NotifierState>, workspace_id: i64, task_ids: Vec<i64>) -> Result<()> {
+    services::timeline::apply_day_plan(&state.pool, workspace_id, task_ids).await?;
+    notifier.reschedule();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_task(state: State<'_, DbState>, notifier: State<'_, NotifierState>, id: i64) -> Result<()> {
+    services::timeline::delete_task(&state.pool, id).await?;
+    notifier.reschedule();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn handle_split_task_deletion(state: State<'_, DbState>, notifier: State<'_, NotifierState>, task_id: i64, keep_past: bool) -> Result<()> {
+    services::timeline::handle_split_task_deletion(&state.pool, task_id, keep_past).await?;
+    notifier.reschedule();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn process_task_transition(state: State<'_, DbState>, notifier: State<'_, NotifierState>, input: TaskTransitionInput) -> Result<()> {
+    services::timeline::process_task_transition(&state.pool, input).await?;
+    notifier.reschedule();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn pause_block(state: State<'_, DbState>, notifier: State<'_, NotifierState>, block_id: i64) -> Result<()> {
+    services::timeline::pause_block(&state.pool, block_id).await?;
+    notifier.reschedule();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn resume_block(state: State<'_, DbState>, notifier: State<'_, NotifierState>, block_id: i64) -> Result<()> {
+    services::timeline::resume_block(&state.pool, block_id).await?;
+    notifier.reschedule();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn update_block_status(state: State<'_, DbState>, notifier: State<'_, NotifierState>, block_id: i64, status: String) -> Result<()> {
+    services::timeline::update_block_status(&state.pool, block_id, status).await?;
+    notifier.reschedule();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn reorder_blocks(state: State<'_, DbState>, notifier: State<'_, NotifierState>, workspace_id: i64, block_ids: Vec<i64>) -> Result<()> {
+    services::timeline::reorder_blocks(&state.pool, workspace_id, block_ids).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
@@ -74,18 +143,24 @@ pub async fn reorder_inbox(state: State<'_, DbState>, workspace_id: i64, task_id
 }
 
 #[tauri::command]
-pub async fn move_task_step(state: State<'_, DbState>, workspace_id: i64, block_id: i64, direction: String) -> Result<()> {
-    services::timeline::move_task_step(&state.pool, workspace_id, block_id, direction).await
+pub async fn move_task_step(state: State<'_, DbState>, notifier: State<'_, NotifierState>, workspace_id: i64, block_id: i64, direction: String) -> Result<()> {
+    services::timeline::move_task_step(&state.pool, workspace_id, block_id, direction).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn move_task_to_priority(state: State<'_, DbState>, workspace_id: i64, block_id: i64) -> Result<()> {
-    services::timeline::move_task_to_priority(&state.pool, workspace_id, block_id).await
+pub async fn move_task_to_priority(state: State<'_, DbState>, notifier: State<'_, NotifierState>, workspace_id: i64, block_id: i64) -> Result<()> {
+    services::timeline::move_task_to_priority(&state.pool, workspace_id, block_id).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn move_task_to_bottom(state: State<'_, DbState>, workspace_id: i64, block_id: i64) -> Result<()> {
-    services::timeline::move_task_to_bottom(&state.pool, workspace_id, block_id).await
+pub async fn move_task_to_bottom(state: State<'_, DbState>, notifier: State<'_, NotifierState>, workspace_id: i64, block_id: i64) -> Result<()> {
+    services::timeline::move_task_to_bottom(&state.pool, workspace_id, block_id).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
@@ -1,5 +1,5 @@
 use tauri::State;
-use crate::domain::{Workspace, UnpluggedTime, CreateWorkspaceInput, DbState, Project, Label, ProjectInput, LabelInput};
+use crate::domain::{Workspace, UnpluggedTime, CreateWorkspaceInput, DbState, NotifierState, Project, Label, ProjectInput, LabelInput, TaskSuggestion, ProjectDetail, DuplicateGroup};
 use crate::services;
 use crate::domain::Result;
 
@@ -29,15 +29,20 @@ pub async fn get_unplugged_times(state: State<'_, DbState>, workspace_id: i64) -
 #[tauri::command]
 pub async fn update_workspace(
     state: State<'_, DbState>,
+    notifier: State<'_, NotifierState>,
     id: i64,
     input: CreateWorkspaceInput,
 ) -> Result<()> {
-    services::workspace::update_workspace(&state.pool, id, input).await
+    services::workspace::update_workspace(&state.pool, id, input).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn delete_workspace(state: State<'_, DbState>, id: i64) -> Result<()> {
-    services::workspace::delete_workspace(&state.pool, id).await
+pub async fn delete_workspace(state: State<'_, DbState>, notifier: State<'_, NotifierState>, id: i64) -> Result<()> {
+    services::workspace::delete_workspace(&state.pool, id).await?;
+    notifier.reschedule();
+    Ok(())
 }
 
 #[tauri::command]
@@ -46,13 +51,18 @@ pub async fn suggest_task_titles(
     workspace_id: i64,
     query: String,
     limit: i64,
-) -> Result<Vec<String>> {
+) -> Result<Vec<TaskSuggestion>> {
     services::workspace::suggest_task_titles(&state.pool, workspace_id, &query, limit).await
 }
 
 #[tauri::command]
-pub async fn get_projects(state: State<'_, DbState>) -> Result<Vec<Project>> {
-    services::workspace::get_projects(&state.pool).await
+pub async fn get_projects(state: State<'_, DbState>, workspace_id: Option<i64>, include_archived: Option<bool>) -> Result<Vec<Project>> {
+    services::workspace::get_projects(&state.pool, workspace_id, include_archived.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn get_project_detail(state: State<'_, DbState>, id: i64) -> Result<ProjectDetail> {
+    services::workspace::get_project_detail(&state.pool, id).await
 }
 
 #[tauri::command]
@@ -65,14 +75,19 @@ pub async fn update_project(state: State<'_, DbState>, id: i64, input: ProjectIn
     services::workspace::update_project(&state.pool, id, input).await
 }
 
+#[tauri::command]
+pub async fn set_project_scope(state: State<'_, DbState>, id: i64, workspace_id: Option<i64>) -> Result<()> {
+    services::workspace::set_project_scope(&state.pool, id, workspace_id).await
+}
+
 #[tauri::command]
 pub async fn delete_project(state: State<'_, DbState>, id: i64) -> Result<()> {
     services::workspace::delete_project(&state.pool, id).await
 }
 
 #[tauri::command]
-pub async fn get_labels(state: State<'_, DbState>) -> Result<Vec<Label>> {
-    services::workspace::get_labels(&state.pool).await
+pub async fn get_labels(state: State<'_, DbState>, workspace_id: Option<i64>) -> Result<Vec<Label>> {
+    services::workspace::get_labels(&state.pool, workspace_id).await
 }
 
 #[tauri::command]
@@ -85,7 +100,42 @@ pub async fn update_label(state: State<'_, DbState>, id: i64, input: LabelInput)
     services::workspace::update_label(&state.pool, id, input).await
 }
 
+#[tauri::command]
+pub async fn set_label_scope(state: State<'_, DbState>, id: i64, workspace_id: Option<i64>) -> Result<()> {
+    services::workspace::set_label_scope(&state.pool, id, workspace_id).await
+}
+
 #[tauri::command]
 pub async fn delete_label(state: State<'_, DbState>, id: i64) -> Result<()> {
     services::workspace::delete_label(&state.pool, id).await
 }
+
+#[tauri::command]
+pub async fn find_duplicate_projects(state: State<'_, DbState>, workspace_id: Option<i64>) -> Result<Vec<DuplicateGroup<Project>>> {
+    services::workspace::find_duplicate_projects(&state.pool, workspace_id).await
+}
+
+#[tauri::command]
+pub async fn find_duplicate_labels(state: State<'_, DbState>, workspace_id: Option<i64>) -> Result<Vec<DuplicateGroup<Label>>> {
+    services::workspace::find_duplicate_labels(&state.pool, workspace_id).await
+}
+
+#[tauri::command]
+pub async fn merge_projects(state: State<'_, DbState>, source_id: i64, target_id: i64) -> Result<()> {
+    services::workspace::merge_projects(&state.pool, source_id, target_id).await
+}
+
+#[tauri::command]
+pub async fn merge_labels(state: State<'_, DbState>, source_id: i64, target_id: i64) -> Result<()> {
+    services::workspace::merge_labels(&state.pool, source_id, target_id).await
+}
+
+#[tauri::command]
+pub async fn reassign_tasks_project(state: State<'_, DbState>, task_ids: Vec<i64>, project_id: Option<i64>) -> Result<()> {
+    services::workspace::reassign_tasks_project(&state.pool, task_ids, project_id).await
+}
+
+#[tauri::command]
+pub async fn reassign_tasks_label(state: State<'_, DbState>, task_ids: Vec<i64>, label_id: Option<i64>) -> Result<()> {
+    services::workspace::reassign_tasks_label(&state.pool, task_ids, label_id).await
+}
@@ -1,7 +1,11 @@
 use sqlx::SqlitePool;
 use chrono::Local;
-use crate::domain::Achievement;
+use crate::domain::{Achievement, CompletedTaskBlock};
 use crate::domain::Result;
+use crate::database::timeline::WORKED_MINUTES_SQL;
+use crate::database::checklist::COMPLETED_STEPS_SQL;
+use crate::database::tag::BLOCK_TAGS_SQL;
+use crate::database::focus::BLOCK_FOCUS_SESSIONS_SQL;
 
 pub async fn get_saved_achievements(
     pool: &SqlitePool,
@@ -120,17 +124,25 @@ pub async fn get_completed_task_blocks(
     workspace_id: i64,
     start_time: &str,
     end_time: &str,
-) -> Result<Vec<(String, Option<String>, Option<String>, String, String)>> {
-    let blocks: Vec<(String, Option<String>, Option<String>, String, String)> = sqlx::query_as(
-        "SELECT tb.title, t.planning_memo, tb.review_memo, tb.start_time, tb.end_time 
+    tags: Option<&[String]>,
+) -> Result<Vec<CompletedTaskBlock>> {
+    let tags_json = tags.map(serde_json::to_string).transpose()?;
+    let blocks = sqlx::query_as::<_, CompletedTaskBlock>(&format!(
+        "SELECT tb.title, t.planning_memo, tb.review_memo, tb.start_time, tb.end_time, {} AS worked_minutes, {} AS completed_steps, {} AS tags, {} AS focus_sessions,
+                g.title AS goal_title, kr.title AS key_result_title
          FROM time_blocks tb
          LEFT JOIN tasks t ON tb.task_id = t.id
+         LEFT JOIN key_results kr ON t.key_result_id = kr.id
+         LEFT JOIN goals g ON kr.goal_id = g.id
          WHERE tb.workspace_id = ?1 AND tb.status = 'DONE' AND tb.start_time >= ?2 AND tb.start_time <= ?3
-         ORDER BY tb.start_time ASC"
-    )
+           AND (?4 IS NULL OR EXISTS (SELECT 1 FROM task_tags tt JOIN tags tg ON tt.tag_id = tg.id WHERE tt.task_id = tb.task_id AND tg.name IN (SELECT value FROM json_each(?4))))
+         ORDER BY tb.start_time ASC",
+        WORKED_MINUTES_SQL, COMPLETED_STEPS_SQL, BLOCK_TAGS_SQL, BLOCK_FOCUS_SESSIONS_SQL
+    ))
     .bind(workspace_id)
     .bind(start_time)
     .bind(end_time)
+    .bind(tags_json)
     .fetch_all(pool)
     .await?;
     Ok(blocks)
@@ -0,0 +1,126 @@
+use sqlx::SqlitePool;
+use crate::domain::{TaskEstimate, DailyPlan};
+use crate::domain::Result;
+use crate::database::timeline::WORKED_MINUTES_SQL;
+
+pub const CREATE_PLAN_SNAPSHOTS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS plan_snapshots (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER NOT NULL,
+        logical_date TEXT NOT NULL,
+        taken_at TEXT NOT NULL,
+        UNIQUE (workspace_id, logical_date),
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE
+    );
+";
+
+pub const CREATE_PLAN_SNAPSHOT_BLOCKS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS plan_snapshot_blocks (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        snapshot_id INTEGER NOT NULL,
+        block_id INTEGER,
+        task_id INTEGER,
+        title TEXT NOT NULL,
+        start_time TEXT NOT NULL,
+        end_time TEXT NOT NULL,
+        status TEXT NOT NULL,
+        estimated_minutes INTEGER NOT NULL DEFAULT 0,
+        FOREIGN KEY (snapshot_id) REFERENCES plan_snapshots (id) ON DELETE CASCADE
+    );
+";
+
+/// SQL expression mapping `tb.start_time` to its logical date; `day_start_param` is the placeholder bound to day_start_time.
+fn logical_date_sql(day_start_param: &str) -> String {
+    format!(
+        "CASE WHEN strftime('%H:%M', tb.start_time) < {0} THEN date(tb.start_time, '-1 day') ELSE date(tb.start_time) END",
+        day_start_param
+    )
+}
+
+/// Tasks with an estimate whose blocks are all finished, keyed by the start of their last block.
+pub async fn get_finished_task_estimates(
+    pool: &SqlitePool,
+    workspace_id: i64,
+    start_time: &str,
+    end_time: &str,
+) -> Result<Vec<TaskEstimate>> {
+    let tasks = sqlx::query_as::<_, TaskEstimate>(&format!(
+        "SELECT t.id AS task_id, t.title, p.name AS project_name, l.name AS label_name, t.estimated_minutes,
+            SUM(CASE WHEN tb.status IN ('DONE', 'CONTINUED') THEN {} ELSE 0 END) AS actual_minutes,
+            MAX(tb.start_time) AS last_start_time
+         FROM tasks t
+         JOIN time_blocks tb ON tb.task_id = t.id
+         LEFT JOIN projects p ON t.project_id = p.id
+         LEFT JOIN labels l ON t.label_id = l.id
+         WHERE t.workspace_id = ?1 AND t.estimated_minutes > 0
+         GROUP BY t.id
+         HAVING SUM(CASE WHEN tb.status IN ('WILL', 'NOW', 'PENDING') THEN 1 ELSE 0 END) = 0
+            AND MAX(tb.start_time) >= ?2 AND MAX(tb.start_time) <= ?3
+         ORDER BY last_start_time ASC",
+        WORKED_MINUTES_SQL
+    ))
+    .bind(workspace_id)
+    .bind(start_time)
+    .bind(end_time)
+    .fetch_all(pool)
+    .await?;
+    Ok(tasks)
+}
+
+pub async fn get_daily_plans(
+    pool: &SqlitePool,
+    workspace_id: i64,
+    start_date: &str,
+    end_date: &str,
+    day_start_time: &str,
+) -> Result<Vec<DailyPlan>> {
+    let plans = sqlx::query_as::<_, DailyPlan>(&format!(
+        "SELECT ps.logical_date,
+            COALESCE(SUM(CASE WHEN psb.status != 'DONE' THEN (strftime('%s', psb.end_time) - strftime('%s', psb.start_time)) / 60 ELSE 0 END), 0) AS planned_minutes,
+            COUNT(DISTINCT CASE WHEN psb.status != 'DONE' THEN psb.task_id END) AS planned_task_count,
+            COUNT(DISTINCT CASE WHEN psb.status != 'DONE' AND EXISTS (
+                SELECT 1 FROM time_blocks tb WHERE tb.task_id = psb.task_id AND tb.status = 'DONE' AND {} = ps.logical_date
+            ) THEN psb.task_id END) AS completed_planned_task_count
+         FROM plan_snapshots ps
+         LEFT JOIN plan_snapshot_blocks psb ON psb.snapshot_id = ps.id
+         WHERE ps.workspace_id = ?1 AND ps.logical_date >= ?2 AND ps.logical_date <= ?3
+         GROUP BY ps.id
+         ORDER BY ps.logical_date ASC",
+        logical_date_sql("?4")
+    ))
+    .bind(workspace_id)
+    .bind(start_date)
+    .bind(end_date)
+    .bind(day_start_time)
+    .fetch_all(pool)
+    .await?;
+    Ok(plans)
+}
+
+/// Worked minutes per logical date for finished (DONE/CONTINUED) blocks.
+pub async fn get_daily_worked_minutes(
+    pool: &SqlitePool,
+    workspace_id: i64,
+    start_time: &str,
+    end_time: &str,
+    day_start_time: &str,
+) -> Result<Vec<(String, i64)>> {
+    let rows: Vec<(String, i64)> = sqlx::query_as(&format!(
+        "SELECT logical_date, SUM(worked_minutes) FROM (
+            SELECT {} AS logical_date, {} AS worked_minutes
+            FROM time_blocks tb
+            WHERE tb.workspace_id = ?1 AND tb.status IN ('DONE', 'CONTINUED') AND tb.start_time >= ?2 AND tb.start_time <= ?3
+         )
+         GROUP BY logical_date
+         ORDER BY logical_date ASC",
+        logical_date_sql("?4"),
+        WORKED_MINUTES_SQL
+    ))
+    .bind(workspace_id)
+    .bind(start_time)
+    .bind(end_time)
+    .bind(day_start_time)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
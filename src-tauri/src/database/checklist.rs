@@ -0,0 +1,99 @@
+use sqlx::SqlitePool;
+use crate::domain::{ChecklistItem, ChecklistItemInput, UpdateChecklistItemInput};
+use crate::domain::Result;
+
+pub const CREATE_TASK_CHECKLIST_ITEMS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS task_checklist_items (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        task_id INTEGER NOT NULL,
+        title TEXT NOT NULL,
+        position INTEGER NOT NULL DEFAULT 0,
+        is_checked BOOLEAN NOT NULL DEFAULT 0,
+        estimated_minutes INTEGER,
+        checked_at TEXT,
+        FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+    );
+";
+
+/// Checked share of a task's checklist in percent, NULL when the task has no items; assumes alias `tb`.
+pub const CHECKLIST_PROGRESS_SQL: &str =
+    "(SELECT SUM(ci.is_checked) * 100 / COUNT(*) FROM task_checklist_items ci WHERE ci.task_id = tb.task_id)";
+
+/// Checked item titles of the task, newline separated, attached only to its last DONE block; assumes alias `tb`.
+pub const COMPLETED_STEPS_SQL: &str =
+    "CASE WHEN tb.id = (SELECT MAX(d.id) FROM time_blocks d WHERE d.task_id = tb.task_id AND d.status = 'DONE')
+     THEN (SELECT GROUP_CONCAT(ci.title, char(10) ORDER BY ci.position, ci.id) FROM task_checklist_items ci WHERE ci.task_id = tb.task_id AND ci.is_checked = 1)
+     END";
+
+pub async fn get_checklist(pool: &SqlitePool, task_id: i64) -> Result<Vec<ChecklistItem>> {
+    let items = sqlx::query_as::<_, ChecklistItem>(
+        "SELECT * FROM task_checklist_items WHERE task_id = ?1 ORDER BY position ASC, id ASC"
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(items)
+}
+
+pub async fn get_checklist_item(pool: &SqlitePool, id: i64) -> Result<Option<ChecklistItem>> {
+    let item = sqlx::query_as::<_, ChecklistItem>("SELECT * FROM task_checklist_items WHERE id = ?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(item)
+}
+
+pub async fn create_checklist_item(pool: &SqlitePool, input: &ChecklistItemInput) -> Result<i64> {
+    let result = sqlx::query(
+        "INSERT INTO task_checklist_items (task_id, title, position, estimated_minutes)
+         VALUES (?1, ?2, (SELECT COALESCE(MAX(position), -1) + 1 FROM task_checklist_items WHERE task_id = ?1), ?3)"
+    )
+    .bind(input.task_id)
+    .bind(&input.title)
+    .bind(input.estimated_minutes)
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn update_checklist_item(pool: &SqlitePool, input: &UpdateChecklistItemInput) -> Result<()> {
+    sqlx::query("UPDATE task_checklist_items SET title = ?1, estimated_minutes = ?2 WHERE id = ?3")
+        .bind(&input.title)
+        .bind(input.estimated_minutes)
+        .bind(input.id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn set_checklist_item_checked(pool: &SqlitePool, id: i64, checked_at: Option<&str>) -> Result<()> {
+    sqlx::query("UPDATE task_checklist_items SET is_checked = ?1, checked_at = ?2 WHERE id = ?3")
+        .bind(checked_at.is_some())
+        .bind(checked_at)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_checklist_item(pool: &SqlitePool, id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM task_checklist_items WHERE id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn reorder_checklist(pool: &SqlitePool, task_id: i64, item_ids: &[i64]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    for (i, &id) in item_ids.iter().enumerate() {
+        sqlx::query("UPDATE task_checklist_items SET position = ?1 WHERE id = ?2 AND task_id = ?3")
+            .bind(i as i64)
+            .bind(id)
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
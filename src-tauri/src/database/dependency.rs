@@ -0,0 +1,81 @@
+use sqlx::{SqlitePool, SqliteConnection};
+use crate::domain::TaskDependency;
+use crate::domain::Result;
+
+pub const CREATE_TASK_DEPENDENCIES_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS task_dependencies (
+        task_id INTEGER NOT NULL,
+        blocked_by_task_id INTEGER NOT NULL,
+        PRIMARY KEY (task_id, blocked_by_task_id),
+        FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
+        FOREIGN KEY (blocked_by_task_id) REFERENCES tasks (id) ON DELETE CASCADE
+    );
+";
+
+/// 1 while any prerequisite of `t` is unscheduled or still has unfinished blocks; assumes alias `t` for tasks.
+pub const IS_BLOCKED_SQL: &str =
+    "EXISTS (SELECT 1 FROM task_dependencies d WHERE d.task_id = t.id AND (
+        NOT EXISTS (SELECT 1 FROM time_blocks p WHERE p.task_id = d.blocked_by_task_id)
+        OR EXISTS (SELECT 1 FROM time_blocks p WHERE p.task_id = d.blocked_by_task_id AND p.status != 'DONE')))";
+
+pub async fn get_task_dependencies(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<TaskDependency>> {
+    let list = sqlx::query_as::<_, TaskDependency>(
+        "SELECT d.task_id, d.blocked_by_task_id FROM task_dependencies d
+         JOIN tasks t ON d.task_id = t.id
+         WHERE t.workspace_id = ?1
+         ORDER BY d.task_id ASC, d.blocked_by_task_id ASC"
+    )
+    .bind(workspace_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(list)
+}
+
+pub async fn add_task_dependency(pool: &SqlitePool, task_id: i64, blocked_by_task_id: i64) -> Result<()> {
+    sqlx::query("INSERT OR IGNORE INTO task_dependencies (task_id, blocked_by_task_id) VALUES (?1, ?2)")
+        .bind(task_id)
+        .bind(blocked_by_task_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Whether an unfinished block of `task_id` already starts before an unfinished block of `blocked_by_task_id`.
+pub async fn is_scheduled_before(pool: &SqlitePool, task_id: i64, blocked_by_task_id: i64) -> Result<bool> {
+    let before: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM time_blocks a JOIN time_blocks b ON b.task_id = ?2
+         WHERE a.task_id = ?1 AND a.status != 'DONE' AND b.status != 'DONE' AND a.start_time < b.start_time)"
+    )
+    .bind(task_id)
+    .bind(blocked_by_task_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(before)
+}
+
+/// The titles of the first dependent and prerequisite, among the dependencies touching `task_id`, where an
+/// open block of the dependent starts before an open block of the prerequisite (the order `reorder_blocks` rejects).
+pub async fn find_order_conflict(conn: &mut SqliteConnection, task_id: i64) -> Result<Option<(String, String)>> {
+    let conflict = sqlx::query_as(
+        "SELECT dt.title, pt.title FROM task_dependencies d
+         JOIN tasks dt ON dt.id = d.task_id
+         JOIN tasks pt ON pt.id = d.blocked_by_task_id
+         WHERE (d.task_id = ?1 OR d.blocked_by_task_id = ?1) AND EXISTS (
+             SELECT 1 FROM time_blocks a JOIN time_blocks b ON b.task_id = d.blocked_by_task_id
+             WHERE a.task_id = d.task_id AND a.status NOT IN ('DONE', 'BREAK') AND b.status NOT IN ('DONE', 'BREAK') AND a.start_time < b.start_time)
+         LIMIT 1"
+    )
+    .bind(task_id)
+    .fetch_optional(conn)
+    .await?;
+    Ok(conflict)
+}
+
+pub async fn remove_task_dependency(pool: &SqlitePool, task_id: i64, blocked_by_task_id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM task_dependencies WHERE task_id = ?1 AND blocked_by_task_id = ?2")
+        .bind(task_id)
+        .bind(blocked_by_task_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
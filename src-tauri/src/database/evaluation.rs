@@ -0,0 +1,57 @@
+use sqlx::SqlitePool;
+use crate::domain::{Achievement, Competency, CompetencyInput};
+use crate::domain::Result;
+
+pub const CREATE_COMPETENCIES_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS competencies (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        description TEXT,
+        position INTEGER NOT NULL DEFAULT 0,
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE
+    );
+";
+
+pub async fn get_competencies(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<Competency>> {
+    let list = sqlx::query_as::<_, Competency>("SELECT * FROM competencies WHERE workspace_id = ?1 ORDER BY position ASC, id ASC")
+        .bind(workspace_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(list)
+}
+
+/// Replaces the whole framework of a workspace, keeping the given order.
+pub async fn replace_competencies(pool: &SqlitePool, workspace_id: i64, competencies: &[CompetencyInput]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM competencies WHERE workspace_id = ?1")
+        .bind(workspace_id)
+        .execute(&mut *tx)
+        .await?;
+    for (position, competency) in competencies.iter().enumerate() {
+        sqlx::query("INSERT INTO competencies (workspace_id, name, description, position) VALUES (?1, ?2, ?3, ?4)")
+            .bind(workspace_id)
+            .bind(&competency.name)
+            .bind(&competency.description)
+            .bind(position as i64)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Saved achievements of other types created within `start_time..=end_time`, oldest first.
+/// Date labels are free-form, so the creation time is what places a report in a period.
+pub async fn get_achievements_in_range(pool: &SqlitePool, workspace_id: i64, start_time: &str, end_time: &str, exclude_type: &str) -> Result<Vec<Achievement>> {
+    let list = sqlx::query_as::<_, Achievement>(
+        "SELECT * FROM achievements WHERE workspace_id = ?1 AND created_at >= ?2 AND created_at <= ?3 AND achievement_type != ?4 ORDER BY created_at ASC, id ASC"
+    )
+    .bind(workspace_id)
+    .bind(start_time)
+    .bind(end_time)
+    .bind(exclude_type)
+    .fetch_all(pool)
+    .await?;
+    Ok(list)
+}
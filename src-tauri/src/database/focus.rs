@@ -0,0 +1,90 @@
+use sqlx::SqlitePool;
+use crate::domain::FocusSession;
+use crate::domain::Result;
+
+pub const CREATE_FOCUS_SESSIONS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS focus_sessions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER NOT NULL,
+        block_id INTEGER,
+        task_id INTEGER,
+        title TEXT NOT NULL,
+        started_at TEXT NOT NULL,
+        ended_at TEXT,
+        work_minutes INTEGER NOT NULL,
+        break_minutes INTEGER NOT NULL,
+        status TEXT NOT NULL DEFAULT 'RUNNING',
+        interruptions INTEGER NOT NULL DEFAULT 0,
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE,
+        FOREIGN KEY (block_id) REFERENCES time_blocks (id) ON DELETE SET NULL,
+        FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE SET NULL
+    );
+";
+
+/// Completed focus sessions of the block aliased `tb`.
+pub const BLOCK_FOCUS_SESSIONS_SQL: &str =
+    "(SELECT COUNT(*) FROM focus_sessions fs WHERE fs.block_id = tb.id AND fs.status = 'COMPLETED')";
+
+pub async fn create_session(pool: &SqlitePool, session: &FocusSession) -> Result<i64> {
+    let result = sqlx::query(
+        "INSERT INTO focus_sessions (workspace_id, block_id, task_id, title, started_at, work_minutes, break_minutes, status) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'RUNNING')"
+    )
+    .bind(session.workspace_id)
+    .bind(session.block_id)
+    .bind(session.task_id)
+    .bind(&session.title)
+    .bind(&session.started_at)
+    .bind(session.work_minutes)
+    .bind(session.break_minutes)
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn get_session(pool: &SqlitePool, id: i64) -> Result<Option<FocusSession>> {
+    let session = sqlx::query_as::<_, FocusSession>("SELECT * FROM focus_sessions WHERE id = ?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(session)
+}
+
+/// The running session of a workspace, or of every workspace when `workspace_id` is None.
+pub async fn get_running_sessions(pool: &SqlitePool, workspace_id: Option<i64>) -> Result<Vec<FocusSession>> {
+    let sessions = sqlx::query_as::<_, FocusSession>("SELECT * FROM focus_sessions WHERE status = 'RUNNING' AND (?1 IS NULL OR workspace_id = ?1) ORDER BY started_at ASC")
+        .bind(workspace_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(sessions)
+}
+
+pub async fn add_interruption(pool: &SqlitePool, id: i64) -> Result<()> {
+    sqlx::query("UPDATE focus_sessions SET interruptions = interruptions + 1 WHERE id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn finish_session(pool: &SqlitePool, id: i64, status: &str, ended_at: &str) -> Result<()> {
+    sqlx::query("UPDATE focus_sessions SET status = ?1, ended_at = ?2 WHERE id = ?3")
+        .bind(status)
+        .bind(ended_at)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Finished sessions started within `start_time..=end_time`.
+pub async fn get_finished_sessions_in_range(pool: &SqlitePool, workspace_id: i64, start_time: &str, end_time: &str) -> Result<Vec<FocusSession>> {
+    let sessions = sqlx::query_as::<_, FocusSession>(
+        "SELECT * FROM focus_sessions WHERE workspace_id = ?1 AND status != 'RUNNING' AND started_at >= ?2 AND started_at <= ?3 ORDER BY started_at ASC"
+    )
+    .bind(workspace_id)
+    .bind(start_time)
+    .bind(end_time)
+    .fetch_all(pool)
+    .await?;
+    Ok(sessions)
+}
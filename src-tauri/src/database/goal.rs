@@ -0,0 +1,209 @@
+use sqlx::SqlitePool;
+use crate::domain::{Goal, GoalInput, KeyResult, KeyResultInput, KeyResultCheckIn, CheckInInput};
+use crate::domain::Result;
+use crate::database::timeline::WORKED_MINUTES_SQL;
+
+pub const CREATE_GOALS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS goals (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER NOT NULL,
+        title TEXT NOT NULL,
+        description TEXT,
+        period_start TEXT NOT NULL,
+        period_end TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE
+    );
+";
+
+pub const CREATE_KEY_RESULTS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS key_results (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        goal_id INTEGER NOT NULL,
+        title TEXT NOT NULL,
+        metric TEXT NOT NULL DEFAULT 'TIME',
+        target_value REAL NOT NULL,
+        unit TEXT,
+        position INTEGER NOT NULL DEFAULT 0,
+        FOREIGN KEY (goal_id) REFERENCES goals (id) ON DELETE CASCADE
+    );
+";
+
+pub const CREATE_KEY_RESULT_CHECK_INS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS key_result_check_ins (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        key_result_id INTEGER NOT NULL,
+        value REAL NOT NULL,
+        note TEXT,
+        checked_at TEXT NOT NULL,
+        FOREIGN KEY (key_result_id) REFERENCES key_results (id) ON DELETE CASCADE
+    );
+";
+
+pub async fn get_goals(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<Goal>> {
+    let goals = sqlx::query_as::<_, Goal>("SELECT * FROM goals WHERE workspace_id = ?1 ORDER BY period_end DESC, id ASC")
+        .bind(workspace_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(goals)
+}
+
+pub async fn get_goal(pool: &SqlitePool, id: i64) -> Result<Option<Goal>> {
+    let goal = sqlx::query_as::<_, Goal>("SELECT * FROM goals WHERE id = ?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(goal)
+}
+
+pub async fn create_goal(pool: &SqlitePool, input: &GoalInput, created_at: &str) -> Result<i64> {
+    let result = sqlx::query("INSERT INTO goals (workspace_id, title, description, period_start, period_end, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)")
+        .bind(input.workspace_id)
+        .bind(&input.title)
+        .bind(&input.description)
+        .bind(&input.period_start)
+        .bind(&input.period_end)
+        .bind(created_at)
+        .execute(pool)
+        .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn update_goal(pool: &SqlitePool, id: i64, input: &GoalInput) -> Result<()> {
+    sqlx::query("UPDATE goals SET title = ?1, description = ?2, period_start = ?3, period_end = ?4 WHERE id = ?5")
+        .bind(&input.title)
+        .bind(&input.description)
+        .bind(&input.period_start)
+        .bind(&input.period_end)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_goal(pool: &SqlitePool, id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM goals WHERE id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Key results of a goal with their linked work and check-in value, aggregated in one query.
+/// Linked minutes are the worked minutes of DONE blocks starting within `start_time..=end_time`;
+/// `current_value` adds the logged minutes for `TIME` and is the latest check-in for `NUMBER`.
+pub async fn get_key_results_with_work(pool: &SqlitePool, goal_id: i64, start_time: &str, end_time: &str) -> Result<Vec<KeyResult>> {
+    let key_results = sqlx::query_as::<_, KeyResult>(&format!(
+        "SELECT kr.*,
+            COALESCE(lt.task_count, 0) AS linked_task_count,
+            COALESCE(lw.minutes, 0) AS linked_minutes,
+            CASE WHEN kr.metric = 'TIME' THEN COALESCE(lw.minutes, 0) + COALESCE(ci.total, 0)
+                 ELSE COALESCE((SELECT c.value FROM key_result_check_ins c WHERE c.key_result_id = kr.id ORDER BY c.checked_at DESC, c.id DESC LIMIT 1), 0)
+            END AS current_value
+         FROM key_results kr
+         LEFT JOIN (SELECT key_result_id, COUNT(*) AS task_count FROM tasks WHERE key_result_id IS NOT NULL GROUP BY key_result_id) lt ON lt.key_result_id = kr.id
+         LEFT JOIN (
+            SELECT t.key_result_id, SUM({}) AS minutes FROM time_blocks tb JOIN tasks t ON tb.task_id = t.id
+            WHERE t.key_result_id IN (SELECT id FROM key_results WHERE goal_id = ?1) AND tb.status = 'DONE' AND tb.start_time >= ?2 AND tb.start_time <= ?3
+            GROUP BY t.key_result_id
+         ) lw ON lw.key_result_id = kr.id
+         LEFT JOIN (SELECT key_result_id, SUM(value) AS total FROM key_result_check_ins GROUP BY key_result_id) ci ON ci.key_result_id = kr.id
+         WHERE kr.goal_id = ?1
+         ORDER BY kr.position ASC, kr.id ASC",
+        WORKED_MINUTES_SQL
+    ))
+    .bind(goal_id)
+    .bind(start_time)
+    .bind(end_time)
+    .fetch_all(pool)
+    .await?;
+    Ok(key_results)
+}
+
+pub async fn get_key_result(pool: &SqlitePool, id: i64) -> Result<Option<KeyResult>> {
+    let key_result = sqlx::query_as::<_, KeyResult>("SELECT * FROM key_results WHERE id = ?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(key_result)
+}
+
+pub async fn create_key_result(pool: &SqlitePool, input: &KeyResultInput) -> Result<i64> {
+    let result = sqlx::query(
+        "INSERT INTO key_results (goal_id, title, metric, target_value, unit, position)
+         VALUES (?1, ?2, ?3, ?4, ?5, (SELECT COALESCE(MAX(position), -1) + 1 FROM key_results WHERE goal_id = ?1))",
+    )
+    .bind(input.goal_id)
+    .bind(&input.title)
+    .bind(&input.metric)
+    .bind(input.target_value)
+    .bind(&input.unit)
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn update_key_result(pool: &SqlitePool, id: i64, input: &KeyResultInput) -> Result<()> {
+    sqlx::query("UPDATE key_results SET title = ?1, metric = ?2, target_value = ?3, unit = ?4 WHERE id = ?5")
+        .bind(&input.title)
+        .bind(&input.metric)
+        .bind(input.target_value)
+        .bind(&input.unit)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_key_result(pool: &SqlitePool, id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM key_results WHERE id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn add_check_in(pool: &SqlitePool, input: &CheckInInput, checked_at: &str) -> Result<i64> {
+    let result = sqlx::query("INSERT INTO key_result_check_ins (key_result_id, value, note, checked_at) VALUES (?1, ?2, ?3, ?4)")
+        .bind(input.key_result_id)
+        .bind(input.value)
+        .bind(&input.note)
+        .bind(checked_at)
+        .execute(pool)
+        .await?;
+    Ok(result.last_insert_rowid())
+}
+
+/// Newest first.
+pub async fn get_check_ins(pool: &SqlitePool, key_result_id: i64) -> Result<Vec<KeyResultCheckIn>> {
+    let check_ins = sqlx::query_as::<_, KeyResultCheckIn>("SELECT * FROM key_result_check_ins WHERE key_result_id = ?1 ORDER BY checked_at DESC, id DESC")
+        .bind(key_result_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(check_ins)
+}
+
+pub async fn delete_check_in(pool: &SqlitePool, id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM key_result_check_ins WHERE id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn has_check_ins(pool: &SqlitePool, key_result_id: i64) -> Result<bool> {
+    let (exists,): (bool,) = sqlx::query_as("SELECT EXISTS (SELECT 1 FROM key_result_check_ins WHERE key_result_id = ?1)")
+        .bind(key_result_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(exists)
+}
+
+pub async fn set_task_key_result(pool: &SqlitePool, task_id: i64, key_result_id: Option<i64>) -> Result<()> {
+    sqlx::query("UPDATE tasks SET key_result_id = ?1 WHERE id = ?2")
+        .bind(key_result_id)
+        .bind(task_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
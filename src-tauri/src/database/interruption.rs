@@ -0,0 +1,54 @@
+use sqlx::{SqlitePool, SqliteConnection};
+use crate::domain::Interruption;
+use crate::domain::Result;
+
+pub const CREATE_INTERRUPTIONS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS interruptions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER NOT NULL,
+        interrupting_task_id INTEGER,
+        interrupting_title TEXT NOT NULL,
+        interrupted_task_id INTEGER,
+        interrupted_title TEXT NOT NULL,
+        occurred_at TEXT NOT NULL,
+        lost_minutes INTEGER NOT NULL,
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE,
+        FOREIGN KEY (interrupting_task_id) REFERENCES tasks (id) ON DELETE SET NULL,
+        FOREIGN KEY (interrupted_task_id) REFERENCES tasks (id) ON DELETE SET NULL
+    );
+";
+
+pub async fn record_interruption(conn: &mut SqliteConnection, interruption: &Interruption) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO interruptions (workspace_id, interrupting_task_id, interrupting_title, interrupted_task_id, interrupted_title, occurred_at, lost_minutes) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+    )
+    .bind(interruption.workspace_id)
+    .bind(interruption.interrupting_task_id)
+    .bind(&interruption.interrupting_title)
+    .bind(interruption.interrupted_task_id)
+    .bind(&interruption.interrupted_title)
+    .bind(&interruption.occurred_at)
+    .bind(interruption.lost_minutes)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Interruptions within `start_time..=end_time`, with the interrupting task's project and label.
+pub async fn get_interruptions_in_range(pool: &SqlitePool, workspace_id: i64, start_time: &str, end_time: &str) -> Result<Vec<Interruption>> {
+    let list = sqlx::query_as::<_, Interruption>(
+        "SELECT i.*, p.name AS project_name, l.name AS label_name
+         FROM interruptions i
+         LEFT JOIN tasks t ON i.interrupting_task_id = t.id
+         LEFT JOIN projects p ON t.project_id = p.id
+         LEFT JOIN labels l ON t.label_id = l.id
+         WHERE i.workspace_id = ?1 AND i.occurred_at >= ?2 AND i.occurred_at <= ?3
+         ORDER BY i.occurred_at ASC, i.id ASC"
+    )
+    .bind(workspace_id)
+    .bind(start_time)
+    .bind(end_time)
+    .fetch_all(pool)
+    .await?;
+    Ok(list)
+}
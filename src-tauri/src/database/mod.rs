@@ -1,5 +1,110 @@
+use sqlx::SqlitePool;
+
 pub mod user;
 pub mod workspace;
 pub mod timeline;
 pub mod achievement;
 pub mod gemini;
+pub mod analytics;
+pub mod checklist;
+pub mod dependency;
+pub mod tag;
+pub mod search;
+pub mod rollover;
+pub mod notification;
+pub mod overrun;
+pub mod focus;
+pub mod interruption;
+pub mod retrospective;
+pub mod goal;
+pub mod standup;
+pub mod evaluation;
+
+/// Creates and upgrades the schema. Every step is idempotent, so it runs on each start.
+pub async fn run_migrations(pool: &SqlitePool) {
+    sqlx::query("CREATE TABLE IF NOT EXISTS users (id INTEGER PRIMARY KEY CHECK (id = 1), nickname TEXT NOT NULL, gemini_api_key TEXT, lang TEXT NOT NULL DEFAULT 'en', last_successful_model TEXT, is_notification_enabled BOOLEAN NOT NULL DEFAULT 0, is_free_user BOOLEAN NOT NULL DEFAULT 1, day_start_time TEXT NOT NULL DEFAULT '04:00')").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE users ADD COLUMN lang TEXT NOT NULL DEFAULT 'en'").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE users ADD COLUMN last_successful_model TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE users ADD COLUMN is_notification_enabled BOOLEAN NOT NULL DEFAULT 0").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE users ADD COLUMN is_free_user BOOLEAN NOT NULL DEFAULT 1").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE users ADD COLUMN day_start_time TEXT NOT NULL DEFAULT '04:00'").execute(pool).await.ok();
+    
+    sqlx::query("CREATE TABLE IF NOT EXISTS workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE workspaces ADD COLUMN rollover_policy TEXT NOT NULL DEFAULT 'INBOX'").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE workspaces ADD COLUMN last_rollover_date TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE workspaces ADD COLUMN overrun_policy TEXT NOT NULL DEFAULT 'FLAG'").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE workspaces ADD COLUMN overrun_extend_minutes INTEGER NOT NULL DEFAULT 15").execute(pool).await.ok();
+    sqlx::query("CREATE TABLE IF NOT EXISTS unplugged_times (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, label TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE)").execute(pool).await.ok();
+    sqlx::query(workspace::CREATE_PROJECTS_TABLE).execute(pool).await.ok();
+    sqlx::query(workspace::CREATE_LABELS_TABLE).execute(pool).await.ok();
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, estimated_minutes INTEGER NOT NULL DEFAULT 0, FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE)").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE tasks ADD COLUMN estimated_minutes INTEGER NOT NULL DEFAULT 0").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE tasks ADD COLUMN project_id INTEGER REFERENCES projects (id) ON DELETE SET NULL").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE tasks ADD COLUMN label_id INTEGER REFERENCES labels (id) ON DELETE SET NULL").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE tasks ADD COLUMN position INTEGER NOT NULL DEFAULT 0").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE tasks ADD COLUMN due_at TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE tasks ADD COLUMN is_at_risk BOOLEAN NOT NULL DEFAULT 0").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE tasks ADD COLUMN priority INTEGER NOT NULL DEFAULT 2").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE tasks ADD COLUMN key_result_id INTEGER REFERENCES key_results (id) ON DELETE SET NULL").execute(pool).await.ok();
+    if let Err(e) = workspace::migrate_workspace_scoped_catalogs(pool).await {
+        eprintln!("Failed to scope projects and labels to workspaces: {}", e);
+    }
+    sqlx::query("ALTER TABLE projects ADD COLUMN description TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE projects ADD COLUMN role TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE projects ADD COLUMN tech_stack TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE projects ADD COLUMN link TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE projects ADD COLUMN start_date TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE projects ADD COLUMN end_date TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE projects ADD COLUMN status TEXT NOT NULL DEFAULT 'ACTIVE'").execute(pool).await.ok();
+    sqlx::query(workspace::CREATE_PROJECTS_SCOPE_INDEX).execute(pool).await.ok();
+    sqlx::query(workspace::CREATE_LABELS_SCOPE_INDEX).execute(pool).await.ok();
+    sqlx::query("CREATE TABLE IF NOT EXISTS time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL, review_memo TEXT, is_urgent BOOLEAN NOT NULL DEFAULT 0, FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE, FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE)").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE time_blocks ADD COLUMN is_urgent BOOLEAN NOT NULL DEFAULT 0").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE time_blocks ADD COLUMN planning_memo TEXT").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE time_blocks ADD COLUMN overrun_minutes INTEGER NOT NULL DEFAULT 0").execute(pool).await.ok();
+    sqlx::query(timeline::CREATE_BLOCK_INTERVALS_TABLE).execute(pool).await.ok();
+    sqlx::query(analytics::CREATE_PLAN_SNAPSHOTS_TABLE).execute(pool).await.ok();
+    sqlx::query(analytics::CREATE_PLAN_SNAPSHOT_BLOCKS_TABLE).execute(pool).await.ok();
+    sqlx::query(checklist::CREATE_TASK_CHECKLIST_ITEMS_TABLE).execute(pool).await.ok();
+    sqlx::query(dependency::CREATE_TASK_DEPENDENCIES_TABLE).execute(pool).await.ok();
+    sqlx::query(tag::CREATE_TAGS_TABLE).execute(pool).await.ok();
+    sqlx::query(tag::CREATE_TASK_TAGS_TABLE).execute(pool).await.ok();
+    sqlx::query(rollover::CREATE_ROLLOVER_REPORTS_TABLE).execute(pool).await.ok();
+    sqlx::query(rollover::CREATE_ROLLOVER_REPORT_ITEMS_TABLE).execute(pool).await.ok();
+    sqlx::query(notification::CREATE_NOTIFICATION_SETTINGS_TABLE).execute(pool).await.ok();
+    sqlx::query(focus::CREATE_FOCUS_SESSIONS_TABLE).execute(pool).await.ok();
+    sqlx::query(interruption::CREATE_INTERRUPTIONS_TABLE).execute(pool).await.ok();
+    sqlx::query(retrospective::CREATE_RETROSPECTIVES_TABLE).execute(pool).await.ok();
+    sqlx::query(goal::CREATE_GOALS_TABLE).execute(pool).await.ok();
+    sqlx::query(goal::CREATE_KEY_RESULTS_TABLE).execute(pool).await.ok();
+    sqlx::query(goal::CREATE_KEY_RESULT_CHECK_INS_TABLE).execute(pool).await.ok();
+    sqlx::query(evaluation::CREATE_COMPETENCIES_TABLE).execute(pool).await.ok();
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS achievements (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, achievement_type TEXT NOT NULL, content TEXT NOT NULL, date_label TEXT NOT NULL, created_at TEXT NOT NULL, used_model TEXT, FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE)").execute(pool).await.ok();
+    sqlx::query("ALTER TABLE achievements ADD COLUMN used_model TEXT").execute(pool).await.ok();
+    sqlx::query(search::CREATE_SEARCH_INDEX_TABLE).execute(pool).await.ok();
+    for trigger in search::CREATE_SEARCH_TRIGGERS {
+        sqlx::query(trigger).execute(pool).await.ok();
+    }
+    if let Err(e) = search::backfill_search_index(pool).await {
+        eprintln!("Failed to build the search index: {}", e);
+    }
+
+    sqlx::query(gemini::CREATE_GEMINI_MODELS_TABLE).execute(pool).await.ok();
+    sqlx::query(gemini::CREATE_AI_USAGE_LOGS_TABLE).execute(pool).await.ok();
+
+    sqlx::query("DROP TABLE IF EXISTS recurring_tasks").execute(pool).await.ok();
+
+    // Normalization: Ensure all time blocks and unplugged times have :00 seconds
+    sqlx::query("UPDATE time_blocks SET start_time = strftime('%Y-%m-%dT%H:%M:00', start_time), end_time = strftime('%Y-%m-%dT%H:%M:00', end_time)").execute(pool).await.ok();
+    sqlx::query("UPDATE unplugged_times SET start_time = strftime('%H:%M', start_time), end_time = strftime('%H:%M', end_time)").execute(pool).await.ok();
+}
+
+/// An in-memory database with the full schema, for tests.
+#[cfg(test)]
+pub async fn test_pool() -> SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().connect("sqlite::memory:").await.unwrap();
+    run_migrations(&pool).await;
+    pool
+}
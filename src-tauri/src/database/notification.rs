@@ -0,0 +1,67 @@
+use sqlx::SqlitePool;
+use crate::domain::{NotificationSettings, TimeBlock};
+use crate::domain::Result;
+use crate::database::timeline::is_block_paused;
+
+pub const CREATE_NOTIFICATION_SETTINGS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS notification_settings (
+        workspace_id INTEGER PRIMARY KEY,
+        notify_at_start BOOLEAN NOT NULL DEFAULT 1,
+        lead_minutes INTEGER NOT NULL DEFAULT 5,
+        notify_at_end BOOLEAN NOT NULL DEFAULT 1,
+        overrun_interval_minutes INTEGER NOT NULL DEFAULT 15,
+        quiet_start TEXT,
+        quiet_end TEXT,
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE
+    );
+";
+
+pub async fn get_settings(pool: &SqlitePool, workspace_id: i64) -> Result<Option<NotificationSettings>> {
+    let settings = sqlx::query_as::<_, NotificationSettings>("SELECT * FROM notification_settings WHERE workspace_id = ?1")
+        .bind(workspace_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(settings)
+}
+
+pub async fn save_settings(pool: &SqlitePool, settings: &NotificationSettings) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO notification_settings (workspace_id, notify_at_start, lead_minutes, notify_at_end, overrun_interval_minutes, quiet_start, quiet_end)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT(workspace_id) DO UPDATE SET
+            notify_at_start = excluded.notify_at_start,
+            lead_minutes = excluded.lead_minutes,
+            notify_at_end = excluded.notify_at_end,
+            overrun_interval_minutes = excluded.overrun_interval_minutes,
+            quiet_start = excluded.quiet_start,
+            quiet_end = excluded.quiet_end",
+    )
+    .bind(settings.workspace_id)
+    .bind(settings.notify_at_start)
+    .bind(settings.lead_minutes)
+    .bind(settings.notify_at_end)
+    .bind(settings.overrun_interval_minutes)
+    .bind(&settings.quiet_start)
+    .bind(&settings.quiet_end)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// The NOW block (with `is_paused` filled in) and the first WILL block starting after `after`.
+pub async fn get_watched_blocks(pool: &SqlitePool, workspace_id: i64, after: &str) -> Result<(Option<TimeBlock>, Option<TimeBlock>)> {
+    let mut now_block = sqlx::query_as::<_, TimeBlock>("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status = 'NOW' ORDER BY start_time ASC LIMIT 1")
+        .bind(workspace_id)
+        .fetch_optional(pool)
+        .await?;
+    if let Some(block) = now_block.as_mut() {
+        block.is_paused = is_block_paused(pool, block.id).await?;
+    }
+
+    let next_block = sqlx::query_as::<_, TimeBlock>("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status = 'WILL' AND start_time > ?2 ORDER BY start_time ASC LIMIT 1")
+        .bind(workspace_id)
+        .bind(after)
+        .fetch_optional(pool)
+        .await?;
+    Ok((now_block, next_block))
+}
@@ -0,0 +1,38 @@
+use sqlx::SqlitePool;
+use crate::domain::TimeBlock;
+use crate::domain::Result;
+
+/// NOW blocks, with the policy of their workspace, that reached their end by `now` and are not paused.
+pub async fn get_overdue_blocks(pool: &SqlitePool, now: &str) -> Result<Vec<(TimeBlock, String, i64)>> {
+    let blocks = sqlx::query_as::<_, TimeBlock>(
+        "SELECT tb.* FROM time_blocks tb
+         WHERE tb.status = 'NOW' AND tb.end_time <= ?1
+           AND COALESCE((SELECT ended_at IS NOT NULL FROM block_intervals WHERE block_id = tb.id ORDER BY started_at DESC, id DESC LIMIT 1), 0) = 0
+         ORDER BY tb.end_time ASC"
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    let mut overdue = Vec::new();
+    for block in blocks {
+        let policy: Option<(String, i64)> = sqlx::query_as("SELECT overrun_policy, overrun_extend_minutes FROM workspaces WHERE id = ?1")
+            .bind(block.workspace_id)
+            .fetch_optional(pool)
+            .await?;
+        if let Some((policy, extend_minutes)) = policy {
+            overdue.push((block, policy, extend_minutes));
+        }
+    }
+    Ok(overdue)
+}
+
+/// Raises the recorded overrun of a block; it never shrinks.
+pub async fn record_overrun(pool: &SqlitePool, block_id: i64, minutes: i64) -> Result<i64> {
+    let (recorded,): (i64,) = sqlx::query_as("UPDATE time_blocks SET overrun_minutes = MAX(overrun_minutes, ?1) WHERE id = ?2 RETURNING overrun_minutes")
+        .bind(minutes)
+        .bind(block_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(recorded)
+}
@@ -0,0 +1,81 @@
+use sqlx::SqlitePool;
+use crate::domain::Retrospective;
+use crate::domain::Result;
+
+pub const CREATE_RETROSPECTIVES_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS retrospectives (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER NOT NULL,
+        logical_date TEXT NOT NULL,
+        went_well TEXT,
+        blockers TEXT,
+        mood_score INTEGER,
+        energy_score INTEGER,
+        learnings TEXT,
+        tomorrow_focus TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        UNIQUE (workspace_id, logical_date),
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE
+    );
+";
+
+/// Inserts the day's entry or replaces its fields, keeping the original `created_at`.
+pub async fn save_retrospective(pool: &SqlitePool, retro: &Retrospective) -> Result<Retrospective> {
+    let saved = sqlx::query_as::<_, Retrospective>(
+        "INSERT INTO retrospectives (workspace_id, logical_date, went_well, blockers, mood_score, energy_score, learnings, tomorrow_focus, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9)
+         ON CONFLICT(workspace_id, logical_date) DO UPDATE SET
+            went_well = excluded.went_well,
+            blockers = excluded.blockers,
+            mood_score = excluded.mood_score,
+            energy_score = excluded.energy_score,
+            learnings = excluded.learnings,
+            tomorrow_focus = excluded.tomorrow_focus,
+            updated_at = excluded.updated_at
+         RETURNING *",
+    )
+    .bind(retro.workspace_id)
+    .bind(&retro.logical_date)
+    .bind(&retro.went_well)
+    .bind(&retro.blockers)
+    .bind(retro.mood_score)
+    .bind(retro.energy_score)
+    .bind(&retro.learnings)
+    .bind(&retro.tomorrow_focus)
+    .bind(&retro.updated_at)
+    .fetch_one(pool)
+    .await?;
+    Ok(saved)
+}
+
+pub async fn get_retrospective(pool: &SqlitePool, workspace_id: i64, logical_date: &str) -> Result<Option<Retrospective>> {
+    let retro = sqlx::query_as::<_, Retrospective>("SELECT * FROM retrospectives WHERE workspace_id = ?1 AND logical_date = ?2")
+        .bind(workspace_id)
+        .bind(logical_date)
+        .fetch_optional(pool)
+        .await?;
+    Ok(retro)
+}
+
+/// Entries for the logical dates `start_date..=end_date`, oldest first.
+pub async fn get_retrospectives_in_range(pool: &SqlitePool, workspace_id: i64, start_date: &str, end_date: &str) -> Result<Vec<Retrospective>> {
+    let list = sqlx::query_as::<_, Retrospective>(
+        "SELECT * FROM retrospectives WHERE workspace_id = ?1 AND logical_date >= ?2 AND logical_date <= ?3 ORDER BY logical_date ASC"
+    )
+    .bind(workspace_id)
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await?;
+    Ok(list)
+}
+
+pub async fn delete_retrospective(pool: &SqlitePool, workspace_id: i64, logical_date: &str) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM retrospectives WHERE workspace_id = ?1 AND logical_date = ?2")
+        .bind(workspace_id)
+        .bind(logical_date)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,115 @@
+use sqlx::{SqlitePool, SqliteConnection};
+use crate::domain::{RolloverReport, RolloverItem};
+use crate::domain::Result;
+
+pub const CREATE_ROLLOVER_REPORTS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS rollover_reports (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER NOT NULL,
+        logical_date TEXT NOT NULL,
+        policy TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        acknowledged_at TEXT,
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE
+    );
+";
+
+pub const CREATE_ROLLOVER_REPORT_ITEMS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS rollover_report_items (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        report_id INTEGER NOT NULL,
+        task_id INTEGER,
+        title TEXT NOT NULL,
+        from_date TEXT NOT NULL,
+        minutes INTEGER NOT NULL,
+        action TEXT NOT NULL,
+        new_start TEXT,
+        FOREIGN KEY (report_id) REFERENCES rollover_reports (id) ON DELETE CASCADE,
+        FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE SET NULL
+    );
+";
+
+/// Rollover policy and the logical date of the last rollover of a workspace.
+pub async fn get_rollover_state(conn: &mut SqliteConnection, workspace_id: i64) -> Result<Option<(String, Option<String>)>> {
+    let row = sqlx::query_as("SELECT rollover_policy, last_rollover_date FROM workspaces WHERE id = ?1")
+        .bind(workspace_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+pub async fn set_last_rollover_date(conn: &mut SqliteConnection, workspace_id: i64, logical_date: &str) -> Result<()> {
+    sqlx::query("UPDATE workspaces SET last_rollover_date = ?1 WHERE id = ?2")
+        .bind(logical_date)
+        .bind(workspace_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+pub async fn create_report(conn: &mut SqliteConnection, workspace_id: i64, logical_date: &str, policy: &str, created_at: &str) -> Result<i64> {
+    let result = sqlx::query("INSERT INTO rollover_reports (workspace_id, logical_date, policy, created_at) VALUES (?1, ?2, ?3, ?4)")
+        .bind(workspace_id)
+        .bind(logical_date)
+        .bind(policy)
+        .bind(created_at)
+        .execute(&mut *conn)
+        .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn create_report_item(conn: &mut SqliteConnection, report_id: i64, item: &RolloverItem) -> Result<()> {
+    sqlx::query("INSERT INTO rollover_report_items (report_id, task_id, title, from_date, minutes, action, new_start) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)")
+        .bind(report_id)
+        .bind(item.task_id)
+        .bind(&item.title)
+        .bind(&item.from_date)
+        .bind(item.minutes)
+        .bind(&item.action)
+        .bind(&item.new_start)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// Newest reports first, with their items; only unconfirmed ones when `pending_only`.
+pub async fn get_reports(pool: &SqlitePool, workspace_id: i64, pending_only: bool) -> Result<Vec<RolloverReport>> {
+    let mut reports = sqlx::query_as::<_, RolloverReport>(
+        "SELECT * FROM rollover_reports WHERE workspace_id = ?1 AND (?2 = 0 OR acknowledged_at IS NULL) ORDER BY logical_date DESC, id DESC"
+    )
+    .bind(workspace_id)
+    .bind(pending_only)
+    .fetch_all(pool)
+    .await?;
+    for report in reports.iter_mut() {
+        report.items = get_report_items(pool, report.id).await?;
+    }
+    Ok(reports)
+}
+
+pub async fn get_report(pool: &SqlitePool, id: i64) -> Result<Option<RolloverReport>> {
+    let report = sqlx::query_as::<_, RolloverReport>("SELECT * FROM rollover_reports WHERE id = ?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    let Some(mut report) = report else { return Ok(None) };
+    report.items = get_report_items(pool, report.id).await?;
+    Ok(Some(report))
+}
+
+async fn get_report_items(pool: &SqlitePool, report_id: i64) -> Result<Vec<RolloverItem>> {
+    let items = sqlx::query_as::<_, RolloverItem>("SELECT * FROM rollover_report_items WHERE report_id = ?1 ORDER BY id ASC")
+        .bind(report_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(items)
+}
+
+pub async fn acknowledge_report(pool: &SqlitePool, id: i64, acknowledged_at: &str) -> Result<()> {
+    sqlx::query("UPDATE rollover_reports SET acknowledged_at = COALESCE(acknowledged_at, ?1) WHERE id = ?2")
+        .bind(acknowledged_at)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use sqlx::SqlitePool;
+use crate::domain::{SearchHit, SearchQuery};
+use crate::domain::Result;
+
+/// One FTS5 row per document. The rowid encodes the source as `ref_id * 4 + kind`
+/// (TASK 1, REVIEW 2, ACHIEVEMENT 3) so triggers can replace rows by rowid.
+pub const CREATE_SEARCH_INDEX_TABLE: &str = "
+    CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+        kind UNINDEXED,
+        ref_id UNINDEXED,
+        workspace_id UNINDEXED,
+        title,
+        body,
+        tokenize = 'unicode61 remove_diacritics 2'
+    );
+";
+
+pub const CREATE_SEARCH_TRIGGERS: [&str; 9] = [
+    "CREATE TRIGGER IF NOT EXISTS tasks_search_insert AFTER INSERT ON tasks BEGIN
+        INSERT INTO search_index (rowid, kind, ref_id, workspace_id, title, body)
+        VALUES (NEW.id * 4 + 1, 'TASK', NEW.id, NEW.workspace_id, NEW.title, COALESCE(NEW.planning_memo, ''));
+    END",
+    "CREATE TRIGGER IF NOT EXISTS tasks_search_update AFTER UPDATE OF title, planning_memo ON tasks BEGIN
+        DELETE FROM search_index WHERE rowid = OLD.id * 4 + 1;
+        INSERT INTO search_index (rowid, kind, ref_id, workspace_id, title, body)
+        VALUES (NEW.id * 4 + 1, 'TASK', NEW.id, NEW.workspace_id, NEW.title, COALESCE(NEW.planning_memo, ''));
+    END",
+    "CREATE TRIGGER IF NOT EXISTS tasks_search_delete AFTER DELETE ON tasks BEGIN
+        DELETE FROM search_index WHERE rowid = OLD.id * 4 + 1;
+    END",
+    "CREATE TRIGGER IF NOT EXISTS time_blocks_search_insert AFTER INSERT ON time_blocks WHEN COALESCE(NEW.review_memo, '') != '' BEGIN
+        INSERT INTO search_index (rowid, kind, ref_id, workspace_id, title, body)
+        VALUES (NEW.id * 4 + 2, 'REVIEW', NEW.id, NEW.workspace_id, NEW.title, NEW.review_memo);
+    END",
+    "CREATE TRIGGER IF NOT EXISTS time_blocks_search_update AFTER UPDATE OF title, review_memo ON time_blocks BEGIN
+        DELETE FROM search_index WHERE rowid = OLD.id * 4 + 2;
+        INSERT INTO search_index (rowid, kind, ref_id, workspace_id, title, body)
+        SELECT NEW.id * 4 + 2, 'REVIEW', NEW.id, NEW.workspace_id, NEW.title, NEW.review_memo WHERE COALESCE(NEW.review_memo, '') != '';
+    END",
+    "CREATE TRIGGER IF NOT EXISTS time_blocks_search_delete AFTER DELETE ON time_blocks BEGIN
+        DELETE FROM search_index WHERE rowid = OLD.id * 4 + 2;
+    END",
+    "CREATE TRIGGER IF NOT EXISTS achievements_search_insert AFTER INSERT ON achievements BEGIN
+        INSERT INTO search_index (rowid, kind, ref_id, workspace_id, title, body)
+        VALUES (NEW.id * 4 + 3, 'ACHIEVEMENT', NEW.id, NEW.workspace_id, NEW.date_label, NEW.content);
+    END",
+    "CREATE TRIGGER IF NOT EXISTS achievements_search_update AFTER UPDATE OF date_label, content ON achievements BEGIN
+        DELETE FROM search_index WHERE rowid = OLD.id * 4 + 3;
+        INSERT INTO search_index (rowid, kind, ref_id, workspace_id, title, body)
+        VALUES (NEW.id * 4 + 3, 'ACHIEVEMENT', NEW.id, NEW.workspace_id, NEW.date_label, NEW.content);
+    END",
+    "CREATE TRIGGER IF NOT EXISTS achievements_search_delete AFTER DELETE ON achievements BEGIN
+        DELETE FROM search_index WHERE rowid = OLD.id * 4 + 3;
+    END",
+];
+
+/// Fills an empty index from existing rows; databases created before the
+/// index existed are covered on first launch.
+pub async fn backfill_search_index(pool: &SqlitePool) -> Result<()> {
+    let indexed: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM search_index").fetch_one(pool).await?;
+    if indexed.0 > 0 {
+        return Ok(());
+    }
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "INSERT INTO search_index (rowid, kind, ref_id, workspace_id, title, body)
+         SELECT id * 4 + 1, 'TASK', id, workspace_id, title, COALESCE(planning_memo, '') FROM tasks",
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "INSERT INTO search_index (rowid, kind, ref_id, workspace_id, title, body)
+         SELECT id * 4 + 2, 'REVIEW', id, workspace_id, title, review_memo FROM time_blocks WHERE COALESCE(review_memo, '') != ''",
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "INSERT INTO search_index (rowid, kind, ref_id, workspace_id, title, body)
+         SELECT id * 4 + 3, 'ACHIEVEMENT', id, workspace_id, date_label, content FROM achievements",
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// `match_expr` must already be a valid FTS5 expression; the query's kinds are
+/// expected upper-cased. The bounds compare against the first block start
+/// (tasks), the block start (reviews) or `created_at` (achievements). Tasks
+/// that were never scheduled have no date, so any bound leaves them out.
+pub async fn search(
+    pool: &SqlitePool,
+    match_expr: &str,
+    query: &SearchQuery,
+    start_bound: Option<&str>,
+    end_bound: Option<&str>,
+    limit: i64,
+) -> Result<Vec<SearchHit>> {
+    let hits = sqlx::query_as::<_, SearchHit>(
+        "SELECT * FROM (
+            SELECT s.kind, s.ref_id, s.workspace_id, bt.id AS task_id,
+                highlight(search_index, 3, '<mark>', '</mark>') AS title,
+                snippet(search_index, 4, '<mark>', '</mark>', '…', 16) AS snippet,
+                CASE s.kind
+                    WHEN 'TASK' THEN (SELECT MIN(start_time) FROM time_blocks WHERE task_id = s.ref_id)
+                    WHEN 'REVIEW' THEN tb.start_time
+                    ELSE a.created_at
+                END AS occurred_at,
+                p.name AS project_name, l.name AS label_name,
+                bm25(search_index, 0.0, 0.0, 0.0, 5.0, 1.0) AS score,
+                bt.project_id, bt.label_id
+            FROM search_index s
+            LEFT JOIN time_blocks tb ON s.kind = 'REVIEW' AND tb.id = s.ref_id
+            LEFT JOIN achievements a ON s.kind = 'ACHIEVEMENT' AND a.id = s.ref_id
+            LEFT JOIN tasks bt ON bt.id = CASE s.kind WHEN 'TASK' THEN s.ref_id WHEN 'REVIEW' THEN tb.task_id END
+            LEFT JOIN projects p ON p.id = bt.project_id
+            LEFT JOIN labels l ON l.id = bt.label_id
+            WHERE search_index MATCH ?1 AND (?2 IS NULL OR s.workspace_id = ?2)
+         )
+         WHERE (?3 IS NULL OR project_id = ?3)
+           AND (?4 IS NULL OR label_id = ?4)
+           AND (?5 IS NULL OR occurred_at >= ?5)
+           AND (?6 IS NULL OR occurred_at <= ?6)
+           AND (?7 IS NULL OR kind IN (SELECT value FROM json_each(?7)))
+         ORDER BY score ASC
+         LIMIT ?8",
+    )
+    .bind(match_expr)
+    .bind(query.workspace_id)
+    .bind(query.project_id)
+    .bind(query.label_id)
+    .bind(start_bound)
+    .bind(end_bound)
+    .bind(query.kinds.as_ref().map(serde_json::to_string).transpose()?)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE projects (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER, name TEXT NOT NULL, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE labels (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER, name TEXT NOT NULL, color TEXT NOT NULL, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, project_id INTEGER, label_id INTEGER)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL, review_memo TEXT)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE achievements (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, achievement_type TEXT NOT NULL, content TEXT NOT NULL, date_label TEXT NOT NULL, created_at TEXT NOT NULL, used_model TEXT)").execute(&pool).await.unwrap();
+        sqlx::query(CREATE_SEARCH_INDEX_TABLE).execute(&pool).await.unwrap();
+        for trigger in CREATE_SEARCH_TRIGGERS {
+            sqlx::query(trigger).execute(&pool).await.unwrap();
+        }
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_search_index_follows_rows() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO projects (id, name, last_used) VALUES (1, 'will-done', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, planning_memo, project_id) VALUES (1, 1, 'Migration review', 'Check the sqlite upgrade', 1), (2, 1, 'Lunch', NULL, NULL), (3, 2, 'Migration plan', NULL, NULL)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status, review_memo) VALUES (1, 1, 1, 'Migration review', '2026-03-01T09:00:00', '2026-03-01T10:00:00', 'DONE', NULL), (2, 2, 1, 'Lunch', '2026-03-02T12:00:00', '2026-03-02T13:00:00', 'DONE', NULL)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO achievements (id, workspace_id, achievement_type, content, date_label, created_at) VALUES (1, 1, 'DAILY', 'Shipped the sqlite migration', '2026-03-01', '2026-03-01T22:00:00')").execute(&pool).await.unwrap();
+
+        let hits = search(&pool, "\"migration\"*", &SearchQuery { workspace_id: Some(1), ..Default::default() }, None, None, 10).await.unwrap();
+        assert_eq!(hits.len(), 2);
+        let task_hit = hits.iter().find(|h| h.kind == "TASK").unwrap();
+        assert_eq!(task_hit.title, "<mark>Migration</mark> review");
+        assert_eq!(task_hit.project_name.as_deref(), Some("will-done"));
+        assert_eq!(task_hit.occurred_at.as_deref(), Some("2026-03-01T09:00:00"));
+
+        // Review memos are indexed once written, and removed with their block.
+        sqlx::query("UPDATE time_blocks SET review_memo = 'Sqlite migration took longer than planned' WHERE id = 2").execute(&pool).await.unwrap();
+        let reviews = search(&pool, "\"longer\"*", &SearchQuery { kinds: Some(vec!["REVIEW".to_string()]), ..Default::default() }, None, None, 10).await.unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].task_id, Some(2));
+        assert!(reviews[0].snippet.contains("<mark>longer</mark>"));
+        sqlx::query("DELETE FROM time_blocks WHERE id = 2").execute(&pool).await.unwrap();
+        assert!(search(&pool, "\"longer\"*", &SearchQuery::default(), None, None, 10).await.unwrap().is_empty());
+
+        let by_project = search(&pool, "\"migration\"*", &SearchQuery { project_id: Some(1), ..Default::default() }, None, None, 10).await.unwrap();
+        assert_eq!(by_project.len(), 1);
+        let dated = search(&pool, "\"migration\"*", &SearchQuery::default(), Some("2026-03-01T12:00:00"), None, 10).await.unwrap();
+        assert_eq!(dated.len(), 1);
+        assert_eq!(dated[0].kind, "ACHIEVEMENT");
+
+        // An inbox task has no block to date it: found without bounds, left out with any bound
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (4, 1, 'Migration notes')").execute(&pool).await.unwrap();
+        let undated = search(&pool, "\"notes\"*", &SearchQuery::default(), None, None, 10).await.unwrap();
+        assert_eq!((undated.len(), undated[0].occurred_at.as_deref()), (1, None));
+        assert!(search(&pool, "\"notes\"*", &SearchQuery::default(), None, Some("2026-12-31T23:59:00"), 10).await.unwrap().is_empty());
+
+        sqlx::query("UPDATE tasks SET title = 'Upgrade review' WHERE id = 1").execute(&pool).await.unwrap();
+        assert!(search(&pool, "\"upgrade\"*", &SearchQuery { workspace_id: Some(1), kinds: Some(vec!["TASK".to_string()]), ..Default::default() }, None, None, 10).await.unwrap().len() == 1);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_search_index() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Quarterly report')").execute(&pool).await.unwrap();
+        sqlx::query("DELETE FROM search_index").execute(&pool).await.unwrap();
+
+        backfill_search_index(&pool).await.unwrap();
+        backfill_search_index(&pool).await.unwrap();
+        let hits = search(&pool, "\"quarter\"*", &SearchQuery::default(), None, None, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+    }
+}
@@ -0,0 +1,39 @@
+use sqlx::SqlitePool;
+use crate::domain::StandupItem;
+use crate::domain::Result;
+use crate::database::dependency::IS_BLOCKED_SQL;
+
+/// Start of the latest DONE block starting within `after..before`.
+pub async fn get_last_done_start(pool: &SqlitePool, workspace_id: i64, after: &str, before: &str) -> Result<Option<String>> {
+    let (start,): (Option<String>,) = sqlx::query_as(
+        "SELECT MAX(start_time) FROM time_blocks WHERE workspace_id = ?1 AND status = 'DONE' AND start_time >= ?2 AND start_time < ?3"
+    )
+    .bind(workspace_id)
+    .bind(after)
+    .bind(before)
+    .fetch_one(pool)
+    .await?;
+    Ok(start)
+}
+
+/// Unfinished tasks waiting on a prerequisite, with the unfinished prerequisites in `note`.
+pub async fn get_blocked_tasks(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<StandupItem>> {
+    let items = sqlx::query_as::<_, StandupItem>(&format!(
+        "SELECT t.id AS task_id, t.title, 'BLOCKED' AS status, t.estimated_minutes AS minutes, p.name AS project_name,
+                (SELECT GROUP_CONCAT(pt.title, ', ') FROM task_dependencies d JOIN tasks pt ON d.blocked_by_task_id = pt.id
+                 WHERE d.task_id = t.id AND (
+                    NOT EXISTS (SELECT 1 FROM time_blocks b WHERE b.task_id = pt.id)
+                    OR EXISTS (SELECT 1 FROM time_blocks b WHERE b.task_id = pt.id AND b.status != 'DONE'))) AS note
+         FROM tasks t
+         LEFT JOIN projects p ON t.project_id = p.id
+         WHERE t.workspace_id = ?1 AND {}
+           AND (NOT EXISTS (SELECT 1 FROM time_blocks b WHERE b.task_id = t.id)
+                OR EXISTS (SELECT 1 FROM time_blocks b WHERE b.task_id = t.id AND b.status != 'DONE'))
+         ORDER BY t.priority ASC, t.id ASC",
+        IS_BLOCKED_SQL
+    ))
+    .bind(workspace_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(items)
+}
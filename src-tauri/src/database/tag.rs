@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use sqlx::{SqlitePool, SqliteConnection};
+use chrono::Local;
+use crate::domain::{Tag, TagStat};
+use crate::domain::Result;
+use crate::database::timeline::WORKED_MINUTES_SQL;
+
+pub const CREATE_TAGS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS tags (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        last_used TEXT NOT NULL,
+        UNIQUE (workspace_id, name),
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE
+    );
+";
+
+pub const CREATE_TASK_TAGS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS task_tags (
+        task_id INTEGER NOT NULL,
+        tag_id INTEGER NOT NULL,
+        PRIMARY KEY (task_id, tag_id),
+        FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
+        FOREIGN KEY (tag_id) REFERENCES tags (id) ON DELETE CASCADE
+    );
+";
+
+/// Comma separated tag names of the block's task; assumes alias `tb`.
+pub const BLOCK_TAGS_SQL: &str =
+    "(SELECT GROUP_CONCAT(tg.name, ', ' ORDER BY tg.name) FROM task_tags tt JOIN tags tg ON tt.tag_id = tg.id WHERE tt.task_id = tb.task_id)";
+
+pub async fn get_tags(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<Tag>> {
+    let list = sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE workspace_id = ?1 ORDER BY last_used DESC, name ASC")
+        .bind(workspace_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(list)
+}
+
+/// Tag names per task id for the given tasks.
+pub async fn get_task_tag_map(pool: &SqlitePool, task_ids: &[i64]) -> Result<HashMap<i64, Vec<String>>> {
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT tt.task_id, tg.name FROM task_tags tt JOIN tags tg ON tt.tag_id = tg.id
+         WHERE tt.task_id IN (SELECT value FROM json_each(?1)) ORDER BY tg.name ASC"
+    )
+    .bind(serde_json::to_string(task_ids)?)
+    .fetch_all(pool)
+    .await?;
+
+    let mut map: HashMap<i64, Vec<String>> = HashMap::new();
+    for (task_id, name) in rows {
+        map.entry(task_id).or_default().push(name);
+    }
+    Ok(map)
+}
+
+/// Replaces the tags of a task, creating missing tags in the workspace.
+pub async fn set_task_tags(conn: &mut SqliteConnection, workspace_id: i64, task_id: i64, names: &[String]) -> Result<()> {
+    let now = Local::now().format("%Y-%m-%dT%H:%M:00").to_string();
+    sqlx::query("DELETE FROM task_tags WHERE task_id = ?1").bind(task_id).execute(&mut *conn).await?;
+    for name in names {
+        sqlx::query("INSERT INTO tags (workspace_id, name, last_used) VALUES (?1, ?2, ?3) ON CONFLICT(workspace_id, name) DO UPDATE SET last_used = excluded.last_used")
+            .bind(workspace_id)
+            .bind(name)
+            .bind(&now)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("INSERT OR IGNORE INTO task_tags (task_id, tag_id) SELECT ?1, id FROM tags WHERE workspace_id = ?2 AND name = ?3")
+            .bind(task_id)
+            .bind(workspace_id)
+            .bind(name)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
+
+pub async fn get_tag_stats(pool: &SqlitePool, workspace_id: i64, start_time: &str, end_time: &str) -> Result<Vec<TagStat>> {
+    let stats = sqlx::query_as::<_, TagStat>(&format!(
+        "SELECT tg.name, COALESCE(SUM({}), 0) AS worked_minutes, COUNT(DISTINCT tb.task_id) AS task_count
+         FROM time_blocks tb
+         JOIN task_tags tt ON tt.task_id = tb.task_id
+         JOIN tags tg ON tg.id = tt.tag_id
+         WHERE tb.workspace_id = ?1 AND tb.status IN ('DONE', 'CONTINUED') AND tb.start_time >= ?2 AND tb.start_time <= ?3
+         GROUP BY tg.id
+         ORDER BY worked_minutes DESC, tg.name ASC",
+        WORKED_MINUTES_SQL
+    ))
+    .bind(workspace_id)
+    .bind(start_time)
+    .bind(end_time)
+    .fetch_all(pool)
+    .await?;
+    Ok(stats)
+}
@@ -1,7 +1,26 @@
 use sqlx::{SqlitePool};
 use chrono::{NaiveDateTime, Duration, NaiveDate, Local};
-use crate::domain::{Task, TimeBlock, UnpluggedTime};
+use crate::domain::{Task, TimeBlock, UnpluggedTime, BlockInterval};
 use crate::domain::{Result};
+use crate::database::checklist::CHECKLIST_PROGRESS_SQL;
+use crate::database::dependency::IS_BLOCKED_SQL;
+
+pub const CREATE_BLOCK_INTERVALS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS block_intervals (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        block_id INTEGER NOT NULL,
+        started_at TEXT NOT NULL,
+        ended_at TEXT,
+        FOREIGN KEY (block_id) REFERENCES time_blocks (id) ON DELETE CASCADE
+    );
+";
+
+/// Minutes actually worked on a block (`tb`): the sum of its recorded work intervals,
+/// or the plain block span when the block was never paused.
+pub const WORKED_MINUTES_SQL: &str = "COALESCE(
+    (SELECT SUM(strftime('%s', COALESCE(bi.ended_at, tb.end_time)) - strftime('%s', bi.started_at)) / 60 FROM block_intervals bi WHERE bi.block_id = tb.id),
+    (strftime('%s', tb.end_time) - strftime('%s', tb.start_time)) / 60
+)";
 
 pub async fn get_today_completed_duration(pool: &SqlitePool, workspace_id: i64, day_start_time: &str) -> Result<i64> {
     let now = Local::now();
@@ -15,11 +34,12 @@ pub async fn get_today_completed_duration(pool: &SqlitePool, workspace_id: i64,
     let start_of_day = NaiveDateTime::parse_from_str(&format!("{}T{}", logical_date.format("%Y-%m-%d"), day_start_time), "%Y-%m-%dT%H:%M").unwrap();
     let end_of_day = start_of_day + Duration::days(1) - Duration::seconds(1);
 
-    let row: (Option<i64>,) = sqlx::query_as(
-        "SELECT SUM((strftime('%s', end_time) - strftime('%s', start_time)) / 60) 
-         FROM time_blocks 
-         WHERE workspace_id = ?1 AND status IN ('DONE', 'PENDING', 'CONTINUED') AND start_time >= ?2 AND start_time <= ?3"
-    )
+    let row: (Option<i64>,) = sqlx::query_as(&format!(
+        "SELECT SUM({}) 
+         FROM time_blocks tb 
+         WHERE tb.workspace_id = ?1 AND tb.status IN ('DONE', 'PENDING', 'CONTINUED') AND tb.start_time >= ?2 AND tb.start_time <= ?3",
+        WORKED_MINUTES_SQL
+    ))
     .bind(workspace_id)
     .bind(start_of_day.format("%Y-%m-%dT%H:%M:00").to_string())
     .bind(end_of_day.format("%Y-%m-%dT%H:%M:00").to_string())
@@ -33,27 +53,49 @@ pub async fn get_timeline(pool: &SqlitePool, workspace_id: i64, target_date: Nai
     let start_of_day = NaiveDateTime::parse_from_str(&format!("{}T{}", target_date.format("%Y-%m-%d"), day_start_time), "%Y-%m-%dT%H:%M").unwrap();
     let end_of_day = start_of_day + Duration::days(1) - Duration::seconds(1);
 
-    let mut blocks = sqlx::query_as::<_, TimeBlock>(
-        "SELECT tb.*, t.planning_memo, p.name as project_name, l.name as label_name, l.color as label_color 
+    let mut blocks = get_blocks_in_range(
+        pool,
+        workspace_id,
+        &start_of_day.format("%Y-%m-%dT%H:%M:00").to_string(),
+        &end_of_day.format("%Y-%m-%dT%H:%M:00").to_string(),
+    )
+    .await?;
+
+    let unplugged: Vec<UnpluggedTime> = sqlx::query_as("SELECT * FROM unplugged_times WHERE workspace_id = ?1")
+        .bind(workspace_id)
+        .fetch_all(pool)
+        .await?;
+    blocks.extend(unplugged_blocks(workspace_id, &unplugged, target_date, day_start_time));
+
+    blocks.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+    Ok(blocks)
+}
+
+/// Task blocks starting within `start_time..=end_time`, in start order.
+pub async fn get_blocks_in_range(pool: &SqlitePool, workspace_id: i64, start_time: &str, end_time: &str) -> Result<Vec<TimeBlock>> {
+    let blocks = sqlx::query_as::<_, TimeBlock>(&format!(
+        "SELECT tb.*, t.planning_memo, t.priority, p.name as project_name, l.name as label_name, l.color as label_color, {} AS checklist_progress
          FROM time_blocks tb
          LEFT JOIN tasks t ON tb.task_id = t.id
          LEFT JOIN projects p ON t.project_id = p.id
          LEFT JOIN labels l ON t.label_id = l.id
          WHERE tb.workspace_id = ?1 AND tb.start_time >= ?2 AND tb.start_time <= ?3 
-         ORDER BY tb.start_time ASC"
-    )
+         ORDER BY tb.start_time ASC",
+        CHECKLIST_PROGRESS_SQL
+    ))
     .bind(workspace_id)
-    .bind(start_of_day.format("%Y-%m-%dT%H:%M:00").to_string())
-    .bind(end_of_day.format("%Y-%m-%dT%H:%M:00").to_string())
+    .bind(start_time)
+    .bind(end_time)
     .fetch_all(pool)
     .await?;
+    Ok(blocks)
+}
 
-    let unplugged: Vec<UnpluggedTime> = sqlx::query_as("SELECT * FROM unplugged_times WHERE workspace_id = ?1")
-        .bind(workspace_id)
-        .fetch_all(pool)
-        .await?;
-
+/// Expands the workspace's daily unplugged times into `UNPLUGGED` pseudo-blocks (id -1)
+/// on one logical date; times before `day_start_time` fall on the next calendar day.
+pub fn unplugged_blocks(workspace_id: i64, unplugged: &[UnpluggedTime], target_date: NaiveDate, day_start_time: &str) -> Vec<TimeBlock> {
     use chrono::NaiveTime;
+    let mut blocks = Vec::new();
     for ut in unplugged {
         let ut_start_time = NaiveTime::parse_from_str(&ut.start_time, "%H:%M").unwrap();
         let ut_end_time = NaiveTime::parse_from_str(&ut.end_time, "%H:%M").unwrap();
@@ -70,7 +112,7 @@ pub async fn get_timeline(pool: &SqlitePool, workspace_id: i64, target_date: Nai
             id: -1,
             task_id: None,
             workspace_id,
-            title: ut.label,
+            title: ut.label.clone(),
             start_time: ut_start_dt.format("%Y-%m-%dT%H:%M:00").to_string(),
             end_time: ut_end_dt.format("%Y-%m-%dT%H:%M:00").to_string(),
             status: "UNPLUGGED".to_string(),
@@ -80,24 +122,66 @@ pub async fn get_timeline(pool: &SqlitePool, workspace_id: i64, target_date: Nai
             project_name: None,
             label_name: None,
             label_color: None,
+            paused_minutes: 0,
+            is_paused: false,
+            overrun_minutes: 0,
+            priority: None,
+            checklist_progress: None,
+            tags: Vec::new(),
         });
     }
+    blocks
+}
 
-    blocks.sort_by(|a, b| a.start_time.cmp(&b.start_time));
-    Ok(blocks)
+pub async fn get_block_intervals_in_range(pool: &SqlitePool, workspace_id: i64, start_time: &str, end_time: &str) -> Result<Vec<BlockInterval>> {
+    let intervals = sqlx::query_as::<_, BlockInterval>(
+        "SELECT bi.* FROM block_intervals bi
+         JOIN time_blocks tb ON bi.block_id = tb.id
+         WHERE tb.workspace_id = ?1 AND tb.start_time >= ?2 AND tb.start_time <= ?3
+         ORDER BY bi.block_id ASC, bi.started_at ASC"
+    )
+    .bind(workspace_id)
+    .bind(start_time)
+    .bind(end_time)
+    .fetch_all(pool)
+    .await?;
+    Ok(intervals)
 }
 
 
-pub async fn get_inbox(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<Task>> {
-    let list = sqlx::query_as::<_, Task>(
-        "SELECT * FROM tasks WHERE workspace_id = ?1 AND id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL) ORDER BY position ASC, id ASC"
-    )
+/// Tasks with the nearest due date come first, then the manual inbox order.
+pub const INBOX_ORDER_SQL: &str = "ORDER BY due_at IS NULL, due_at ASC, position ASC, id ASC";
+/// Highest priority first, then the nearest due date, then the manual inbox order.
+pub const INBOX_PRIORITY_ORDER_SQL: &str = "ORDER BY priority ASC, due_at IS NULL, due_at ASC, position ASC, id ASC";
+pub const INBOX_MANUAL_ORDER_SQL: &str = "ORDER BY position ASC, id ASC";
+
+pub async fn get_inbox(pool: &SqlitePool, workspace_id: i64, order_sql: &str) -> Result<Vec<Task>> {
+    let list = sqlx::query_as::<_, Task>(&format!(
+        "SELECT t.*, {} AS is_blocked FROM tasks t WHERE workspace_id = ?1 AND id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL) {}",
+        IS_BLOCKED_SQL, order_sql
+    ))
     .bind(workspace_id)
     .fetch_all(pool)
     .await?;
     Ok(list)
 }
 
+/// Tasks whose plan runs past their due date, plus unfinished ones already past it at `now`:
+/// still in the inbox, or with open blocks left.
+pub async fn get_at_risk_tasks(pool: &SqlitePool, workspace_id: i64, now: &str) -> Result<Vec<Task>> {
+    let list = sqlx::query_as::<_, Task>(
+        "SELECT * FROM tasks t WHERE t.workspace_id = ?1 AND (t.is_at_risk = 1 OR (t.due_at < ?2 AND (
+             NOT EXISTS (SELECT 1 FROM time_blocks tb WHERE tb.task_id = t.id)
+             OR EXISTS (SELECT 1 FROM time_blocks tb WHERE tb.task_id = t.id AND tb.status IN ('NOW', 'WILL', 'PENDING')))))
+         ORDER BY t.due_at ASC, t.id ASC"
+    )
+        .bind(workspace_id)
+        .bind(now)
+        .fetch_all(pool)
+        .await?;
+    Ok(list)
+}
+
 pub async fn get_active_dates(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<String>> {
     use sqlx::Row;
     let rows = sqlx::query(
@@ -115,6 +199,15 @@ pub async fn get_active_dates(pool: &SqlitePool, workspace_id: i64) -> Result<Ve
     Ok(dates)
 }
 
+/// Whether the block's last work interval is closed, i.e. it is paused.
+pub async fn is_block_paused(pool: &SqlitePool, block_id: i64) -> Result<bool> {
+    let paused: Option<(bool,)> = sqlx::query_as("SELECT ended_at IS NOT NULL FROM block_intervals WHERE block_id = ?1 ORDER BY started_at DESC, id DESC LIMIT 1")
+        .bind(block_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(paused.is_some_and(|(p,)| p))
+}
+
 pub async fn check_active_block_exists(pool: &SqlitePool, workspace_id: i64) -> Result<bool> {
     let active_block = sqlx::query("SELECT 1 FROM time_blocks WHERE workspace_id = ?1 AND status = 'NOW'")
         .bind(workspace_id)
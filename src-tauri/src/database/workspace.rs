@@ -1,6 +1,101 @@
-use sqlx::SqlitePool;
-use crate::domain::{Workspace, UnpluggedTime, CreateWorkspaceInput, Project, Label, ProjectInput, LabelInput};
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+use crate::domain::{Workspace, UnpluggedTime, CreateWorkspaceInput, Project, Label, ProjectInput, LabelInput, TaskOccurrence};
 use crate::domain::Result;
+use crate::database::timeline::WORKED_MINUTES_SQL;
+
+/// `workspace_id` NULL marks a project shared by every workspace.
+pub const CREATE_PROJECTS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS projects (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER,
+        name TEXT NOT NULL,
+        last_used TEXT NOT NULL,
+        description TEXT,
+        role TEXT,
+        tech_stack TEXT,
+        link TEXT,
+        start_date TEXT,
+        end_date TEXT,
+        status TEXT NOT NULL DEFAULT 'ACTIVE',
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE
+    );
+";
+
+pub const CREATE_PROJECTS_SCOPE_INDEX: &str =
+    "CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_scope_name ON projects (COALESCE(workspace_id, 0), name)";
+
+/// `workspace_id` NULL marks a label shared by every workspace.
+pub const CREATE_LABELS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS labels (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        workspace_id INTEGER,
+        name TEXT NOT NULL,
+        color TEXT NOT NULL,
+        last_used TEXT NOT NULL,
+        FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE
+    );
+";
+
+pub const CREATE_LABELS_SCOPE_INDEX: &str =
+    "CREATE UNIQUE INDEX IF NOT EXISTS idx_labels_scope_name ON labels (COALESCE(workspace_id, 0), name)";
+
+/// Single owning workspace of the rows' tasks, or NULL when they span several
+/// (or none); assumes alias `p` and a `tasks` column named by `{fk}`.
+fn owning_workspace_sql(fk: &str) -> String {
+    format!("(SELECT CASE WHEN COUNT(DISTINCT t.workspace_id) = 1 THEN MIN(t.workspace_id) END FROM tasks t WHERE t.{} = p.id)", fk)
+}
+
+/// Rebuilds the legacy globally-unique `projects`/`labels` tables with a
+/// `workspace_id` column. Rows used by exactly one workspace become local to
+/// it; everything else stays shared.
+pub async fn migrate_workspace_scoped_catalogs(pool: &SqlitePool) -> Result<()> {
+    let scoped: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM pragma_table_info('projects') WHERE name = 'workspace_id'")
+        .fetch_one(pool)
+        .await?;
+    if scoped.0 > 0 {
+        return Ok(());
+    }
+
+    // Foreign keys must be off while tables referenced by `tasks` are swapped,
+    // and the pragma is a no-op inside a transaction. The connection goes back to
+    // the pool afterwards, so enforcement is restored whether or not the rebuild worked.
+    let mut conn = pool.acquire().await?;
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+    let rebuilt = rebuild_scoped_catalogs(&mut conn).await;
+    let restored = sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await;
+    rebuilt?;
+    restored?;
+    Ok(())
+}
+
+async fn rebuild_scoped_catalogs(conn: &mut SqliteConnection) -> Result<()> {
+    let mut tx = conn.begin().await?;
+
+    sqlx::query(&CREATE_PROJECTS_TABLE.replace("projects (", "projects_scoped (")).execute(&mut *tx).await?;
+    sqlx::query(&format!(
+        "INSERT INTO projects_scoped (id, workspace_id, name, last_used) SELECT p.id, {}, p.name, p.last_used FROM projects p",
+        owning_workspace_sql("project_id")
+    ))
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query("DROP TABLE projects").execute(&mut *tx).await?;
+    sqlx::query("ALTER TABLE projects_scoped RENAME TO projects").execute(&mut *tx).await?;
+
+    sqlx::query(&CREATE_LABELS_TABLE.replace("labels (", "labels_scoped (")).execute(&mut *tx).await?;
+    sqlx::query(&format!(
+        "INSERT INTO labels_scoped (id, workspace_id, name, color, last_used) SELECT p.id, {}, p.name, p.color, p.last_used FROM labels p",
+        owning_workspace_sql("label_id")
+    ))
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query("DROP TABLE labels").execute(&mut *tx).await?;
+    sqlx::query("ALTER TABLE labels_scoped RENAME TO labels").execute(&mut *tx).await?;
+
+    sqlx::query(CREATE_PROJECTS_SCOPE_INDEX).execute(&mut *tx).await?;
+    sqlx::query(CREATE_LABELS_SCOPE_INDEX).execute(&mut *tx).await?;
+    tx.commit().await?;
+    Ok(())
+}
 
 pub async fn get_workspaces(pool: &SqlitePool) -> Result<Vec<Workspace>> {
     let workspaces = sqlx::query_as::<_, Workspace>("SELECT * FROM workspaces")
@@ -23,12 +118,15 @@ pub async fn create_workspace(
 ) -> Result<i64> {
     let mut tx = pool.begin().await?;
     let result = sqlx::query(
-        "INSERT INTO workspaces (name, core_time_start, core_time_end, role_intro) VALUES (?1, ?2, ?3, ?4)",
+        "INSERT INTO workspaces (name, core_time_start, core_time_end, role_intro, rollover_policy, overrun_policy, overrun_extend_minutes) VALUES (?1, ?2, ?3, ?4, COALESCE(?5, 'INBOX'), COALESCE(?6, 'FLAG'), COALESCE(?7, 15))",
     )
     .bind(&input.name)
     .bind(&input.core_time_start)
     .bind(&input.core_time_end)
     .bind(&input.role_intro)
+    .bind(&input.rollover_policy)
+    .bind(&input.overrun_policy)
+    .bind(input.overrun_extend_minutes)
     .execute(&mut *tx)
     .await?;
 
@@ -65,12 +163,15 @@ pub async fn update_workspace(
 ) -> Result<()> {
     let mut tx = pool.begin().await?;
     sqlx::query(
-        "UPDATE workspaces SET name=?1, core_time_start=?2, core_time_end=?3, role_intro=?4 WHERE id=?5",
+        "UPDATE workspaces SET name=?1, core_time_start=?2, core_time_end=?3, role_intro=?4, rollover_policy=COALESCE(?5, rollover_policy), overrun_policy=COALESCE(?6, overrun_policy), overrun_extend_minutes=COALESCE(?7, overrun_extend_minutes) WHERE id=?8",
     )
     .bind(&input.name)
     .bind(&input.core_time_start)
     .bind(&input.core_time_end)
     .bind(&input.role_intro)
+    .bind(&input.rollover_policy)
+    .bind(&input.overrun_policy)
+    .bind(input.overrun_extend_minutes)
     .bind(id)
     .execute(&mut *tx)
     .await?;
@@ -104,8 +205,16 @@ pub async fn delete_workspace(pool: &SqlitePool, id: i64) -> Result<()> {
     Ok(())
 }
 
-pub async fn get_projects(pool: &SqlitePool) -> Result<Vec<Project>> {
-    let projects = sqlx::query_as::<_, Project>("SELECT * FROM projects ORDER BY last_used DESC")
+/// Projects visible from `workspace_id` (its own plus shared ones), or every
+/// project when no workspace is given. Archived ones only with `include_archived`.
+pub async fn get_projects(pool: &SqlitePool, workspace_id: Option<i64>, include_archived: bool) -> Result<Vec<Project>> {
+    let projects = sqlx::query_as::<_, Project>(
+        "SELECT * FROM projects
+         WHERE (?1 IS NULL OR workspace_id IS NULL OR workspace_id = ?1) AND (?2 OR status != 'ARCHIVED')
+         ORDER BY last_used DESC",
+    )
+        .bind(workspace_id)
+        .bind(include_archived)
         .fetch_all(pool)
         .await?;
     Ok(projects)
@@ -113,23 +222,78 @@ pub async fn get_projects(pool: &SqlitePool) -> Result<Vec<Project>> {
 
 pub async fn create_project(pool: &SqlitePool, input: ProjectInput) -> Result<i64> {
     let now = chrono::Local::now().format("%Y-%m-%dT%H:%M:00").to_string();
-    let result = sqlx::query("INSERT INTO projects (name, last_used) VALUES (?1, ?2)")
+    let result = sqlx::query(
+        "INSERT INTO projects (workspace_id, name, last_used, description, role, tech_stack, link, start_date, end_date, status)
+         VALUES (?1, ?2, ?3, NULLIF(?4, ''), NULLIF(?5, ''), NULLIF(?6, ''), NULLIF(?7, ''), NULLIF(?8, ''), NULLIF(?9, ''), COALESCE(?10, 'ACTIVE'))",
+    )
+        .bind(input.workspace_id)
         .bind(&input.name)
         .bind(&now)
+        .bind(&input.description)
+        .bind(&input.role)
+        .bind(&input.tech_stack)
+        .bind(&input.link)
+        .bind(&input.start_date)
+        .bind(&input.end_date)
+        .bind(&input.status)
         .execute(pool)
         .await?;
     Ok(result.last_insert_rowid())
 }
 
 pub async fn update_project(pool: &SqlitePool, id: i64, input: ProjectInput) -> Result<()> {
-    sqlx::query("UPDATE projects SET name = ?1 WHERE id = ?2")
+    sqlx::query(
+        "UPDATE projects SET name = ?1,
+            description = CASE WHEN ?2 IS NULL THEN description ELSE NULLIF(?2, '') END,
+            role = CASE WHEN ?3 IS NULL THEN role ELSE NULLIF(?3, '') END,
+            tech_stack = CASE WHEN ?4 IS NULL THEN tech_stack ELSE NULLIF(?4, '') END,
+            link = CASE WHEN ?5 IS NULL THEN link ELSE NULLIF(?5, '') END,
+            start_date = CASE WHEN ?6 IS NULL THEN start_date ELSE NULLIF(?6, '') END,
+            end_date = CASE WHEN ?7 IS NULL THEN end_date ELSE NULLIF(?7, '') END,
+            status = COALESCE(?8, status)
+         WHERE id = ?9",
+    )
         .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.role)
+        .bind(&input.tech_stack)
+        .bind(&input.link)
+        .bind(&input.start_date)
+        .bind(&input.end_date)
+        .bind(&input.status)
         .bind(id)
         .execute(pool)
         .await?;
     Ok(())
 }
 
+pub async fn get_project(pool: &SqlitePool, id: i64) -> Result<Option<Project>> {
+    let project = sqlx::query_as::<_, Project>("SELECT * FROM projects WHERE id = ?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(project)
+}
+
+/// Totals over every task of the project: (worked minutes of DONE blocks,
+/// task count, first block start, last block end).
+pub async fn get_project_totals(pool: &SqlitePool, id: i64) -> Result<(i64, i64, Option<String>, Option<String>)> {
+    let totals: (Option<i64>, Option<String>, Option<String>) = sqlx::query_as(&format!(
+        "SELECT SUM({}), MIN(tb.start_time), MAX(tb.end_time)
+         FROM time_blocks tb JOIN tasks t ON t.id = tb.task_id
+         WHERE t.project_id = ?1 AND tb.status = 'DONE'",
+        WORKED_MINUTES_SQL
+    ))
+    .bind(id)
+    .fetch_one(pool)
+    .await?;
+    let task_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tasks WHERE project_id = ?1")
+        .bind(id)
+        .fetch_one(pool)
+        .await?;
+    Ok((totals.0.unwrap_or(0), task_count.0, totals.1, totals.2))
+}
+
 pub async fn delete_project(pool: &SqlitePool, id: i64) -> Result<()> {
     sqlx::query("DELETE FROM projects WHERE id = ?1")
         .bind(id)
@@ -138,8 +302,11 @@ pub async fn delete_project(pool: &SqlitePool, id: i64) -> Result<()> {
     Ok(())
 }
 
-pub async fn get_labels(pool: &SqlitePool) -> Result<Vec<Label>> {
-    let labels = sqlx::query_as::<_, Label>("SELECT * FROM labels ORDER BY last_used DESC")
+/// Labels visible from `workspace_id` (its own plus shared ones), or every
+/// label when no workspace is given.
+pub async fn get_labels(pool: &SqlitePool, workspace_id: Option<i64>) -> Result<Vec<Label>> {
+    let labels = sqlx::query_as::<_, Label>("SELECT * FROM labels WHERE ?1 IS NULL OR workspace_id IS NULL OR workspace_id = ?1 ORDER BY last_used DESC")
+        .bind(workspace_id)
         .fetch_all(pool)
         .await?;
     Ok(labels)
@@ -147,7 +314,8 @@ pub async fn get_labels(pool: &SqlitePool) -> Result<Vec<Label>> {
 
 pub async fn create_label(pool: &SqlitePool, input: LabelInput) -> Result<i64> {
     let now = chrono::Local::now().format("%Y-%m-%dT%H:%M:00").to_string();
-    let result = sqlx::query("INSERT INTO labels (name, color, last_used) VALUES (?1, ?2, ?3)")
+    let result = sqlx::query("INSERT INTO labels (workspace_id, name, color, last_used) VALUES (?1, ?2, ?3, ?4)")
+        .bind(input.workspace_id)
         .bind(&input.name)
         .bind(&input.color)
         .bind(&now)
@@ -174,23 +342,142 @@ pub async fn delete_label(pool: &SqlitePool, id: i64) -> Result<()> {
     Ok(())
 }
 
-pub async fn search_task_titles(
-    pool: &sqlx::SqlitePool,
+/// Task-referenced catalogs that can be merged and bulk reassigned.
+#[derive(Clone, Copy, Debug)]
+pub enum Catalog {
+    Project,
+    Label,
+}
+
+impl Catalog {
+    fn table(self) -> &'static str {
+        match self {
+            Catalog::Project => "projects",
+            Catalog::Label => "labels",
+        }
+    }
+
+    fn task_column(self) -> &'static str {
+        match self {
+            Catalog::Project => "project_id",
+            Catalog::Label => "label_id",
+        }
+    }
+}
+
+/// `Some(scope)` when the row exists, where a `None` scope means shared.
+pub async fn get_catalog_scope(pool: &SqlitePool, catalog: Catalog, id: i64) -> Result<Option<Option<i64>>> {
+    let row: Option<(Option<i64>,)> = sqlx::query_as(&format!("SELECT workspace_id FROM {} WHERE id = ?1", catalog.table()))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(|(workspace_id,)| workspace_id))
+}
+
+/// Number of tasks referencing `id` that live outside `workspace_id`.
+pub async fn count_catalog_tasks_outside(pool: &SqlitePool, catalog: Catalog, id: i64, workspace_id: i64) -> Result<i64> {
+    let row: (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM tasks WHERE {} = ?1 AND workspace_id != ?2", catalog.task_column()))
+        .bind(id)
+        .bind(workspace_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(row.0)
+}
+
+/// Whether another row of the target scope (`None` = shared) already uses the name of `id`.
+pub async fn is_catalog_name_taken(pool: &SqlitePool, catalog: Catalog, id: i64, workspace_id: Option<i64>) -> Result<bool> {
+    let taken: bool = sqlx::query_scalar(&format!(
+        "SELECT EXISTS (SELECT 1 FROM {table} WHERE id != ?1 AND COALESCE(workspace_id, 0) = COALESCE(?2, 0)
+            AND name = (SELECT name FROM {table} WHERE id = ?1))",
+        table = catalog.table()
+    ))
+    .bind(id)
+    .bind(workspace_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(taken)
+}
+
+/// Moves a row to another scope; `None` shares it with every workspace.
+pub async fn set_catalog_scope(pool: &SqlitePool, catalog: Catalog, id: i64, workspace_id: Option<i64>) -> Result<()> {
+    sqlx::query(&format!("UPDATE {} SET workspace_id = ?1 WHERE id = ?2", catalog.table()))
+        .bind(workspace_id)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Number of the given tasks that live outside `workspace_id`.
+pub async fn count_tasks_outside(pool: &SqlitePool, task_ids: &[i64], workspace_id: i64) -> Result<i64> {
+    let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tasks WHERE id IN (SELECT value FROM json_each(?1)) AND workspace_id != ?2")
+        .bind(serde_json::to_string(task_ids)?)
+        .bind(workspace_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(row.0)
+}
+
+/// Repoints every task from `source_id` to `target_id` and deletes the source.
+pub async fn merge_catalog(pool: &SqlitePool, catalog: Catalog, source_id: i64, target_id: i64) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(&format!("UPDATE tasks SET {col} = ?1 WHERE {col} = ?2", col = catalog.task_column()))
+        .bind(target_id)
+        .bind(source_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(&format!(
+        "UPDATE {table} SET last_used = MAX(last_used, (SELECT last_used FROM {table} WHERE id = ?2)) WHERE id = ?1",
+        table = catalog.table()
+    ))
+    .bind(target_id)
+    .bind(source_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(&format!("DELETE FROM {} WHERE id = ?1", catalog.table()))
+        .bind(source_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Points the given tasks at `target_id`; `None` detaches them.
+pub async fn reassign_tasks(pool: &SqlitePool, catalog: Catalog, task_ids: &[i64], target_id: Option<i64>) -> Result<()> {
+    sqlx::query(&format!("UPDATE tasks SET {} = ?1 WHERE id IN (SELECT value FROM json_each(?2))", catalog.task_column()))
+        .bind(target_id)
+        .bind(serde_json::to_string(task_ids)?)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// The `limit` most recently created tasks whose title matches `query`, ordered by last use,
+/// with the minutes actually worked on each.
+pub async fn search_task_occurrences(
+    pool: &SqlitePool,
     workspace_id: i64,
     query: &str,
     limit: i64,
-) -> Result<Vec<String>> {
+) -> Result<Vec<TaskOccurrence>> {
     let wildcard_query = format!("%{}%", query);
-    let titles: Vec<String> = sqlx::query_scalar(
-        "SELECT DISTINCT title FROM tasks WHERE workspace_id = ?1 AND title LIKE ?2 LIMIT ?3",
-    )
+    let occurrences = sqlx::query_as::<_, TaskOccurrence>(&format!(
+        "SELECT t.id AS task_id, t.title, t.planning_memo, p.name AS project_name, l.name AS label_name,
+            (SELECT SUM({}) FROM time_blocks tb WHERE tb.task_id = t.id AND tb.status IN ('DONE', 'CONTINUED')) AS actual_minutes,
+            (SELECT MAX(tb.start_time) FROM time_blocks tb WHERE tb.task_id = t.id) AS last_used
+         FROM (SELECT id FROM tasks WHERE workspace_id = ?1 AND title LIKE ?2 ORDER BY id DESC LIMIT ?3) recent
+         JOIN tasks t ON t.id = recent.id
+         LEFT JOIN projects p ON t.project_id = p.id
+         LEFT JOIN labels l ON t.label_id = l.id
+         ORDER BY last_used DESC, t.id DESC",
+        WORKED_MINUTES_SQL
+    ))
     .bind(workspace_id)
     .bind(&wildcard_query)
     .bind(limit)
     .fetch_all(pool)
     .await?;
-
-    Ok(titles)
+    Ok(occurrences)
 }
 
 #[cfg(test)]
@@ -199,32 +486,40 @@ mod tests {
     use sqlx::sqlite::SqlitePool;
 
     #[tokio::test]
-    async fn test_search_task_titles() {
+    async fn test_search_task_occurrences() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.unwrap();
-        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, estimated_minutes INTEGER NOT NULL DEFAULT 0, FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE)").execute(&pool).await.unwrap();
-
-        let ws_id = sqlx::query("INSERT INTO workspaces (name) VALUES ('Test WS')").execute(&pool).await.unwrap().last_insert_rowid();
-
-        sqlx::query("INSERT INTO tasks (workspace_id, title) VALUES (?1, 'Apple Pie')").bind(ws_id).execute(&pool).await.unwrap();
-        sqlx::query("INSERT INTO tasks (workspace_id, title) VALUES (?1, 'Apple Juice')").bind(ws_id).execute(&pool).await.unwrap();
-        sqlx::query("INSERT INTO tasks (workspace_id, title) VALUES (?1, 'Banana')").bind(ws_id).execute(&pool).await.unwrap();
-        sqlx::query("INSERT INTO tasks (workspace_id, title) VALUES (?1, 'Apple Pie')").bind(ws_id).execute(&pool).await.unwrap();
-
-        let titles = search_task_titles(&pool, ws_id, "App", 10).await.unwrap();
-        assert_eq!(titles.len(), 2);
-        assert!(titles.contains(&"Apple Pie".to_string()));
-        assert!(titles.contains(&"Apple Juice".to_string()));
-
-        let bananas = search_task_titles(&pool, ws_id, "Ban", 10).await.unwrap();
-        assert_eq!(bananas.len(), 1);
-        assert_eq!(bananas[0], "Banana");
+        sqlx::query(CREATE_PROJECTS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(CREATE_LABELS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, estimated_minutes INTEGER NOT NULL DEFAULT 0, project_id INTEGER, label_id INTEGER, position INTEGER NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL, review_memo TEXT, planning_memo TEXT, is_urgent BOOLEAN NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query(crate::database::timeline::CREATE_BLOCK_INTERVALS_TABLE).execute(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO projects (id, name, last_used) VALUES (1, 'will-done', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, project_id) VALUES (1, 1, 'Code review', 1)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (2, 1, 'Code review')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (3, 1, 'Lunch')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (1, 1, 'Code review', '2026-03-02T09:00:00', '2026-03-02T09:40:00', 'DONE')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (2, 1, 'Code review', '2026-03-01T09:00:00', '2026-03-01T09:20:00', 'DONE')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (2, 1, 'Code review', '2026-03-01T10:00:00', '2026-03-01T10:30:00', 'WILL')").execute(&pool).await.unwrap();
+
+        let occurrences = search_task_occurrences(&pool, 1, "review", 10).await.unwrap();
+        assert_eq!(occurrences.len(), 2);
+        assert_eq!(occurrences[0].task_id, 1);
+        assert_eq!(occurrences[0].actual_minutes, Some(40));
+        assert_eq!(occurrences[0].project_name.as_deref(), Some("will-done"));
+        assert_eq!(occurrences[1].actual_minutes, Some(20));
+        assert_eq!(occurrences[1].last_used.as_deref(), Some("2026-03-01T10:00:00"));
+
+        // The limit keeps the most recently created matches
+        let recent = search_task_occurrences(&pool, 1, "review", 1).await.unwrap();
+        assert_eq!(recent.iter().map(|o| o.task_id).collect::<Vec<_>>(), vec![2]);
     }
 
     #[tokio::test]
     async fn test_create_workspace_transaction() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT, rollover_policy TEXT NOT NULL DEFAULT 'INBOX', overrun_policy TEXT NOT NULL DEFAULT 'FLAG', overrun_extend_minutes INTEGER NOT NULL DEFAULT 15)").execute(&pool).await.unwrap();
         sqlx::query("CREATE TABLE unplugged_times (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, label TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, FOREIGN KEY (workspace_id) REFERENCES workspaces (id))").execute(&pool).await.unwrap();
 
         let input = CreateWorkspaceInput {
@@ -233,6 +528,9 @@ mod tests {
             core_time_end: Some("18:00".to_string()),
             role_intro: Some("Engineer".to_string()),
             unplugged_times: vec![],
+            rollover_policy: None,
+            overrun_policy: None,
+            overrun_extend_minutes: None,
         };
 
         let mut tx = pool.begin().await.unwrap();
@@ -271,52 +569,179 @@ mod tests {
     #[tokio::test]
     async fn test_project_crud() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::query("CREATE TABLE projects (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.unwrap();
+        sqlx::query(CREATE_PROJECTS_TABLE).execute(&pool).await.unwrap();
 
         // 1. Create Project
-        let project_id = create_project(&pool, ProjectInput { name: "Test Project".to_string() }).await.unwrap();
+        let project_id = create_project(&pool, ProjectInput { name: "Test Project".to_string(), ..Default::default() }).await.unwrap();
         assert!(project_id > 0);
 
         // 2. Get Projects
-        let projects = get_projects(&pool).await.unwrap();
+        let projects = get_projects(&pool, None, true).await.unwrap();
         assert_eq!(projects.len(), 1);
         assert_eq!(projects[0].name, "Test Project");
 
         // 3. Update Project
-        update_project(&pool, project_id, ProjectInput { name: "Updated Project".to_string() }).await.unwrap();
-        let projects_updated = get_projects(&pool).await.unwrap();
+        update_project(&pool, project_id, ProjectInput { name: "Updated Project".to_string(), ..Default::default() }).await.unwrap();
+        let projects_updated = get_projects(&pool, None, true).await.unwrap();
         assert_eq!(projects_updated[0].name, "Updated Project");
 
         // 4. Delete Project
         delete_project(&pool, project_id).await.unwrap();
-        let projects_after_delete = get_projects(&pool).await.unwrap();
+        let projects_after_delete = get_projects(&pool, None, true).await.unwrap();
         assert!(projects_after_delete.is_empty());
     }
 
     #[tokio::test]
     async fn test_label_crud() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::query("CREATE TABLE labels (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, color TEXT NOT NULL, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.unwrap();
+        sqlx::query(CREATE_LABELS_TABLE).execute(&pool).await.unwrap();
 
         // 1. Create Label
-        let label_id = create_label(&pool, LabelInput { name: "Bug".to_string(), color: "#FF0000".to_string() }).await.unwrap();
+        let label_id = create_label(&pool, LabelInput { name: "Bug".to_string(), color: "#FF0000".to_string(), workspace_id: None }).await.unwrap();
         assert!(label_id > 0);
 
         // 2. Get Labels
-        let labels = get_labels(&pool).await.unwrap();
+        let labels = get_labels(&pool, None).await.unwrap();
         assert_eq!(labels.len(), 1);
         assert_eq!(labels[0].name, "Bug");
         assert_eq!(labels[0].color, "#FF0000");
 
         // 3. Update Label
-        update_label(&pool, label_id, LabelInput { name: "Feature".to_string(), color: "#00FF00".to_string() }).await.unwrap();
-        let labels_updated = get_labels(&pool).await.unwrap();
+        update_label(&pool, label_id, LabelInput { name: "Feature".to_string(), color: "#00FF00".to_string(), workspace_id: None }).await.unwrap();
+        let labels_updated = get_labels(&pool, None).await.unwrap();
         assert_eq!(labels_updated[0].name, "Feature");
         assert_eq!(labels_updated[0].color, "#00FF00");
 
         // 4. Delete Label
         delete_label(&pool, label_id).await.unwrap();
-        let labels_after_delete = get_labels(&pool).await.unwrap();
+        let labels_after_delete = get_labels(&pool, None).await.unwrap();
         assert!(labels_after_delete.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_projects_scoped_to_workspace() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.unwrap();
+        sqlx::query(CREATE_PROJECTS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(CREATE_PROJECTS_SCOPE_INDEX).execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Work'), (2, 'Side')").execute(&pool).await.unwrap();
+
+        create_project(&pool, ProjectInput { name: "Day job".to_string(), workspace_id: Some(1), ..Default::default() }).await.unwrap();
+        create_project(&pool, ProjectInput { name: "Side".to_string(), workspace_id: Some(2), ..Default::default() }).await.unwrap();
+        let shared = create_project(&pool, ProjectInput { name: "Reading".to_string(), ..Default::default() }).await.unwrap();
+        // Same name is allowed in another scope, but not twice in one.
+        create_project(&pool, ProjectInput { name: "Side".to_string(), workspace_id: Some(1), ..Default::default() }).await.unwrap();
+        assert!(create_project(&pool, ProjectInput { name: "Side".to_string(), workspace_id: Some(2), ..Default::default() }).await.is_err());
+
+        let mut names: Vec<String> = get_projects(&pool, Some(2), false).await.unwrap().into_iter().map(|p| p.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["Reading", "Side"]);
+        assert_eq!(get_projects(&pool, None, true).await.unwrap().len(), 4);
+
+        assert!(!is_catalog_name_taken(&pool, Catalog::Project, shared, Some(2)).await.unwrap());
+        set_catalog_scope(&pool, Catalog::Project, shared, Some(1)).await.unwrap();
+        assert_eq!(get_projects(&pool, Some(2), false).await.unwrap().len(), 1);
+        // Workspace 1 already has its own "Side"
+        assert!(is_catalog_name_taken(&pool, Catalog::Project, 2, Some(1)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_workspace_scoped_catalogs() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE projects (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE labels (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, color TEXT NOT NULL, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, project_id INTEGER REFERENCES projects (id) ON DELETE SET NULL, label_id INTEGER REFERENCES labels (id) ON DELETE SET NULL)").execute(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO projects (id, name, last_used) VALUES (1, 'Day job', '2026-03-01T09:00:00'), (2, 'Common', '2026-03-01T09:00:00'), (3, 'Unused', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO labels (id, name, color, last_used) VALUES (1, 'Bug', '#FF0000', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (workspace_id, title, project_id, label_id) VALUES (1, 'A', 1, 1), (1, 'B', 2, 1), (2, 'C', 2, NULL)").execute(&pool).await.unwrap();
+
+        migrate_workspace_scoped_catalogs(&pool).await.unwrap();
+        // Running again is a no-op.
+        migrate_workspace_scoped_catalogs(&pool).await.unwrap();
+
+        let projects = get_projects(&pool, None, true).await.unwrap();
+        let scope_of = |id: i64| projects.iter().find(|p| p.id == id).unwrap().workspace_id;
+        assert_eq!(scope_of(1), Some(1));
+        assert_eq!(scope_of(2), None);
+        assert_eq!(scope_of(3), None);
+        assert_eq!(get_labels(&pool, None).await.unwrap()[0].workspace_id, Some(1));
+
+        let project_ids: Vec<(Option<i64>,)> = sqlx::query_as("SELECT project_id FROM tasks ORDER BY id").fetch_all(&pool).await.unwrap();
+        assert_eq!(project_ids, vec![(Some(1),), (Some(2),), (Some(2),)]);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_workspace_scoped_catalogs_restores_foreign_keys_on_error() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        // A legacy table without `last_used` makes the copy fail halfway
+        sqlx::query("CREATE TABLE projects (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, project_id INTEGER, label_id INTEGER)").execute(&pool).await.unwrap();
+
+        assert!(migrate_workspace_scoped_catalogs(&pool).await.is_err());
+        let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys").fetch_one(&pool).await.unwrap();
+        assert_eq!(foreign_keys, 1);
+        let tables: Vec<(String,)> = sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'projects_scoped'").fetch_all(&pool).await.unwrap();
+        assert!(tables.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_project_detail_and_archiving() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.unwrap();
+        sqlx::query(CREATE_PROJECTS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, project_id INTEGER)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query(crate::database::timeline::CREATE_BLOCK_INTERVALS_TABLE).execute(&pool).await.unwrap();
+
+        let input = ProjectInput { name: "will-done".to_string(), description: Some("Time-blocking planner".to_string()), tech_stack: Some("Rust, Tauri".to_string()), start_date: Some("2026-01-01".to_string()), ..Default::default() };
+        let id = create_project(&pool, input).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, project_id) VALUES (1, 1, 'A', ?1), (2, 1, 'B', ?1)").bind(id).execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (1, 1, 'A', '2026-03-01T09:00:00', '2026-03-01T10:00:00', 'DONE'), (2, 1, 'B', '2026-03-02T09:00:00', '2026-03-02T09:30:00', 'DONE'), (2, 1, 'B', '2026-03-03T09:00:00', '2026-03-03T09:30:00', 'WILL')").execute(&pool).await.unwrap();
+
+        let (minutes, tasks, first, last) = get_project_totals(&pool, id).await.unwrap();
+        assert_eq!((minutes, tasks), (90, 2));
+        assert_eq!(first.as_deref(), Some("2026-03-01T09:00:00"));
+        assert_eq!(last.as_deref(), Some("2026-03-02T09:30:00"));
+
+        // Omitted fields are kept, empty strings clear.
+        update_project(&pool, id, ProjectInput { name: "will-done".to_string(), tech_stack: Some("".to_string()), status: Some("ARCHIVED".to_string()), ..Default::default() }).await.unwrap();
+        let project = get_project(&pool, id).await.unwrap().unwrap();
+        assert_eq!(project.description.as_deref(), Some("Time-blocking planner"));
+        assert_eq!(project.tech_stack, None);
+        assert_eq!(project.status, "ARCHIVED");
+
+        assert!(get_projects(&pool, Some(1), false).await.unwrap().is_empty());
+        assert_eq!(get_projects(&pool, Some(1), true).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_merge_and_reassign_catalog() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.unwrap();
+        sqlx::query(CREATE_PROJECTS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, project_id INTEGER, label_id INTEGER)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Work'), (2, 'Side')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO projects (id, workspace_id, name, last_used) VALUES (1, 1, 'will-done', '2026-03-01T09:00:00'), (2, NULL, 'Will Done', '2026-03-05T09:00:00'), (3, 2, 'Side', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, project_id) VALUES (1, 1, 'A', 1), (2, 1, 'B', 2), (3, 2, 'C', 2)").execute(&pool).await.unwrap();
+
+        // Task 3 lives in workspace 2, so the shared project can't fold into a workspace-1 one.
+        assert_eq!(count_catalog_tasks_outside(&pool, Catalog::Project, 2, 1).await.unwrap(), 1);
+        assert_eq!(count_tasks_outside(&pool, &[1, 2], 1).await.unwrap(), 0);
+        assert_eq!(get_catalog_scope(&pool, Catalog::Project, 3).await.unwrap(), Some(Some(2)));
+        assert_eq!(get_catalog_scope(&pool, Catalog::Project, 9).await.unwrap(), None);
+
+        merge_catalog(&pool, Catalog::Project, 1, 2).await.unwrap();
+        let project_ids: Vec<(Option<i64>,)> = sqlx::query_as("SELECT project_id FROM tasks ORDER BY id").fetch_all(&pool).await.unwrap();
+        assert_eq!(project_ids, vec![(Some(2),), (Some(2),), (Some(2),)]);
+        assert!(get_project(&pool, 1).await.unwrap().is_none());
+        assert_eq!(get_project(&pool, 2).await.unwrap().unwrap().last_used, "2026-03-05T09:00:00");
+
+        reassign_tasks(&pool, Catalog::Project, &[3], Some(3)).await.unwrap();
+        reassign_tasks(&pool, Catalog::Project, &[1], None).await.unwrap();
+        let project_ids: Vec<(Option<i64>,)> = sqlx::query_as("SELECT project_id FROM tasks ORDER BY id").fetch_all(&pool).await.unwrap();
+        assert_eq!(project_ids, vec![(None,), (Some(2),), (Some(3),)]);
+    }
 }
@@ -11,3 +11,33 @@ pub struct Achievement {
     pub created_at: String,
     pub used_model: Option<String>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletedTaskBlock {
+    pub title: String,
+    pub planning_memo: Option<String>,
+    pub review_memo: Option<String>,
+    pub start_time: String,
+    pub end_time: String,
+    pub worked_minutes: i64,
+    #[sqlx(default)]
+    pub completed_steps: Option<String>, // Checked checklist items, newline separated; only on the task's last block
+    #[sqlx(default)]
+    pub tags: Option<String>, // Comma separated
+    #[sqlx(default)]
+    pub focus_sessions: i64, // Completed focus sessions inside the block
+    #[sqlx(default)]
+    pub goal_title: Option<String>, // Goal the task advances through its key result
+    #[sqlx(default)]
+    pub key_result_title: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AchievementOptions {
+    #[serde(default)]
+    pub tags: Option<Vec<String>>, // Only tasks carrying at least one of these tags
+    #[serde(default)]
+    pub include_retrospectives: bool, // Adds the period's retrospective notes as context
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskEstimate {
+    pub task_id: i64,
+    pub title: String,
+    pub project_name: Option<String>,
+    pub label_name: Option<String>,
+    pub estimated_minutes: i64,
+    pub actual_minutes: i64,
+    pub last_start_time: String,
+    #[sqlx(default)]
+    pub logical_date: String,
+    #[sqlx(default)]
+    pub ratio: f64, // actual / estimated
+    #[sqlx(default)]
+    pub is_overrun: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateGroup {
+    pub name: Option<String>, // None groups tasks without a project/label
+    pub task_count: i64,
+    pub estimated_minutes: i64,
+    pub actual_minutes: i64,
+    pub ratio: f64,
+    pub overrun_count: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyPlan {
+    pub logical_date: String,
+    pub planned_minutes: i64,
+    pub planned_task_count: i64,
+    pub completed_planned_task_count: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyAccuracy {
+    pub logical_date: String,
+    pub planned_minutes: Option<i64>, // None when no snapshot was taken that day
+    pub planned_task_count: i64,
+    pub completed_planned_task_count: i64,
+    pub actual_minutes: i64,
+    pub estimated_minutes: i64, // estimates of the tasks finished that day
+    pub ratio: f64,
+    pub overrun_count: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimationAccuracyReport {
+    pub overall: EstimateGroup,
+    pub tasks: Vec<TaskEstimate>,
+    pub by_project: Vec<EstimateGroup>,
+    pub by_label: Vec<EstimateGroup>,
+    pub daily: Vec<DailyAccuracy>,
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItem {
+    pub id: i64,
+    pub task_id: i64,
+    pub title: String,
+    pub position: i64,
+    pub is_checked: bool,
+    pub estimated_minutes: Option<i64>,
+    pub checked_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItemInput {
+    pub task_id: i64,
+    pub title: String,
+    pub estimated_minutes: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChecklistItemInput {
+    pub id: i64,
+    pub title: String,
+    pub estimated_minutes: Option<i64>,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDependency {
+    pub task_id: i64,
+    pub blocked_by_task_id: i64, // Prerequisite that has to be finished first
+}
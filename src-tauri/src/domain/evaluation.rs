@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// `achievement_type` of generated self-evaluations.
+pub const SELF_EVALUATION_TYPE: &str = "SELF_EVALUATION";
+
+/// One entry of a workspace's competency framework, e.g. "Ownership".
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Competency {
+    pub id: i64,
+    pub workspace_id: i64,
+    pub name: String,
+    /// What the competency means in this team; guides the mapping of evidence.
+    pub description: Option<String>,
+    pub position: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompetencyInput {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfEvaluationInput {
+    pub workspace_id: i64,
+    pub start_date: String, // "YYYY-MM-DD"
+    pub end_date: String,
+    pub date_label: String, // e.g. "2026 H1"
+    #[serde(default)]
+    pub overwrite: bool,
+    #[serde(default)]
+    pub force_retry: bool,
+    #[serde(default)]
+    pub target_model: Option<String>,
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_FOCUS_WORK_MINUTES: i64 = 25;
+pub const DEFAULT_FOCUS_BREAK_MINUTES: i64 = 5;
+
+/// A Pomodoro-style work session inside a NOW block.
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusSession {
+    pub id: i64,
+    pub workspace_id: i64,
+    pub block_id: Option<i64>,
+    pub task_id: Option<i64>,
+    pub title: String,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub work_minutes: i64,
+    pub break_minutes: i64,
+    pub status: String, // RUNNING, COMPLETED, CANCELLED
+    pub interruptions: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StartFocusInput {
+    pub block_id: i64,
+    #[serde(default)]
+    pub work_minutes: Option<i64>,
+    #[serde(default)]
+    pub break_minutes: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusTaskStats {
+    pub task_id: Option<i64>,
+    pub title: String,
+    pub completed_sessions: i64,
+    pub focus_minutes: i64,
+    pub interruptions: i64,
+}
+
+/// Focus sessions of one logical day; cancelled sessions count the minutes actually spent.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusDayStats {
+    pub logical_date: String, // "YYYY-MM-DD"
+    pub completed_sessions: i64,
+    pub cancelled_sessions: i64,
+    pub focus_minutes: i64,
+    pub interruptions: i64,
+    pub tasks: Vec<FocusTaskStats>,
+}
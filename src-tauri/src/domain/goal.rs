@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// How a key result is measured: minutes of linked DONE work plus logged minutes,
+/// or a number that check-ins set directly.
+pub const KEY_RESULT_METRICS: [&str; 2] = ["TIME", "NUMBER"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Goal {
+    pub id: i64,
+    pub workspace_id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    /// Logical dates the goal runs over, "YYYY-MM-DD", both inclusive.
+    pub period_start: String,
+    pub period_end: String,
+    pub created_at: String,
+    #[sqlx(skip)]
+    pub key_results: Vec<KeyResult>,
+    /// Mean of the key results' progress, each capped at 100.
+    #[sqlx(skip)]
+    pub progress_percent: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyResult {
+    pub id: i64,
+    pub goal_id: i64,
+    pub title: String,
+    pub metric: String, // "TIME" | "NUMBER"
+    /// Minutes for `TIME`, the metric's own unit otherwise.
+    pub target_value: f64,
+    pub unit: Option<String>,
+    pub position: i64,
+    /// Worked minutes of DONE blocks of linked tasks within the goal period.
+    #[sqlx(default)]
+    pub linked_minutes: i64,
+    #[sqlx(default)]
+    pub linked_task_count: i64,
+    #[sqlx(default)]
+    pub current_value: f64,
+    /// `current_value` against the target; may exceed 100.
+    #[sqlx(skip)]
+    pub progress_percent: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyResultCheckIn {
+    pub id: i64,
+    pub key_result_id: i64,
+    /// Minutes worked outside the timeline for `TIME`, the new current value for `NUMBER`.
+    pub value: f64,
+    pub note: Option<String>,
+    pub checked_at: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalInput {
+    pub workspace_id: i64, // Ignored on update
+    pub title: String,
+    pub description: Option<String>,
+    pub period_start: String,
+    pub period_end: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyResultInput {
+    pub goal_id: i64, // Ignored on update
+    pub title: String,
+    pub metric: String,
+    pub target_value: f64,
+    pub unit: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckInInput {
+    pub key_result_id: i64,
+    pub value: f64,
+    pub note: Option<String>,
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// An urgent task that cut the NOW block short. Titles are copied so the record
+/// outlives deleted tasks; project and label are those of the interrupting task.
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Interruption {
+    pub id: i64,
+    pub workspace_id: i64,
+    pub interrupting_task_id: Option<i64>,
+    pub interrupting_title: String,
+    pub interrupted_task_id: Option<i64>,
+    pub interrupted_title: String,
+    pub occurred_at: String,
+    /// Minutes the urgent task took from the interrupted one.
+    pub lost_minutes: i64,
+    #[sqlx(default)]
+    pub project_name: Option<String>,
+    #[sqlx(default)]
+    pub label_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InterruptionGroup {
+    pub key: String,
+    pub count: i64,
+    pub lost_minutes: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InterruptionReport {
+    pub total_count: i64,
+    pub total_lost_minutes: i64,
+    /// Every logical day of the range, in order.
+    pub by_day: Vec<InterruptionGroup>,
+    /// Interruptions without a project or label are left out of these, largest first.
+    pub by_project: Vec<InterruptionGroup>,
+    pub by_label: Vec<InterruptionGroup>,
+    pub items: Vec<Interruption>,
+}
@@ -3,6 +3,20 @@ pub mod workspace;
 pub mod timeline;
 pub mod achievement;
 pub mod gemini;
+pub mod analytics;
+pub mod checklist;
+pub mod dependency;
+pub mod tag;
+pub mod search;
+pub mod rollover;
+pub mod notification;
+pub mod overrun;
+pub mod focus;
+pub mod interruption;
+pub mod retrospective;
+pub mod goal;
+pub mod standup;
+pub mod evaluation;
 pub mod error;
 
 pub use user::*;
@@ -10,8 +24,33 @@ pub use workspace::*;
 pub use timeline::*;
 pub use achievement::*;
 pub use gemini::*;
+pub use analytics::*;
+pub use checklist::*;
+pub use dependency::*;
+pub use tag::*;
+pub use search::*;
+pub use rollover::*;
+pub use notification::*;
+pub use overrun::*;
+pub use focus::*;
+pub use interruption::*;
+pub use retrospective::*;
+pub use goal::*;
+pub use standup::*;
+pub use evaluation::*;
 pub use error::*;
 
 pub struct DbState {
     pub pool: sqlx::Pool<sqlx::Sqlite>,
 }
+
+/// Wakes the background notifier so it re-reads the timeline after a mutation.
+pub struct NotifierState {
+    pub wake: std::sync::Arc<tokio::sync::Notify>,
+}
+
+impl NotifierState {
+    pub fn reschedule(&self) {
+        self.wake.notify_one();
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// Per-workspace notification rules. `User.is_notification_enabled` stays the master switch.
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationSettings {
+    pub workspace_id: i64,
+    /// Notify when the next planned block is due to start.
+    pub notify_at_start: bool,
+    /// Minutes before the end of the NOW block to warn; 0 disables the warning.
+    pub lead_minutes: i64,
+    pub notify_at_end: bool,
+    /// Repeat every N minutes while the NOW block runs past its end; 0 disables reminders.
+    pub overrun_interval_minutes: i64,
+    /// `HH:MM`; notifications between start and end are dropped. May wrap past midnight.
+    pub quiet_start: Option<String>,
+    pub quiet_end: Option<String>,
+}
+
+impl NotificationSettings {
+    pub fn defaults(workspace_id: i64) -> Self {
+        Self {
+            workspace_id,
+            notify_at_start: true,
+            lead_minutes: 5,
+            notify_at_end: true,
+            overrun_interval_minutes: 15,
+            quiet_start: None,
+            quiet_end: None,
+        }
+    }
+}
+
+/// Omitted fields keep their value; empty quiet hours clear them.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationSettingsInput {
+    #[serde(default)]
+    pub notify_at_start: Option<bool>,
+    #[serde(default)]
+    pub lead_minutes: Option<i64>,
+    #[serde(default)]
+    pub notify_at_end: Option<bool>,
+    #[serde(default)]
+    pub overrun_interval_minutes: Option<i64>,
+    #[serde(default)]
+    pub quiet_start: Option<String>,
+    #[serde(default)]
+    pub quiet_end: Option<String>,
+}
+
+/// `BLOCK_START`, `BEFORE_END`, `AT_END`, `OVERRUN` or `FOCUS_END`.
+pub const NOTIFICATION_KINDS: [&str; 5] = ["BLOCK_START", "BEFORE_END", "AT_END", "OVERRUN", "FOCUS_END"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledNotification {
+    pub workspace_id: i64,
+    /// Clicking the notification opens the transition modal of this block.
+    pub block_id: i64,
+    pub kind: String,
+    pub fire_at: String,
+    pub title: String,
+    pub body: String,
+}
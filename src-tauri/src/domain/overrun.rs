@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// What happens to a NOW block once it passes its planned end: push the end back in
+/// `overrun_extend_minutes` steps, mark it done at the planned end, or only record the overrun.
+pub const OVERRUN_POLICIES: [&str; 3] = ["EXTEND", "COMPLETE", "FLAG"];
+
+/// Longest step `EXTEND` may use; a day is as far as an overrun is worth pushing at once.
+pub const MAX_OVERRUN_EXTEND_MINUTES: i64 = 24 * 60;
+
+/// One policy applied by the background overrun check.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrunAction {
+    pub workspace_id: i64,
+    pub block_id: i64,
+    pub policy: String,
+    /// Minutes recorded on the block so far.
+    pub overrun_minutes: i64,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Range of the mood and energy scores.
+pub const MIN_RETROSPECTIVE_SCORE: i64 = 1;
+pub const MAX_RETROSPECTIVE_SCORE: i64 = 5;
+
+/// The user's own look back on one logical day of a workspace.
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Retrospective {
+    pub id: i64,
+    pub workspace_id: i64,
+    /// Logical date the entry is about, "YYYY-MM-DD".
+    pub logical_date: String,
+    pub went_well: Option<String>,
+    pub blockers: Option<String>,
+    pub mood_score: Option<i64>,
+    pub energy_score: Option<i64>,
+    pub learnings: Option<String>,
+    pub tomorrow_focus: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveRetrospectiveInput {
+    pub workspace_id: i64,
+    pub logical_date: String, // "YYYY-MM-DD"
+    pub went_well: Option<String>,
+    pub blockers: Option<String>,
+    pub mood_score: Option<i64>,
+    pub energy_score: Option<i64>,
+    pub learnings: Option<String>,
+    pub tomorrow_focus: Option<String>,
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// What the day rollover does with unfinished blocks left on past days:
+/// return the task to the inbox, carry it onto today's timeline, or mark it done at its planned end.
+pub const ROLLOVER_POLICIES: [&str; 3] = ["INBOX", "CARRY", "COMPLETE"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverReport {
+    pub id: i64,
+    pub workspace_id: i64,
+    /// Logical date the rollover ran for, "YYYY-MM-DD".
+    pub logical_date: String,
+    pub policy: String,
+    pub created_at: String,
+    /// Set once the user has confirmed the report.
+    pub acknowledged_at: Option<String>,
+    #[sqlx(skip)]
+    pub items: Vec<RolloverItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverItem {
+    pub id: i64,
+    pub report_id: i64,
+    pub task_id: Option<i64>,
+    pub title: String,
+    /// Logical date the leftover blocks were planned on.
+    pub from_date: String,
+    /// Planned minutes of the leftover blocks.
+    pub minutes: i64,
+    /// Action actually applied (`INBOX`, `CARRY` or `COMPLETE`); a task with finished
+    /// blocks cannot return to the inbox and is carried instead.
+    pub action: String,
+    /// Start of the carried blocks on today's timeline.
+    pub new_start: Option<String>,
+}
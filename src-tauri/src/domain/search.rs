@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Searchable document kinds: a task (title + planning memo), a block review
+/// memo, or an achievement.
+pub const SEARCH_KINDS: [&str; 3] = ["TASK", "REVIEW", "ACHIEVEMENT"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQuery {
+    pub text: String,
+    #[serde(default)]
+    pub workspace_id: Option<i64>,
+    #[serde(default)]
+    pub project_id: Option<i64>,
+    #[serde(default)]
+    pub label_id: Option<i64>,
+    /// Logical date "YYYY-MM-DD", inclusive. Either bound drops unscheduled inbox tasks.
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// Logical date "YYYY-MM-DD", inclusive.
+    #[serde(default)]
+    pub end_date: Option<String>,
+    /// Subset of `SEARCH_KINDS`; all kinds when omitted.
+    #[serde(default)]
+    pub kinds: Option<Vec<String>>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub kind: String,
+    /// Task, time block or achievement id depending on `kind`.
+    pub ref_id: i64,
+    pub workspace_id: i64,
+    pub task_id: Option<i64>,
+    /// Title with matches wrapped in `<mark>`.
+    pub title: String,
+    /// Best matching excerpt of the memo or content, matches wrapped in `<mark>`.
+    pub snippet: String,
+    pub occurred_at: Option<String>,
+    pub project_name: Option<String>,
+    pub label_name: Option<String>,
+    /// bm25 rank; lower is more relevant.
+    pub score: f64,
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// `TEMPLATE` fills a fixed layout without AI; `AI` lets the LLM phrase it.
+pub const STANDUP_MODES: [&str; 2] = ["TEMPLATE", "AI"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StandupInput {
+    pub workspace_id: i64,
+    #[serde(default)]
+    pub logical_date: Option<String>, // "YYYY-MM-DD", defaults to today
+    #[serde(default)]
+    pub mode: Option<String>, // Defaults to `TEMPLATE`
+    #[serde(default)]
+    pub target_model: Option<String>,
+    #[serde(default)]
+    pub force_retry: bool,
+}
+
+/// One task in a standup section; split tasks are merged into a single item.
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StandupItem {
+    pub task_id: Option<i64>,
+    pub title: String,
+    pub status: String, // Block status, or "BLOCKED"
+    /// Worked minutes for finished work, planned minutes otherwise.
+    pub minutes: i64,
+    pub project_name: Option<String>,
+    /// Review memo of finished work, unfinished prerequisites of blocked tasks.
+    pub note: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Standup {
+    pub logical_date: String,
+    /// Last earlier logical day with finished work, looking back a week.
+    pub previous_date: String,
+    pub yesterday: Vec<StandupItem>,
+    pub today: Vec<StandupItem>,
+    pub blocked: Vec<StandupItem>,
+    pub markdown: String,
+    /// Slack-style text: `*bold*` headings and `•` bullets.
+    pub plain_text: String,
+    pub used_model: Option<String>,
+}
@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Task priorities run from P0 (highest) to P3; tasks without an explicit priority are P2.
+pub const DEFAULT_TASK_PRIORITY: i64 = 2;
 
 #[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +14,18 @@ pub struct Task {
     pub estimated_minutes: i64,
     pub project_id: Option<i64>,
     pub label_id: Option<i64>,
+    #[sqlx(default)]
+    pub due_at: Option<String>,
+    #[sqlx(default)]
+    pub is_at_risk: bool, // The last unfinished block ends after due_at
+    #[sqlx(default)]
+    pub priority: i64,
+    #[sqlx(default)]
+    pub is_blocked: bool, // Some prerequisite is not finished yet
+    #[sqlx(default)]
+    pub key_result_id: Option<i64>, // Key result this task advances
+    #[sqlx(skip)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
@@ -31,6 +47,27 @@ pub struct TimeBlock {
     pub label_name: Option<String>,
     #[sqlx(default)]
     pub label_color: Option<String>,
+    #[sqlx(default)]
+    pub paused_minutes: i64,
+    #[sqlx(default)]
+    pub is_paused: bool,
+    #[sqlx(default)]
+    pub overrun_minutes: i64, // Minutes the block ran or was extended past its planned end
+    #[sqlx(default)]
+    pub priority: Option<i64>,
+    #[sqlx(default)]
+    pub checklist_progress: Option<i64>, // Percent of checked items, None without a checklist
+    #[sqlx(skip)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockInterval {
+    pub id: i64,
+    pub block_id: i64,
+    pub started_at: String,
+    pub ended_at: Option<String>, // None while the block is being worked on
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -45,6 +82,62 @@ pub struct AddTaskInput {
     pub is_inbox: Option<bool>,
     pub project_name: Option<String>,
     pub label_name: Option<String>,
+    #[serde(default)]
+    pub due_at: Option<String>,
+    #[serde(default)]
+    pub priority: Option<i64>, // 0 (P0) to 3 (P3), defaults to DEFAULT_TASK_PRIORITY
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Logical date "YYYY-MM-DD" whose plan the task is appended to; today when omitted.
+    #[serde(default)]
+    pub target_date: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddResult {
+    pub input: AddTaskInput,
+    pub diagnostics: Vec<String>, // Human-readable notes about ignored or defaulted tokens
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedTask {
+    pub task_id: i64,
+    pub title: String,
+    pub minutes: i64,
+    pub start_time: String,
+    pub end_time: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DayPlanPreview {
+    pub window_start: String,
+    pub window_end: String,
+    pub capacity_minutes: i64,  // Window minus unplugged times
+    pub scheduled_minutes: i64, // Already taken by NOW/WILL/PENDING blocks
+    pub remaining_minutes: i64,
+    pub planned: Vec<PlannedTask>,
+    pub skipped: Vec<Task>,     // Inbox tasks that do not fit into the remaining window
+}
+
+/// Block minutes (duration minus paused time) of one logical day.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DayTotals {
+    pub by_status: BTreeMap<String, i64>,  // Every status, UNPLUGGED included
+    pub by_project: BTreeMap<String, i64>, // Task blocks with a project only
+    pub by_label: BTreeMap<String, i64>,   // Task blocks with a label only
+    pub overrun_minutes: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineDay {
+    pub logical_date: String, // "YYYY-MM-DD"
+    pub blocks: Vec<TimeBlock>,
+    pub totals: DayTotals,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -67,4 +160,10 @@ pub struct UpdateTaskInput {
     pub review_memo: Option<String>,
     pub project_name: Option<String>,
     pub label_name: Option<String>,
+    #[serde(default)]
+    pub due_at: Option<String>, // None keeps the current due date, an empty string clears it
+    #[serde(default)]
+    pub priority: Option<i64>,  // None keeps the current priority
+    #[serde(default)]
+    pub tags: Option<Vec<String>>, // None keeps the current tags, an empty list removes them
 }
@@ -8,6 +8,12 @@ pub struct Workspace {
     pub core_time_start: Option<String>,
     pub core_time_end: Option<String>,
     pub role_intro: Option<String>,
+    /// What the day rollover does with unfinished blocks of past days; one of `ROLLOVER_POLICIES`.
+    pub rollover_policy: String,
+    /// What happens to a NOW block once it passes its end; one of `OVERRUN_POLICIES`.
+    pub overrun_policy: String,
+    /// Step by which `EXTEND` pushes the end of an overrunning block.
+    pub overrun_extend_minutes: i64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
@@ -24,23 +30,69 @@ pub struct UnpluggedTime {
 #[serde(rename_all = "camelCase")]
 pub struct Project {
     pub id: i64,
+    /// `None` means the project is shared across every workspace.
+    pub workspace_id: Option<i64>,
     pub name: String,
     pub last_used: String,
+    pub description: Option<String>,
+    pub role: Option<String>,
+    pub tech_stack: Option<String>,
+    pub link: Option<String>,
+    /// `YYYY-MM-DD`
+    pub start_date: Option<String>,
+    /// `YYYY-MM-DD`
+    pub end_date: Option<String>,
+    /// ACTIVE, FINISHED or ARCHIVED. Archived projects are hidden from autocomplete.
+    pub status: String,
 }
 
+pub const PROJECT_STATUSES: [&str; 3] = ["ACTIVE", "FINISHED", "ARCHIVED"];
+
 #[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct Label {
     pub id: i64,
+    /// `None` means the label is shared across every workspace.
+    pub workspace_id: Option<i64>,
     pub name: String,
     pub color: String,
     pub last_used: String,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// On update, omitted fields keep their value and an empty string clears them.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectInput {
     pub name: String,
+    /// Owning workspace on create; omit to create a shared project.
+    #[serde(default)]
+    pub workspace_id: Option<i64>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub tech_stack: Option<String>,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDetail {
+    #[serde(flatten)]
+    pub project: Project,
+    /// Worked minutes over the project's DONE blocks.
+    pub total_minutes: i64,
+    pub task_count: i64,
+    pub first_activity: Option<String>,
+    pub last_activity: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -48,6 +100,17 @@ pub struct ProjectInput {
 pub struct LabelInput {
     pub name: String,
     pub color: String,
+    /// Owning workspace on create; omit to create a shared label.
+    #[serde(default)]
+    pub workspace_id: Option<i64>,
+}
+
+/// Projects or labels whose names only differ in case, spacing or punctuation.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup<T> {
+    pub key: String,
+    pub items: Vec<T>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -58,6 +121,14 @@ pub struct CreateWorkspaceInput {
     pub core_time_end: Option<String>,
     pub role_intro: Option<String>,
     pub unplugged_times: Vec<UnpluggedTimeInput>,
+    /// Kept as is on update when omitted; `INBOX` on create.
+    #[serde(default)]
+    pub rollover_policy: Option<String>,
+    /// Kept as is on update when omitted; `FLAG` and 15 minutes on create.
+    #[serde(default)]
+    pub overrun_policy: Option<String>,
+    #[serde(default)]
+    pub overrun_extend_minutes: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -67,3 +138,27 @@ pub struct UnpluggedTimeInput {
     pub start_time: String,
     pub end_time: String,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOccurrence {
+    pub task_id: i64,
+    pub title: String,
+    pub planning_memo: Option<String>,
+    pub project_name: Option<String>,
+    pub label_name: Option<String>,
+    pub actual_minutes: Option<i64>, // None while nothing has been finished
+    pub last_used: Option<String>,   // Latest block start, None for inbox-only tasks
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskSuggestion {
+    pub title: String,
+    pub occurrences: i64,
+    pub median_minutes: Option<i64>,
+    pub project_name: Option<String>,
+    pub label_name: Option<String>,
+    pub planning_memo: Option<String>,
+    pub last_used: Option<String>,
+}
@@ -2,12 +2,13 @@ pub mod domain;
 pub mod database;
 pub mod services;
 pub mod commands;
+pub mod notifier;
 
 use std::fs;
 use tauri::{Manager, Emitter, Listener};
 use sqlx::sqlite::SqlitePool;
 use serde_json;
-use crate::domain::DbState;
+use crate::domain::{DbState, NotifierState};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -66,6 +67,23 @@ pub fn run() {
                     if args.contains(&"clear".to_string()) || args.contains(&"init".to_string()) {
                         println!("🚀 [Dev Mode] Cleaning database...");
                         sqlx::query("DELETE FROM achievements").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM rollover_report_items").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM rollover_reports").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM notification_settings").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM focus_sessions").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM interruptions").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM retrospectives").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM key_result_check_ins").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM key_results").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM goals").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM competencies").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM block_intervals").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM plan_snapshot_blocks").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM plan_snapshots").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM task_checklist_items").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM task_dependencies").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM task_tags").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM tags").execute(&pool).await.ok();
                         sqlx::query("DELETE FROM time_blocks").execute(&pool).await.ok();
                         sqlx::query("DELETE FROM tasks").execute(&pool).await.ok();
                         sqlx::query("DELETE FROM projects").execute(&pool).await.ok();
@@ -75,6 +93,7 @@ pub fn run() {
                         sqlx::query("DELETE FROM users").execute(&pool).await.ok();
                         sqlx::query("DELETE FROM gemini_models").execute(&pool).await.ok();
                         sqlx::query("DELETE FROM ai_usage_logs").execute(&pool).await.ok();
+                        sqlx::query("DELETE FROM search_index").execute(&pool).await.ok();
                         println!("✅ [Dev Mode] Database cleared.");
                     }
 
@@ -180,42 +199,15 @@ pub fn run() {
                     }
                 }
                 
-                // Migrations
-                sqlx::query("CREATE TABLE IF NOT EXISTS users (id INTEGER PRIMARY KEY CHECK (id = 1), nickname TEXT NOT NULL, gemini_api_key TEXT, lang TEXT NOT NULL DEFAULT 'en', last_successful_model TEXT, is_notification_enabled BOOLEAN NOT NULL DEFAULT 0, is_free_user BOOLEAN NOT NULL DEFAULT 1, day_start_time TEXT NOT NULL DEFAULT '04:00')").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE users ADD COLUMN lang TEXT NOT NULL DEFAULT 'en'").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE users ADD COLUMN last_successful_model TEXT").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE users ADD COLUMN is_notification_enabled BOOLEAN NOT NULL DEFAULT 0").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE users ADD COLUMN is_free_user BOOLEAN NOT NULL DEFAULT 1").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE users ADD COLUMN day_start_time TEXT NOT NULL DEFAULT '04:00'").execute(&pool).await.ok();
-                
-                sqlx::query("CREATE TABLE IF NOT EXISTS workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.ok();
-                sqlx::query("CREATE TABLE IF NOT EXISTS unplugged_times (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, label TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE)").execute(&pool).await.ok();
-                sqlx::query("CREATE TABLE IF NOT EXISTS projects (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, last_used TEXT NOT NULL)").execute(&pool).await.ok();
-                sqlx::query("CREATE TABLE IF NOT EXISTS labels (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, color TEXT NOT NULL, last_used TEXT NOT NULL)").execute(&pool).await.ok();
-
-                sqlx::query("CREATE TABLE IF NOT EXISTS tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, estimated_minutes INTEGER NOT NULL DEFAULT 0, FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE)").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE tasks ADD COLUMN estimated_minutes INTEGER NOT NULL DEFAULT 0").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE tasks ADD COLUMN project_id INTEGER REFERENCES projects (id) ON DELETE SET NULL").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE tasks ADD COLUMN label_id INTEGER REFERENCES labels (id) ON DELETE SET NULL").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE tasks ADD COLUMN position INTEGER NOT NULL DEFAULT 0").execute(&pool).await.ok();
-                sqlx::query("CREATE TABLE IF NOT EXISTS time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL, review_memo TEXT, is_urgent BOOLEAN NOT NULL DEFAULT 0, FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE, FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE)").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE time_blocks ADD COLUMN is_urgent BOOLEAN NOT NULL DEFAULT 0").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE time_blocks ADD COLUMN planning_memo TEXT").execute(&pool).await.ok();
-
-                sqlx::query("CREATE TABLE IF NOT EXISTS achievements (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, achievement_type TEXT NOT NULL, content TEXT NOT NULL, date_label TEXT NOT NULL, created_at TEXT NOT NULL, used_model TEXT, FOREIGN KEY (workspace_id) REFERENCES workspaces (id) ON DELETE CASCADE)").execute(&pool).await.ok();
-                sqlx::query("ALTER TABLE achievements ADD COLUMN used_model TEXT").execute(&pool).await.ok();
-
-                sqlx::query(crate::database::gemini::CREATE_GEMINI_MODELS_TABLE).execute(&pool).await.ok();
-                sqlx::query(crate::database::gemini::CREATE_AI_USAGE_LOGS_TABLE).execute(&pool).await.ok();
-
-                sqlx::query("DROP TABLE IF EXISTS recurring_tasks").execute(&pool).await.ok();
-
-                // Normalization: Ensure all time blocks and unplugged times have :00 seconds
-                sqlx::query("UPDATE time_blocks SET start_time = strftime('%Y-%m-%dT%H:%M:00', start_time), end_time = strftime('%Y-%m-%dT%H:%M:00', end_time)").execute(&pool).await.ok();
-                sqlx::query("UPDATE unplugged_times SET start_time = strftime('%H:%M', start_time), end_time = strftime('%H:%M', end_time)").execute(&pool).await.ok();
+                crate::database::run_migrations(&pool).await;
 
                 app_handle.manage(DbState { pool });
 
+                // Block start/end reminders, rescheduled by the mutating commands
+                let wake = std::sync::Arc::new(tokio::sync::Notify::new());
+                app_handle.manage(NotifierState { wake: wake.clone() });
+                crate::notifier::spawn(app_handle.clone(), wake);
+
                 // Sync Gemini models on startup
                 let sync_handle = app_handle.clone();
                 tauri::async_runtime::spawn(async move {
@@ -246,24 +238,40 @@ pub fn run() {
             commands::workspace::get_unplugged_times,
             commands::workspace::suggest_task_titles,
             commands::workspace::get_projects,
+            commands::workspace::get_project_detail,
             commands::workspace::create_project,
             commands::workspace::update_project,
+            commands::workspace::set_project_scope,
             commands::workspace::delete_project,
             commands::workspace::get_labels,
             commands::workspace::create_label,
             commands::workspace::update_label,
+            commands::workspace::set_label_scope,
             commands::workspace::delete_label,
+            commands::workspace::find_duplicate_projects,
+            commands::workspace::find_duplicate_labels,
+            commands::workspace::merge_projects,
+            commands::workspace::merge_labels,
+            commands::workspace::reassign_tasks_project,
+            commands::workspace::reassign_tasks_label,
             commands::timeline::get_greeting,
             commands::timeline::add_task,
+            commands::timeline::quick_add_task,
             commands::timeline::get_timeline,
+            commands::timeline::get_timeline_range,
             commands::timeline::get_inbox,
+            commands::timeline::get_at_risk_tasks,
             commands::timeline::update_task,
             commands::timeline::move_to_inbox,
             commands::timeline::move_to_timeline,
             commands::timeline::move_all_to_timeline,
+            commands::timeline::preview_day_plan,
+            commands::timeline::apply_day_plan,
             commands::timeline::delete_task,
             commands::timeline::handle_split_task_deletion,
             commands::timeline::process_task_transition,
+            commands::timeline::pause_block,
+            commands::timeline::resume_block,
             commands::timeline::update_block_status,
             commands::timeline::reorder_blocks,
             commands::timeline::reorder_inbox,
@@ -277,7 +285,51 @@ pub fn run() {
             commands::achievement::get_saved_achievements,
             commands::achievement::get_latest_saved_achievement,
             commands::achievement::fetch_available_models,
-            commands::gemini::check_daily_exhausted_log
+            commands::gemini::check_daily_exhausted_log,
+            commands::analytics::get_estimation_accuracy,
+            commands::checklist::get_checklist,
+            commands::checklist::add_checklist_item,
+            commands::checklist::update_checklist_item,
+            commands::checklist::toggle_checklist_item,
+            commands::checklist::delete_checklist_item,
+            commands::checklist::reorder_checklist,
+            commands::dependency::get_task_dependencies,
+            commands::dependency::add_task_dependency,
+            commands::dependency::remove_task_dependency,
+            commands::tag::get_tags,
+            commands::tag::get_tag_stats,
+            commands::search::search,
+            commands::rollover::run_rollover,
+            commands::rollover::get_rollover_reports,
+            commands::rollover::acknowledge_rollover_report,
+            commands::notification::get_notification_settings,
+            commands::notification::update_notification_settings,
+            commands::focus::start_focus_session,
+            commands::focus::get_active_focus_session,
+            commands::focus::interrupt_focus_session,
+            commands::focus::cancel_focus_session,
+            commands::focus::get_focus_stats,
+            commands::interruption::get_interruption_report,
+            commands::retrospective::save_retrospective,
+            commands::retrospective::get_retrospective,
+            commands::retrospective::get_retrospectives,
+            commands::retrospective::delete_retrospective,
+            commands::goal::get_goals,
+            commands::goal::get_goal,
+            commands::goal::create_goal,
+            commands::goal::update_goal,
+            commands::goal::delete_goal,
+            commands::goal::create_key_result,
+            commands::goal::update_key_result,
+            commands::goal::delete_key_result,
+            commands::goal::add_key_result_check_in,
+            commands::goal::get_key_result_check_ins,
+            commands::goal::delete_key_result_check_in,
+            commands::goal::set_task_key_result,
+            commands::standup::generate_standup,
+            commands::evaluation::get_competencies,
+            commands::evaluation::save_competencies,
+            commands::evaluation::generate_self_evaluation
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
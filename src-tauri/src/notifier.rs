@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use chrono::{Duration, Local, NaiveDateTime};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::Notify;
+use crate::domain::DbState;
+use crate::services;
+
+/// Longest sleep between passes, so settings and clock changes are picked up without a wake.
+const MAX_IDLE_SECONDS: i64 = 60;
+
+/// Runs the notification loop: fires whatever fell due since the last pass, applies the overrun
+/// policies and closes finished focus sessions, then sleeps until the next notification or until a timeline mutation wakes it
+/// through `NotifierState`.
+pub fn spawn(app: AppHandle, wake: Arc<Notify>) {
+    tauri::async_runtime::spawn(async move {
+        let mut checked_until = Local::now().naive_local();
+        loop {
+            let now = Local::now().naive_local();
+            let state = app.state::<DbState>();
+            match services::notification::notifications_between(&state.pool, checked_until, now).await {
+                Ok(due) => {
+                    for n in due {
+                        // The block id lets the notification-action listener open its transition modal
+                        if let Err(e) = app.notification().builder().id(n.block_id as i32).title(&n.title).body(&n.body).show() {
+                            eprintln!("Failed to show notification: {}", e);
+                        }
+                    }
+                }
+                Err(e) => eprintln!("Failed to plan notifications: {}", e),
+            }
+            checked_until = now;
+
+            // After the notifications, so the end of a block is announced before it is extended or closed
+            match services::overrun::apply_overrun_policies_at(&state.pool, now).await {
+                Ok(actions) => {
+                    let mut workspace_ids: Vec<i64> = actions.iter().map(|a| a.workspace_id).collect();
+                    workspace_ids.sort_unstable();
+                    workspace_ids.dedup();
+                    for workspace_id in workspace_ids {
+                        app.emit("timeline-updated", workspace_id).ok();
+                    }
+                }
+                Err(e) => eprintln!("Failed to apply overrun policies: {}", e),
+            }
+            match services::focus::complete_due_focus_sessions_at(&state.pool, now).await {
+                Ok(sessions) => {
+                    let mut workspace_ids: Vec<i64> = sessions.iter().map(|s| s.workspace_id).collect();
+                    workspace_ids.sort_unstable();
+                    workspace_ids.dedup();
+                    for workspace_id in workspace_ids {
+                        app.emit("timeline-updated", workspace_id).ok();
+                    }
+                }
+                Err(e) => eprintln!("Failed to close focus sessions: {}", e),
+            }
+
+            let horizon = now + Duration::seconds(MAX_IDLE_SECONDS);
+            let next_fire = services::notification::notifications_between(&state.pool, now, horizon).await
+                .ok()
+                .and_then(|upcoming| upcoming.first().and_then(|n| NaiveDateTime::parse_from_str(&n.fire_at, "%Y-%m-%dT%H:%M:%S").ok()))
+                .unwrap_or(horizon);
+            let wait = (next_fire - now).to_std().unwrap_or_default();
+
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = wake.notified() => {
+                    // Notifications already due before the mutation are not re-fired
+                    checked_until = Local::now().naive_local();
+                }
+            }
+        }
+    });
+}
@@ -1,6 +1,6 @@
 use sqlx::SqlitePool;
 use chrono::NaiveDateTime;
-use crate::domain::{Achievement, Result, AppError, DbGeminiModel};
+use crate::domain::{Achievement, AchievementOptions, CompletedTaskBlock, Result, AppError, DbGeminiModel};
 use crate::database;
 use crate::services;
 
@@ -14,6 +14,7 @@ pub async fn generate_achievement(
     force_retry: bool,
     overwrite: bool,
     target_model: Option<String>,
+    options: AchievementOptions,
 ) -> Result<Achievement> {
     let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
 
@@ -35,13 +36,25 @@ pub async fn generate_achievement(
         + chrono::Duration::days(1) - chrono::Duration::seconds(1))
         .format("%Y-%m-%dT%H:%M:00").to_string();
 
-    let blocks = database::achievement::get_completed_task_blocks(pool, workspace_id, &start_of_range, &end_of_range).await?;
+    let tag_filter = options.tags.as_deref().map(services::tag::normalize_tags).filter(|t| !t.is_empty());
+    let blocks = database::achievement::get_completed_task_blocks(pool, workspace_id, &start_of_range, &end_of_range, tag_filter.as_deref()).await?;
 
     if blocks.is_empty() {
         return Err(AppError::InvalidInput("No completed tasks found for the selected period.".to_string()));
     }
 
+    let tasks_heading = if blocks.iter().any(|b| b.goal_title.is_some()) {
+        "**Completed Tasks** (NOTE: Grouped under the goals they advanced. In Key Achievements, group the bullets under each goal as a bold sub-heading and keep the other work after them.):"
+    } else {
+        "**Completed Tasks**:"
+    };
     let task_summary = build_task_summary(blocks);
+    let retrospective_context = if options.include_retrospectives {
+        let retros = database::retrospective::get_retrospectives_in_range(pool, workspace_id, start_date, end_date).await?;
+        services::retrospective::build_retrospective_context(&retros)
+    } else {
+        String::new()
+    };
     
     let base_system_prompt = "You are an expert technical writer helping a professional document their daily achievements.
 Your goal is to transform the user's raw task logs into a highly objective, professional 'Brag Document' (Performance Report) suitable for a resume or performance review.
@@ -77,15 +90,23 @@ CRITICAL RULE: Regardless of the instructions above, you MUST generate the final
         format!("Performance Summary from {} to {}", start_date, end_date)
     };
 
-    let user_content = format!(
+    let mut user_content = format!(
         "**Period**: {}
 
 **User Role/Context**: {} (NOTE: Use this ONLY to understand the technical context. DO NOT mention this role in your generated output.)
 
-**Completed Tasks**:
+{}
 {}",
-        period_desc, role_intro, task_summary
+        period_desc, role_intro, tasks_heading, task_summary
     );
+    if !retrospective_context.is_empty() {
+        user_content.push_str(&format!(
+            "
+**Daily Retrospectives** (NOTE: The user's own notes. Use them ONLY to better understand the impact of the completed tasks. DO NOT document them as separate items.):
+{}",
+            retrospective_context
+        ));
+    }
 
     let (result_text, final_model_name) = match target_model {
         Some(model_name) => {
@@ -153,25 +174,72 @@ pub async fn fetch_available_models(
     database::gemini::get_active_models(pool).await.map_err(AppError::Database)
 }
 
-fn build_task_summary(blocks: Vec<(String, Option<String>, Option<String>, String, String)>) -> String {
+/// Task entries for the prompt. When some tasks advance a goal, the entries are grouped
+/// under their goals in order of first appearance, followed by the remaining work.
+fn build_task_summary(blocks: Vec<CompletedTaskBlock>) -> String {
     let mut task_summary = String::new();
-    for (title, planning, review, start, end) in blocks {
-        let s = NaiveDateTime::parse_from_str(&start, "%Y-%m-%dT%H:%M:%S").unwrap_or_default();
-        let e = NaiveDateTime::parse_from_str(&end, "%Y-%m-%dT%H:%M:%S").unwrap_or_default();
-        let duration = (e - s).num_minutes();
-        
-        task_summary.push_str(&format!(
-            "### Task: {}
-- **Duration**: {} mins
+    if blocks.iter().all(|b| b.goal_title.is_none()) {
+        for block in blocks {
+            push_task_entry(&mut task_summary, block);
+        }
+        return task_summary;
+    }
+
+    let mut groups: Vec<(Option<String>, Vec<CompletedTaskBlock>)> = Vec::new();
+    for block in blocks {
+        match groups.iter_mut().find(|(goal, _)| *goal == block.goal_title) {
+            Some((_, list)) => list.push(block),
+            None => groups.push((block.goal_title.clone(), vec![block])),
+        }
+    }
+    groups.sort_by_key(|(goal, _)| goal.is_none());
+    for (goal, list) in groups {
+        match goal {
+            Some(title) => task_summary.push_str(&format!("## Goal: {}\n\n", title)),
+            None => task_summary.push_str("## Other Work\n\n"),
+        }
+        for block in list {
+            push_task_entry(&mut task_summary, block);
+        }
+    }
+    task_summary
+}
+
+fn push_task_entry(task_summary: &mut String, block: CompletedTaskBlock) {
+    let s = NaiveDateTime::parse_from_str(&block.start_time, "%Y-%m-%dT%H:%M:%S").unwrap_or_default();
+    let e = NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S").unwrap_or_default();
+    let paused = ((e - s).num_minutes() - block.worked_minutes).max(0);
+    let duration = if paused > 0 {
+        format!("{} mins (excluding {} mins paused)", block.worked_minutes, paused)
+    } else {
+        format!("{} mins", block.worked_minutes)
+    };
+    
+    task_summary.push_str(&format!(
+        "### Task: {}
+- **Duration**: {}
 - **Planning**: {}
 - **Review/Outcome**: {}
-
 ",
-            title,
-            duration,
-            planning.unwrap_or_else(|| "N/A".to_string()),
-            review.unwrap_or_else(|| "N/A".to_string())
-        ));
+        block.title,
+        duration,
+        block.planning_memo.unwrap_or_else(|| "N/A".to_string()),
+        block.review_memo.unwrap_or_else(|| "N/A".to_string())
+    ));
+    if let Some(key_result) = block.key_result_title {
+        task_summary.push_str(&format!("- **Key Result**: {}\n", key_result));
     }
-    task_summary
+    if let Some(tags) = block.tags.filter(|t| !t.is_empty()) {
+        task_summary.push_str(&format!("- **Tags**: {}\n", tags));
+    }
+    if block.focus_sessions > 0 {
+        task_summary.push_str(&format!("- **Focus Sessions**: {} completed\n", block.focus_sessions));
+    }
+    if let Some(steps) = block.completed_steps.filter(|s| !s.is_empty()) {
+        task_summary.push_str("- **Completed Steps**:\n");
+        for step in steps.lines() {
+            task_summary.push_str(&format!("  - {}\n", step));
+        }
+    }
+    task_summary.push('\n');
 }
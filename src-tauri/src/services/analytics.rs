@@ -0,0 +1,171 @@
+use sqlx::SqlitePool;
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::BTreeMap;
+use crate::domain::{TaskEstimate, EstimateGroup, DailyAccuracy, EstimationAccuracyReport, Result, AppError};
+use crate::database;
+use crate::services::timeline::{logical_date_of, logical_day_bounds};
+
+pub async fn get_estimation_accuracy(
+    pool: &SqlitePool,
+    workspace_id: i64,
+    start_date: &str, // "YYYY-MM-DD"
+    end_date: &str,   // "YYYY-MM-DD"
+) -> Result<EstimationAccuracyReport> {
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let day_start_time = user.day_start_time;
+
+    let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d").map_err(|e| AppError::DateParse(e.to_string()))?;
+    let end = NaiveDate::parse_from_str(end_date, "%Y-%m-%d").map_err(|e| AppError::DateParse(e.to_string()))?;
+    if end < start {
+        return Err(AppError::InvalidInput("The end date must not be before the start date.".to_string()));
+    }
+    let (range_start, _) = logical_day_bounds(start, &day_start_time)?;
+    let (_, range_end) = logical_day_bounds(end, &day_start_time)?;
+
+    let mut tasks = database::analytics::get_finished_task_estimates(pool, workspace_id, &range_start, &range_end).await?;
+    for task in tasks.iter_mut() {
+        task.ratio = ratio(task.actual_minutes, task.estimated_minutes);
+        task.is_overrun = task.actual_minutes > task.estimated_minutes;
+        if let Ok(last_start) = NaiveDateTime::parse_from_str(&task.last_start_time, "%Y-%m-%dT%H:%M:%S") {
+            task.logical_date = logical_date_of(last_start, &day_start_time).format("%Y-%m-%d").to_string();
+        }
+    }
+
+    let plans = database::analytics::get_daily_plans(pool, workspace_id, start_date, end_date, &day_start_time).await?;
+    let worked = database::analytics::get_daily_worked_minutes(pool, workspace_id, &range_start, &range_end, &day_start_time).await?;
+
+    let mut daily: BTreeMap<String, DailyAccuracy> = BTreeMap::new();
+    let day = |date: &str| -> DailyAccuracy {
+        DailyAccuracy { logical_date: date.to_string(), ..Default::default() }
+    };
+    for plan in plans {
+        let entry = daily.entry(plan.logical_date.clone()).or_insert_with(|| day(&plan.logical_date));
+        entry.planned_minutes = Some(plan.planned_minutes);
+        entry.planned_task_count = plan.planned_task_count;
+        entry.completed_planned_task_count = plan.completed_planned_task_count;
+    }
+    for (date, minutes) in worked {
+        daily.entry(date.clone()).or_insert_with(|| day(&date)).actual_minutes = minutes;
+    }
+    let mut finished_by_day: BTreeMap<String, (i64, i64, i64)> = BTreeMap::new();
+    for task in &tasks {
+        let totals = finished_by_day.entry(task.logical_date.clone()).or_default();
+        totals.0 += task.estimated_minutes;
+        totals.1 += task.actual_minutes;
+        if task.is_overrun { totals.2 += 1; }
+    }
+    for (date, (estimated, actual, overruns)) in finished_by_day {
+        let entry = daily.entry(date.clone()).or_insert_with(|| day(&date));
+        entry.estimated_minutes = estimated;
+        entry.ratio = ratio(actual, estimated);
+        entry.overrun_count = overruns;
+    }
+
+    Ok(EstimationAccuracyReport {
+        overall: summarize(None, tasks.iter()),
+        by_project: group_by(&tasks, |t| t.project_name.clone()),
+        by_label: group_by(&tasks, |t| t.label_name.clone()),
+        daily: daily.into_values().collect(),
+        tasks,
+    })
+}
+
+fn ratio(actual: i64, estimated: i64) -> f64 {
+    if estimated <= 0 {
+        return 0.0;
+    }
+    (actual as f64 / estimated as f64 * 100.0).round() / 100.0
+}
+
+fn summarize<'a>(name: Option<String>, tasks: impl Iterator<Item = &'a TaskEstimate>) -> EstimateGroup {
+    let mut group = EstimateGroup { name, ..Default::default() };
+    for task in tasks {
+        group.task_count += 1;
+        group.estimated_minutes += task.estimated_minutes;
+        group.actual_minutes += task.actual_minutes;
+        if task.is_overrun { group.overrun_count += 1; }
+    }
+    group.ratio = ratio(group.actual_minutes, group.estimated_minutes);
+    group
+}
+
+fn group_by(tasks: &[TaskEstimate], key: impl Fn(&TaskEstimate) -> Option<String>) -> Vec<EstimateGroup> {
+    let mut buckets: BTreeMap<Option<String>, Vec<&TaskEstimate>> = BTreeMap::new();
+    for task in tasks {
+        buckets.entry(key(task)).or_default().push(task);
+    }
+    let mut groups: Vec<EstimateGroup> = buckets.into_iter().map(|(name, list)| summarize(name, list.into_iter())).collect();
+    groups.sort_by_key(|g| std::cmp::Reverse(g.actual_minutes));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new().connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY CHECK (id = 1), nickname TEXT NOT NULL, gemini_api_key TEXT, lang TEXT NOT NULL DEFAULT 'en', last_successful_model TEXT, is_notification_enabled BOOLEAN NOT NULL DEFAULT 0, is_free_user BOOLEAN NOT NULL DEFAULT 1, day_start_time TEXT NOT NULL DEFAULT '04:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO users (id, nickname) VALUES (1, 'TestUser')").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE projects (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE labels (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, color TEXT NOT NULL, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, estimated_minutes INTEGER NOT NULL DEFAULT 0, project_id INTEGER, label_id INTEGER, position INTEGER NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL, review_memo TEXT, planning_memo TEXT, is_urgent BOOLEAN NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query(database::timeline::CREATE_BLOCK_INTERVALS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::analytics::CREATE_PLAN_SNAPSHOTS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::analytics::CREATE_PLAN_SNAPSHOT_BLOCKS_TABLE).execute(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_estimation_accuracy_report() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO projects (id, name, last_used) VALUES (1, 'will-done', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, project_id) VALUES (1, 1, 'Overrun', 60, 1)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, project_id) VALUES (2, 1, 'Early', 30, 1)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes) VALUES (3, 1, 'Unfinished', 30)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes) VALUES (4, 1, 'No estimate', 0)").execute(&pool).await.unwrap();
+
+        // Overrun: 09:00-10:40 with a 10 minute pause -> 90 minutes worked
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (10, 1, 1, 'Overrun', '2026-03-01T09:00:00', '2026-03-01T10:40:00', 'DONE')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at, ended_at) VALUES (10, '2026-03-01T09:00:00', '2026-03-01T09:30:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at, ended_at) VALUES (10, '2026-03-01T09:40:00', '2026-03-01T10:40:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (11, 2, 1, 'Early', '2026-03-01T10:40:00', '2026-03-01T11:00:00', 'DONE')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (12, 3, 1, 'Unfinished', '2026-03-01T11:00:00', '2026-03-01T11:30:00', 'WILL')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (13, 4, 1, 'No estimate', '2026-03-01T12:00:00', '2026-03-01T12:30:00', 'DONE')").execute(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO plan_snapshots (id, workspace_id, logical_date, taken_at) VALUES (1, 1, '2026-03-01', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO plan_snapshot_blocks (snapshot_id, block_id, task_id, title, start_time, end_time, status, estimated_minutes) VALUES (1, 10, 1, 'Overrun', '2026-03-01T09:00:00', '2026-03-01T10:00:00', 'WILL', 60)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO plan_snapshot_blocks (snapshot_id, block_id, task_id, title, start_time, end_time, status, estimated_minutes) VALUES (1, 11, 2, 'Early', '2026-03-01T10:00:00', '2026-03-01T10:30:00', 'WILL', 30)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO plan_snapshot_blocks (snapshot_id, block_id, task_id, title, start_time, end_time, status, estimated_minutes) VALUES (1, 12, 3, 'Unfinished', '2026-03-01T10:30:00', '2026-03-01T11:00:00', 'WILL', 30)").execute(&pool).await.unwrap();
+
+        let report = get_estimation_accuracy(&pool, 1, "2026-03-01", "2026-03-01").await.unwrap();
+
+        assert_eq!(report.tasks.len(), 2);
+        assert_eq!(report.tasks[0].actual_minutes, 90);
+        assert_eq!(report.tasks[0].ratio, 1.5);
+        assert!(report.tasks[0].is_overrun);
+        assert_eq!(report.tasks[1].actual_minutes, 20);
+        assert!(!report.tasks[1].is_overrun);
+
+        assert_eq!(report.overall.task_count, 2);
+        assert_eq!(report.overall.estimated_minutes, 90);
+        assert_eq!(report.overall.actual_minutes, 110);
+        assert_eq!(report.overall.ratio, 1.22);
+        assert_eq!(report.overall.overrun_count, 1);
+        assert_eq!(report.by_project.len(), 1);
+        assert_eq!(report.by_project[0].name.as_deref(), Some("will-done"));
+
+        assert_eq!(report.daily.len(), 1);
+        let day = &report.daily[0];
+        assert_eq!(day.planned_minutes, Some(120));
+        assert_eq!(day.planned_task_count, 3);
+        assert_eq!(day.completed_planned_task_count, 2);
+        assert_eq!(day.actual_minutes, 140);
+        assert_eq!(day.overrun_count, 1);
+    }
+}
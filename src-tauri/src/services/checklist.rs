@@ -0,0 +1,106 @@
+use sqlx::SqlitePool;
+use chrono::Local;
+use crate::domain::{ChecklistItem, ChecklistItemInput, UpdateChecklistItemInput, Result, AppError};
+use crate::database;
+
+pub async fn get_checklist(pool: &SqlitePool, task_id: i64) -> Result<Vec<ChecklistItem>> {
+    database::checklist::get_checklist(pool, task_id).await
+}
+
+pub async fn add_checklist_item(pool: &SqlitePool, input: ChecklistItemInput) -> Result<ChecklistItem> {
+    validate_item(&input.title, input.estimated_minutes)?;
+    let task_exists: Option<(i64,)> = sqlx::query_as("SELECT id FROM tasks WHERE id = ?1").bind(input.task_id).fetch_optional(pool).await?;
+    if task_exists.is_none() {
+        return Err(AppError::NotFound("Task not found".to_string()));
+    }
+    let id = database::checklist::create_checklist_item(pool, &input).await?;
+    database::checklist::get_checklist_item(pool, id).await?.ok_or_else(|| AppError::NotFound("Checklist item not found".to_string()))
+}
+
+pub async fn update_checklist_item(pool: &SqlitePool, input: UpdateChecklistItemInput) -> Result<()> {
+    validate_item(&input.title, input.estimated_minutes)?;
+    database::checklist::update_checklist_item(pool, &input).await
+}
+
+pub async fn toggle_checklist_item(pool: &SqlitePool, id: i64, checked: bool) -> Result<()> {
+    let checked_at = checked.then(|| Local::now().format("%Y-%m-%dT%H:%M:00").to_string());
+    database::checklist::set_checklist_item_checked(pool, id, checked_at.as_deref()).await
+}
+
+pub async fn delete_checklist_item(pool: &SqlitePool, id: i64) -> Result<()> {
+    database::checklist::delete_checklist_item(pool, id).await
+}
+
+pub async fn reorder_checklist(pool: &SqlitePool, task_id: i64, item_ids: Vec<i64>) -> Result<()> {
+    database::checklist::reorder_checklist(pool, task_id, &item_ids).await
+}
+
+fn validate_item(title: &str, estimated_minutes: Option<i64>) -> Result<()> {
+    if title.trim().is_empty() {
+        return Err(AppError::InvalidInput("Checklist item title cannot be empty.".to_string()));
+    }
+    if estimated_minutes.is_some_and(|m| m < 0) {
+        return Err(AppError::InvalidInput("Estimated minutes cannot be negative.".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to connect to memory db");
+
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, estimated_minutes INTEGER NOT NULL DEFAULT 0, project_id INTEGER, label_id INTEGER, position INTEGER NOT NULL DEFAULT 0, key_result_id INTEGER)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL, review_memo TEXT, planning_memo TEXT, is_urgent BOOLEAN NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query(database::timeline::CREATE_BLOCK_INTERVALS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::checklist::CREATE_TASK_CHECKLIST_ITEMS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::tag::CREATE_TAGS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::tag::CREATE_TASK_TAGS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::focus::CREATE_FOCUS_SESSIONS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::goal::CREATE_GOALS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::goal::CREATE_KEY_RESULTS_TABLE).execute(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_checklist_lifecycle() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Release')").execute(&pool).await.unwrap();
+
+        let first = add_checklist_item(&pool, ChecklistItemInput { task_id: 1, title: "Write changelog".to_string(), estimated_minutes: Some(15) }).await.unwrap();
+        let second = add_checklist_item(&pool, ChecklistItemInput { task_id: 1, title: "Tag version".to_string(), estimated_minutes: None }).await.unwrap();
+        assert_eq!((first.position, second.position), (0, 1));
+
+        assert!(add_checklist_item(&pool, ChecklistItemInput { task_id: 1, title: "  ".to_string(), estimated_minutes: None }).await.is_err());
+        assert!(add_checklist_item(&pool, ChecklistItemInput { task_id: 99, title: "Orphan".to_string(), estimated_minutes: None }).await.is_err());
+
+        toggle_checklist_item(&pool, second.id, true).await.unwrap();
+        reorder_checklist(&pool, 1, vec![second.id, first.id]).await.unwrap();
+
+        let items = get_checklist(&pool, 1).await.unwrap();
+        assert_eq!(items[0].title, "Tag version");
+        assert!(items[0].is_checked);
+        assert!(items[0].checked_at.is_some());
+        assert!(!items[1].is_checked);
+
+        let blocks = database::achievement::get_completed_task_blocks(&pool, 1, "2026-03-01T04:00:00", "2026-03-02T03:59:00", None).await.unwrap();
+        assert!(blocks.is_empty());
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (1, 1, 'Release', '2026-03-01T09:00:00', '2026-03-01T10:00:00', 'DONE')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (1, 1, 'Release', '2026-03-01T11:00:00', '2026-03-01T11:30:00', 'DONE')").execute(&pool).await.unwrap();
+        let blocks = database::achievement::get_completed_task_blocks(&pool, 1, "2026-03-01T04:00:00", "2026-03-02T03:59:00", None).await.unwrap();
+        assert_eq!(blocks[0].completed_steps, None);
+        assert_eq!(blocks[1].completed_steps.as_deref(), Some("Tag version"));
+
+        toggle_checklist_item(&pool, second.id, false).await.unwrap();
+        let unchecked = database::checklist::get_checklist_item(&pool, second.id).await.unwrap().unwrap();
+        assert!(!unchecked.is_checked);
+        assert!(unchecked.checked_at.is_none());
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use sqlx::SqlitePool;
+use crate::domain::{TaskDependency, Result, AppError};
+use crate::database;
+
+pub async fn get_task_dependencies(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<TaskDependency>> {
+    database::dependency::get_task_dependencies(pool, workspace_id).await
+}
+
+/// Records that `task_id` cannot be scheduled before `blocked_by_task_id`. Both tasks must
+/// live in the same workspace, the new edge must not close a cycle, and the current timeline
+/// must already respect it; otherwise every later reorder of the day would be rejected.
+pub async fn add_task_dependency(pool: &SqlitePool, task_id: i64, blocked_by_task_id: i64) -> Result<()> {
+    if task_id == blocked_by_task_id {
+        return Err(AppError::InvalidInput("A task cannot depend on itself.".to_string()));
+    }
+    let tasks: Vec<(i64, i64, String)> = sqlx::query_as("SELECT id, workspace_id, title FROM tasks WHERE id IN (?1, ?2)")
+        .bind(task_id)
+        .bind(blocked_by_task_id)
+        .fetch_all(pool)
+        .await?;
+    let (Some(task), Some(prerequisite)) = (tasks.iter().find(|t| t.0 == task_id), tasks.iter().find(|t| t.0 == blocked_by_task_id)) else {
+        return Err(AppError::NotFound("Task not found".to_string()));
+    };
+    if task.1 != prerequisite.1 {
+        return Err(AppError::InvalidInput("Dependencies must stay within one workspace.".to_string()));
+    }
+
+    let existing = database::dependency::get_task_dependencies(pool, task.1).await?;
+    if depends_on(&existing, blocked_by_task_id, task_id) {
+        return Err(AppError::InvalidInput("This dependency would create a cycle.".to_string()));
+    }
+    if database::dependency::is_scheduled_before(pool, task_id, blocked_by_task_id).await? {
+        return Err(AppError::InvalidInput(format!("'{}' is already scheduled before its prerequisite '{}'; move it after it first.", task.2, prerequisite.2)));
+    }
+    database::dependency::add_task_dependency(pool, task_id, blocked_by_task_id).await
+}
+
+pub async fn remove_task_dependency(pool: &SqlitePool, task_id: i64, blocked_by_task_id: i64) -> Result<()> {
+    database::dependency::remove_task_dependency(pool, task_id, blocked_by_task_id).await
+}
+
+/// Whether `task_id` transitively waits for `target`.
+fn depends_on(edges: &[TaskDependency], task_id: i64, target: i64) -> bool {
+    let mut prerequisites: HashMap<i64, Vec<i64>> = HashMap::new();
+    for edge in edges {
+        prerequisites.entry(edge.task_id).or_default().push(edge.blocked_by_task_id);
+    }
+    let mut stack = vec![task_id];
+    let mut seen = vec![task_id];
+    while let Some(current) = stack.pop() {
+        if current == target {
+            return true;
+        }
+        for &next in prerequisites.get(&current).into_iter().flatten() {
+            if !seen.contains(&next) {
+                seen.push(next);
+                stack.push(next);
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_add_task_dependency_rejects_cycles() {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, start_time TEXT NOT NULL, status TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query(database::dependency::CREATE_TASK_DEPENDENCIES_TABLE).execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Write migration'), (2, 1, 'Deploy'), (3, 1, 'Announce'), (4, 2, 'Other workspace')").execute(&pool).await.unwrap();
+
+        add_task_dependency(&pool, 2, 1).await.unwrap();
+        add_task_dependency(&pool, 3, 2).await.unwrap();
+
+        assert!(add_task_dependency(&pool, 1, 3).await.is_err());
+        assert!(add_task_dependency(&pool, 1, 1).await.is_err());
+        assert!(add_task_dependency(&pool, 1, 4).await.is_err());
+        assert_eq!(get_task_dependencies(&pool, 1).await.unwrap().len(), 2);
+
+        remove_task_dependency(&pool, 3, 2).await.unwrap();
+        add_task_dependency(&pool, 1, 3).await.unwrap();
+    }
+}
@@ -0,0 +1,321 @@
+use sqlx::SqlitePool;
+use crate::domain::{Achievement, Competency, CompetencyInput, CompletedTaskBlock, SelfEvaluationInput, SELF_EVALUATION_TYPE, Result, AppError};
+use crate::database;
+use crate::services;
+use crate::services::timeline::logical_range;
+
+pub const MAX_COMPETENCIES: usize = 12;
+/// Longest review period `generate_self_evaluation` covers.
+pub const MAX_EVALUATION_PERIOD_DAYS: i64 = 366;
+/// Tasks listed in the prompt; beyond this the ones with the least worked time are summed up in one line.
+pub const MAX_EVIDENCE_TASKS: usize = 150;
+/// Most recent achievement reports quoted in the prompt.
+pub const MAX_EVIDENCE_ACHIEVEMENTS: usize = 12;
+/// Characters quoted from each achievement report.
+pub const MAX_ACHIEVEMENT_EXCERPT_CHARS: usize = 1500;
+
+pub async fn get_competencies(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<Competency>> {
+    database::evaluation::get_competencies(pool, workspace_id).await
+}
+
+/// Replaces the competency framework of a workspace with `competencies`, in that order.
+pub async fn save_competencies(pool: &SqlitePool, workspace_id: i64, competencies: Vec<CompetencyInput>) -> Result<Vec<Competency>> {
+    database::workspace::get_workspace(pool, workspace_id).await?.ok_or_else(|| AppError::NotFound("Workspace not found".to_string()))?;
+    if competencies.len() > MAX_COMPETENCIES {
+        return Err(AppError::InvalidInput(format!("A framework holds at most {} competencies.", MAX_COMPETENCIES)));
+    }
+    let mut cleaned: Vec<CompetencyInput> = Vec::new();
+    for competency in competencies {
+        let name = competency.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::InvalidInput("Every competency needs a name.".to_string()));
+        }
+        if cleaned.iter().any(|c| c.name.to_lowercase() == name.to_lowercase()) {
+            return Err(AppError::InvalidInput(format!("Duplicate competency: {}", name)));
+        }
+        let description = competency.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+        cleaned.push(CompetencyInput { name, description });
+    }
+    database::evaluation::replace_competencies(pool, workspace_id, &cleaned).await?;
+    database::evaluation::get_competencies(pool, workspace_id).await
+}
+
+/// Maps the DONE tasks and saved achievements of a review period to the workspace's competencies
+/// and saves the result as a `SELF_EVALUATION` achievement.
+pub async fn generate_self_evaluation(pool: &SqlitePool, input: SelfEvaluationInput) -> Result<Achievement> {
+    let date_label = input.date_label.trim();
+    if date_label.is_empty() {
+        return Err(AppError::InvalidInput("A self-evaluation needs a label.".to_string()));
+    }
+
+    let already_exists = database::achievement::check_achievement_exists(pool, input.workspace_id, date_label, SELF_EVALUATION_TYPE).await?;
+    if !input.overwrite && already_exists {
+        return Err(AppError::InvalidInput("A self-evaluation with this label already exists.".to_string()));
+    }
+
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let range = logical_range(&input.start_date, &input.end_date, MAX_EVALUATION_PERIOD_DAYS, &user.day_start_time)?;
+    let workspace = database::workspace::get_workspace(pool, input.workspace_id).await?.ok_or_else(|| AppError::NotFound("Workspace not found".to_string()))?;
+    let competencies = database::evaluation::get_competencies(pool, input.workspace_id).await?;
+    if competencies.is_empty() {
+        return Err(AppError::InvalidInput("Define at least one competency first.".to_string()));
+    }
+
+    let blocks = database::achievement::get_completed_task_blocks(pool, input.workspace_id, &range.start_bound, &range.end_bound, None).await?;
+    let achievements = database::evaluation::get_achievements_in_range(pool, input.workspace_id, &range.start_bound, &range.end_bound, SELF_EVALUATION_TYPE).await?;
+    if blocks.is_empty() && achievements.is_empty() {
+        return Err(AppError::InvalidInput("No completed tasks or saved achievements found for the review period.".to_string()));
+    }
+
+    let base_system_prompt = "You are an expert career coach helping a professional write the self-evaluation for their performance review.
+Your goal is to map the user's completed work to each competency of their team's framework, backed by concrete evidence.
+
+CRITICAL RULES:
+1. Evidence Only: Every bullet must be grounded in the provided tasks or achievement reports. NEVER invent work, numbers or feedback.
+2. Mapping: Use the competency descriptions to decide where evidence belongs. A piece of work may support more than one competency, but do not repeat the same bullet verbatim.
+3. Tone: First person, professional and specific. Start bullets with a strong action verb and state the impact where the evidence shows it.
+4. Gaps: If a competency has no supporting evidence, say so in one line instead of padding it.
+
+OUTPUT LAYOUT (repeat for every competency, in the given order):
+### [Competency Name]
+- [Evidence bullet]
+- ...
+**Summary**: [One sentence on how the user demonstrated this competency]";
+
+    let user_lang = if user.lang == "ko" { "Korean" } else { "English" };
+    let system_prompt = format!(
+        "{}
+
+CRITICAL RULE: Regardless of the instructions above, you MUST generate the final output entirely in the user's requested language: [{}].",
+        base_system_prompt, user_lang
+    );
+
+    let mut user_content = format!(
+        "**Review Period**: {} to {}
+
+**User Role/Context**: {} (NOTE: Use this ONLY to understand the technical context.)
+
+**Competency Framework**:
+{}
+**Completed Tasks** (split tasks are merged):
+{}",
+        range.start.format("%Y-%m-%d"),
+        range.end.format("%Y-%m-%d"),
+        workspace.role_intro.unwrap_or_else(|| "A professional worker".to_string()),
+        build_competency_list(&competencies),
+        build_task_evidence(blocks)
+    );
+    if !achievements.is_empty() {
+        user_content.push_str(&format!("\n**Saved Achievement Reports**:\n{}", build_achievement_evidence(&achievements)));
+    }
+
+    let (result_text, model_name) = match input.target_model {
+        Some(model_name) => {
+            let text = services::gemini::execute_single_model(pool, &model_name, &system_prompt, &user_content).await?;
+            (text, model_name)
+        }
+        None => services::gemini::execute_with_fallback(pool, &system_prompt, &user_content, input.force_retry).await?,
+    };
+    database::user::save_last_model(pool, &model_name).await?;
+
+    if already_exists {
+        database::achievement::update_achievement(pool, input.workspace_id, SELF_EVALUATION_TYPE, &result_text, date_label, Some(&model_name)).await
+    } else {
+        database::achievement::save_achievement(pool, input.workspace_id, SELF_EVALUATION_TYPE, &result_text, date_label, Some(&model_name)).await
+    }
+}
+
+fn build_competency_list(competencies: &[Competency]) -> String {
+    let mut list = String::new();
+    for competency in competencies {
+        match &competency.description {
+            Some(description) => list.push_str(&format!("- **{}**: {}\n", competency.name, description)),
+            None => list.push_str(&format!("- **{}**\n", competency.name)),
+        }
+    }
+    list
+}
+
+/// One entry per task title with the summed worked minutes, so a long period stays compact.
+/// At most `MAX_EVIDENCE_TASKS` tasks are listed, keeping those with the most worked time.
+fn build_task_evidence(blocks: Vec<CompletedTaskBlock>) -> String {
+    let mut tasks: Vec<(CompletedTaskBlock, Vec<String>)> = Vec::new();
+    for block in blocks {
+        let memo = block.review_memo.clone().map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+        let index = match tasks.iter().position(|(t, _)| t.title == block.title) {
+            Some(i) => {
+                tasks[i].0.worked_minutes += block.worked_minutes;
+                i
+            }
+            None => {
+                tasks.push((block, Vec::new()));
+                tasks.len() - 1
+            }
+        };
+        if let Some(memo) = memo.filter(|m| !tasks[index].1.contains(m)) {
+            tasks[index].1.push(memo);
+        }
+    }
+
+    let mut omitted = (0, 0);
+    if tasks.len() > MAX_EVIDENCE_TASKS {
+        let mut order: Vec<usize> = (0..tasks.len()).collect();
+        order.sort_by(|a, b| tasks[*b].0.worked_minutes.cmp(&tasks[*a].0.worked_minutes));
+        let mut keep = vec![false; tasks.len()];
+        for &i in &order[..MAX_EVIDENCE_TASKS] {
+            keep[i] = true;
+        }
+        let (kept, dropped): (Vec<_>, Vec<_>) = tasks.into_iter().zip(keep).partition(|(_, k)| *k);
+        omitted = (dropped.len(), dropped.iter().map(|((t, _), _)| t.worked_minutes).sum::<i64>());
+        tasks = kept.into_iter().map(|(task, _)| task).collect();
+    }
+
+    let mut evidence = String::new();
+    for (task, memos) in tasks {
+        evidence.push_str(&format!("- **{}** ({} mins)", task.title, task.worked_minutes));
+        if let Some(goal) = task.goal_title {
+            evidence.push_str(&format!(" — Goal: {}", goal));
+        }
+        if let Some(tags) = task.tags.filter(|t| !t.is_empty()) {
+            evidence.push_str(&format!(" — Tags: {}", tags));
+        }
+        evidence.push('\n');
+        if !memos.is_empty() {
+            evidence.push_str(&format!("  - Outcome: {}\n", memos.join("; ")));
+        }
+    }
+    if omitted.0 > 0 {
+        evidence.push_str(&format!("- ...and {} smaller tasks ({} mins)\n", omitted.0, omitted.1));
+    }
+    evidence
+}
+
+/// The latest `MAX_EVIDENCE_ACHIEVEMENTS` reports, each cut to `MAX_ACHIEVEMENT_EXCERPT_CHARS` characters.
+fn build_achievement_evidence(achievements: &[Achievement]) -> String {
+    let mut evidence = String::new();
+    for achievement in &achievements[achievements.len().saturating_sub(MAX_EVIDENCE_ACHIEVEMENTS)..] {
+        let content = achievement.content.trim();
+        let excerpt = match content.char_indices().nth(MAX_ACHIEVEMENT_EXCERPT_CHARS) {
+            Some((cut, _)) => format!("{}…", content[..cut].trim_end()),
+            None => content.to_string(),
+        };
+        evidence.push_str(&format!("#### {} ({})\n{}\n\n", achievement.date_label, achievement.achievement_type, excerpt));
+    }
+    evidence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = database::test_pool().await;
+        sqlx::query("INSERT INTO users (id, nickname) VALUES (1, 'TestUser')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        pool
+    }
+
+    fn competency(name: &str, description: Option<&str>) -> CompetencyInput {
+        CompetencyInput { name: name.to_string(), description: description.map(|d| d.to_string()) }
+    }
+
+    fn evaluation_input() -> SelfEvaluationInput {
+        SelfEvaluationInput {
+            workspace_id: 1,
+            start_date: "2026-01-01".to_string(),
+            end_date: "2026-06-30".to_string(),
+            date_label: "2026 H1".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_framework_is_replaced_as_a_whole() {
+        let pool = setup_db().await;
+        save_competencies(&pool, 1, vec![competency("Ownership", None), competency("Collaboration", None)]).await.unwrap();
+        let saved = save_competencies(&pool, 1, vec![
+            competency(" Technical Excellence ", Some("Quality of design and code")),
+            competency("Ownership", Some("  ")),
+        ]).await.unwrap();
+        assert_eq!(saved.iter().map(|c| (c.name.as_str(), c.position)).collect::<Vec<_>>(), vec![("Technical Excellence", 0), ("Ownership", 1)]);
+        assert_eq!(saved[1].description, None);
+
+        assert!(matches!(save_competencies(&pool, 1, vec![competency("Ownership", None), competency("ownership", None)]).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(save_competencies(&pool, 1, vec![competency(" ", None)]).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(save_competencies(&pool, 9, vec![]).await, Err(AppError::NotFound(_))));
+        assert_eq!(get_competencies(&pool, 1).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_generation_needs_a_framework_and_evidence() {
+        let pool = setup_db().await;
+        assert!(matches!(generate_self_evaluation(&pool, SelfEvaluationInput { end_date: "2027-06-30".to_string(), ..evaluation_input() }).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(generate_self_evaluation(&pool, SelfEvaluationInput { date_label: " ".to_string(), ..evaluation_input() }).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(generate_self_evaluation(&pool, evaluation_input()).await, Err(AppError::InvalidInput(m)) if m.contains("competency")));
+
+        save_competencies(&pool, 1, vec![competency("Ownership", None)]).await.unwrap();
+        assert!(matches!(generate_self_evaluation(&pool, evaluation_input()).await, Err(AppError::InvalidInput(m)) if m.contains("No completed tasks")));
+
+        // Reports count by creation time whatever their label says; self-evaluations are not evidence for each other
+        sqlx::query("INSERT INTO achievements (workspace_id, achievement_type, content, date_label, created_at) VALUES
+            (1, 'WEEKLY', 'Week 10', 'Week 10', '2026-03-08T18:00:00'),
+            (1, 'WEEKLY', 'Week 27', '2026-03-01', '2026-07-05T18:00:00'),
+            (1, 'SELF_EVALUATION', 'Old review', '2026-03-01', '2026-03-01T18:00:00')").execute(&pool).await.unwrap();
+        let found = database::evaluation::get_achievements_in_range(&pool, 1, "2026-01-01T04:00:00", "2026-07-01T03:59:00", SELF_EVALUATION_TYPE).await.unwrap();
+        assert_eq!(found.iter().map(|a| a.content.as_str()).collect::<Vec<_>>(), vec!["Week 10"]);
+        database::achievement::save_achievement(&pool, 1, SELF_EVALUATION_TYPE, "H1 review", "2026 H1", None).await.unwrap();
+        assert!(matches!(generate_self_evaluation(&pool, evaluation_input()).await, Err(AppError::InvalidInput(m)) if m.contains("already exists")));
+    }
+
+    #[tokio::test]
+    async fn test_task_evidence_merges_split_tasks() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Cut release'), (2, 1, 'Mentor intern')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status, review_memo) VALUES
+            (1, 1, 'Cut release', '2026-02-02T09:00:00', '2026-02-02T10:00:00', 'DONE', 'Automated changelog'),
+            (2, 1, 'Mentor intern', '2026-02-02T10:00:00', '2026-02-02T10:30:00', 'DONE', NULL),
+            (1, 1, 'Cut release', '2026-02-03T09:00:00', '2026-02-03T09:45:00', 'DONE', 'Shipped 2.0')").execute(&pool).await.unwrap();
+        let blocks = database::achievement::get_completed_task_blocks(&pool, 1, "2026-01-01T04:00:00", "2026-07-01T03:59:00", None).await.unwrap();
+        assert_eq!(
+            build_task_evidence(blocks),
+            "- **Cut release** (105 mins)\n  - Outcome: Automated changelog; Shipped 2.0\n- **Mentor intern** (30 mins)\n"
+        );
+    }
+
+    #[test]
+    fn test_prompt_evidence_is_capped() {
+        let block = |title: String, worked_minutes: i64| CompletedTaskBlock {
+            title,
+            planning_memo: None,
+            review_memo: None,
+            start_time: "2026-02-02T09:00:00".to_string(),
+            end_time: "2026-02-02T10:00:00".to_string(),
+            worked_minutes,
+            completed_steps: None,
+            tags: None,
+            focus_sessions: 0,
+            goal_title: None,
+            key_result_title: None,
+        };
+        let blocks: Vec<CompletedTaskBlock> = (0..MAX_EVIDENCE_TASKS as i64 + 2).map(|i| block(format!("Task {}", i), i + 1)).collect();
+        let evidence = build_task_evidence(blocks);
+        assert_eq!(evidence.lines().count(), MAX_EVIDENCE_TASKS + 1);
+        assert!(!evidence.contains("**Task 0**") && evidence.ends_with("- ...and 2 smaller tasks (3 mins)\n"));
+
+        let report = |content: String| Achievement {
+            id: 0,
+            workspace_id: 1,
+            achievement_type: "WEEKLY".to_string(),
+            content,
+            date_label: "Week".to_string(),
+            created_at: "2026-02-02T18:00:00".to_string(),
+            used_model: None,
+        };
+        let mut reports: Vec<Achievement> = (0..MAX_EVIDENCE_ACHIEVEMENTS).map(|i| report(format!("Report {}", i))).collect();
+        reports.insert(0, report("Oldest".to_string()));
+        reports.push(report("가".repeat(MAX_ACHIEVEMENT_EXCERPT_CHARS + 10)));
+        let evidence = build_achievement_evidence(&reports);
+        assert!(!evidence.contains("Oldest") && !evidence.contains("Report 0\n"));
+        assert!(evidence.contains(&format!("{}…", "가".repeat(MAX_ACHIEVEMENT_EXCERPT_CHARS))));
+    }
+}
@@ -0,0 +1,244 @@
+use sqlx::SqlitePool;
+use chrono::{Duration, Local, NaiveDateTime, Timelike};
+use crate::domain::{FocusSession, StartFocusInput, FocusDayStats, FocusTaskStats, TimeBlock, DEFAULT_FOCUS_WORK_MINUTES, DEFAULT_FOCUS_BREAK_MINUTES, Result, AppError};
+use crate::database;
+use crate::services::timeline::{self, logical_range, parse_datetime};
+
+pub const MAX_FOCUS_WORK_MINUTES: i64 = 180;
+pub const MAX_FOCUS_BREAK_MINUTES: i64 = 60;
+/// Longest window `get_focus_stats` serves in one call.
+pub const MAX_FOCUS_STATS_DAYS: i64 = 366;
+
+pub async fn start_focus_session(pool: &SqlitePool, input: StartFocusInput) -> Result<FocusSession> {
+    start_focus_session_at(pool, input, Local::now().naive_local()).await
+}
+
+/// Starts a focus session inside the NOW block; one session runs per workspace at a time.
+pub async fn start_focus_session_at(pool: &SqlitePool, input: StartFocusInput, now_dt: NaiveDateTime) -> Result<FocusSession> {
+    let work_minutes = input.work_minutes.unwrap_or(DEFAULT_FOCUS_WORK_MINUTES);
+    let break_minutes = input.break_minutes.unwrap_or(DEFAULT_FOCUS_BREAK_MINUTES);
+    if !(1..=MAX_FOCUS_WORK_MINUTES).contains(&work_minutes) {
+        return Err(AppError::InvalidInput(format!("A focus session lasts between 1 and {} minutes.", MAX_FOCUS_WORK_MINUTES)));
+    }
+    if !(0..=MAX_FOCUS_BREAK_MINUTES).contains(&break_minutes) {
+        return Err(AppError::InvalidInput(format!("A break lasts at most {} minutes.", MAX_FOCUS_BREAK_MINUTES)));
+    }
+
+    let block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1").bind(input.block_id).fetch_optional(pool).await?
+        .ok_or_else(|| AppError::NotFound("Block not found".to_string()))?;
+    if block.status != "NOW" {
+        return Err(AppError::InvalidInput("Focus sessions run inside the active (NOW) task.".to_string()));
+    }
+    if database::timeline::is_block_paused(pool, block.id).await? {
+        return Err(AppError::InvalidInput("Resume the task before starting a focus session.".to_string()));
+    }
+    if !database::focus::get_running_sessions(pool, Some(block.workspace_id)).await?.is_empty() {
+        return Err(AppError::InvalidInput("A focus session is already running.".to_string()));
+    }
+
+    let session = FocusSession {
+        id: 0,
+        workspace_id: block.workspace_id,
+        block_id: Some(block.id),
+        task_id: block.task_id,
+        title: block.title,
+        started_at: to_minute(now_dt).format("%Y-%m-%dT%H:%M:00").to_string(),
+        ended_at: None,
+        work_minutes,
+        break_minutes,
+        status: "RUNNING".to_string(),
+        interruptions: 0,
+    };
+    let id = database::focus::create_session(pool, &session).await?;
+    Ok(FocusSession { id, ..session })
+}
+
+pub async fn get_active_focus_session(pool: &SqlitePool, workspace_id: i64) -> Result<Option<FocusSession>> {
+    Ok(database::focus::get_running_sessions(pool, Some(workspace_id)).await?.into_iter().next())
+}
+
+pub async fn interrupt_focus_session(pool: &SqlitePool, id: i64) -> Result<FocusSession> {
+    running_session(pool, id).await?;
+    database::focus::add_interruption(pool, id).await?;
+    running_session(pool, id).await
+}
+
+pub async fn cancel_focus_session(pool: &SqlitePool, id: i64) -> Result<FocusSession> {
+    cancel_focus_session_at(pool, id, Local::now().naive_local()).await
+}
+
+pub async fn cancel_focus_session_at(pool: &SqlitePool, id: i64, now_dt: NaiveDateTime) -> Result<FocusSession> {
+    let session = running_session(pool, id).await?;
+    let ended_at = to_minute(now_dt).format("%Y-%m-%dT%H:%M:00").to_string();
+    database::focus::finish_session(pool, id, "CANCELLED", &ended_at).await?;
+    Ok(FocusSession { status: "CANCELLED".to_string(), ended_at: Some(ended_at), ..session })
+}
+
+async fn running_session(pool: &SqlitePool, id: i64) -> Result<FocusSession> {
+    let session = database::focus::get_session(pool, id).await?.ok_or_else(|| AppError::NotFound("Focus session not found".to_string()))?;
+    if session.status != "RUNNING" {
+        return Err(AppError::InvalidInput("The focus session has already ended.".to_string()));
+    }
+    Ok(session)
+}
+
+pub async fn complete_due_focus_sessions(pool: &SqlitePool) -> Result<Vec<FocusSession>> {
+    complete_due_focus_sessions_at(pool, Local::now().naive_local()).await
+}
+
+/// Closes running sessions whose work time is over. While their block is still NOW they
+/// complete, and their break goes into the timeline as a `BREAK` block during which the
+/// block is not worked on (see `timeline::insert_break_at`). Sessions whose block ended
+/// first are cancelled.
+pub async fn complete_due_focus_sessions_at(pool: &SqlitePool, now_dt: NaiveDateTime) -> Result<Vec<FocusSession>> {
+    let mut finished = Vec::new();
+    for session in database::focus::get_running_sessions(pool, None).await? {
+        let due = parse_datetime(&session.started_at)? + Duration::minutes(session.work_minutes);
+        if due > now_dt {
+            continue;
+        }
+        let block: Option<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1").bind(session.block_id).fetch_optional(pool).await?;
+        let due_str = due.format("%Y-%m-%dT%H:%M:00").to_string();
+
+        let Some(block) = block.filter(|b| b.status == "NOW") else {
+            database::focus::finish_session(pool, session.id, "CANCELLED", &due_str).await?;
+            finished.push(FocusSession { status: "CANCELLED".to_string(), ended_at: Some(due_str), ..session });
+            continue;
+        };
+
+        database::focus::finish_session(pool, session.id, "COMPLETED", &due_str).await?;
+        if session.break_minutes > 0 && !database::timeline::is_block_paused(pool, block.id).await? {
+            let lang = database::user::get_user(pool).await?.map(|u| u.lang).unwrap_or_default();
+            let title = if lang == "ko" { "휴식" } else { "Break" };
+            timeline::insert_break_at(pool, block.id, title, due, session.break_minutes).await?;
+        }
+        finished.push(FocusSession { status: "COMPLETED".to_string(), ended_at: Some(due_str), ..session });
+    }
+    Ok(finished)
+}
+
+/// Per logical day of `start_date..=end_date` (every day present): sessions, focus minutes
+/// and interruptions, in total and per task.
+pub async fn get_focus_stats(pool: &SqlitePool, workspace_id: i64, start_date: &str, end_date: &str) -> Result<Vec<FocusDayStats>> {
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let range = logical_range(start_date, end_date, MAX_FOCUS_STATS_DAYS, &user.day_start_time)?;
+    let sessions = database::focus::get_finished_sessions_in_range(pool, workspace_id, &range.start_bound, &range.end_bound).await?;
+
+    let mut days: Vec<FocusDayStats> = range
+        .days()
+        .map(|date| FocusDayStats { logical_date: date.format("%Y-%m-%d").to_string(), ..Default::default() })
+        .collect();
+    for session in sessions {
+        let started = parse_datetime(&session.started_at)?;
+        let Some(day) = range.day_index(started).and_then(|i| days.get_mut(i)) else { continue; };
+
+        let completed = session.status == "COMPLETED";
+        let minutes = if completed {
+            session.work_minutes
+        } else {
+            let ended = session.ended_at.as_deref().map(parse_datetime).transpose()?.unwrap_or(started);
+            (ended - started).num_minutes().clamp(0, session.work_minutes)
+        };
+
+        day.completed_sessions += completed as i64;
+        day.cancelled_sessions += !completed as i64;
+        day.focus_minutes += minutes;
+        day.interruptions += session.interruptions;
+
+        let index = match day.tasks.iter().position(|t| t.task_id == session.task_id && t.title == session.title) {
+            Some(i) => i,
+            None => {
+                day.tasks.push(FocusTaskStats { task_id: session.task_id, title: session.title.clone(), ..Default::default() });
+                day.tasks.len() - 1
+            }
+        };
+        let task = &mut day.tasks[index];
+        task.completed_sessions += completed as i64;
+        task.focus_minutes += minutes;
+        task.interruptions += session.interruptions;
+    }
+    Ok(days)
+}
+
+fn to_minute(dt: NaiveDateTime) -> NaiveDateTime {
+    dt.with_second(0).and_then(|d| d.with_nanosecond(0)).unwrap_or(dt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = database::test_pool().await;
+        sqlx::query("INSERT INTO users (id, nickname) VALUES (1, 'TestUser')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Write report'), (2, 1, 'Review')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES
+            (1, 1, 1, 'Write report', '2026-03-02T09:00:00', '2026-03-02T10:00:00', 'NOW'),
+            (2, 2, 1, 'Review', '2026-03-02T10:00:00', '2026-03-02T10:30:00', 'WILL')").execute(&pool).await.unwrap();
+        pool
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    async fn block(pool: &SqlitePool, id: i64) -> TimeBlock {
+        sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1").bind(id).fetch_one(pool).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_completed_session_inserts_a_break_and_pushes_the_plan() {
+        let pool = setup_db().await;
+        let input = StartFocusInput { block_id: 1, work_minutes: None, break_minutes: None };
+        let session = start_focus_session_at(&pool, input.clone(), at("2026-03-02T09:10:30")).await.unwrap();
+        assert_eq!((session.started_at.as_str(), session.work_minutes, session.break_minutes), ("2026-03-02T09:10:00", 25, 5));
+        assert!(matches!(start_focus_session_at(&pool, input, at("2026-03-02T09:11:00")).await, Err(AppError::InvalidInput(_))));
+
+        interrupt_focus_session(&pool, session.id).await.unwrap();
+        assert!(complete_due_focus_sessions_at(&pool, at("2026-03-02T09:34:00")).await.unwrap().is_empty());
+        let finished = complete_due_focus_sessions_at(&pool, at("2026-03-02T09:35:20")).await.unwrap();
+        assert_eq!((finished[0].status.as_str(), finished[0].ended_at.as_deref()), ("COMPLETED", Some("2026-03-02T09:35:00")));
+
+        let breaks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE status = 'BREAK'").fetch_all(&pool).await.unwrap();
+        assert_eq!((breaks[0].start_time.as_str(), breaks[0].end_time.as_str()), ("2026-03-02T09:35:00", "2026-03-02T09:40:00"));
+        assert_eq!(block(&pool, 1).await.end_time, "2026-03-02T10:05:00");
+        assert_eq!(block(&pool, 2).await.start_time, "2026-03-02T10:05:00");
+        // The break is not worked time: 09:00-10:05 minus the 5-minute break
+        let (worked,): (i64,) = sqlx::query_as(&format!("SELECT {} FROM time_blocks tb WHERE tb.id = 1", database::timeline::WORKED_MINUTES_SQL)).fetch_one(&pool).await.unwrap();
+        assert_eq!(worked, 60);
+        let mut blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks ORDER BY start_time").fetch_all(&pool).await.unwrap();
+        let intervals = database::timeline::get_block_intervals_in_range(&pool, 1, "2026-03-02T04:00:00", "2026-03-03T03:59:00").await.unwrap();
+        timeline::apply_interval_stats(&mut blocks, &intervals, at("2026-03-02T11:00:00"));
+        let totals = timeline::day_totals(&blocks);
+        assert_eq!((totals.by_status["NOW"], totals.by_status["BREAK"]), (60, 5));
+        // Moving tasks around the day leaves the break where it was taken
+        timeline::move_task_to_bottom(&pool, 1, 2).await.unwrap();
+        let breaks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE status = 'BREAK'").fetch_all(&pool).await.unwrap();
+        assert_eq!(breaks[0].start_time, "2026-03-02T09:35:00");
+
+        assert!(matches!(interrupt_focus_session(&pool, session.id).await, Err(AppError::InvalidInput(_))));
+        assert!(get_active_focus_session(&pool, 1).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_focus_stats_per_day_and_task() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO focus_sessions (workspace_id, block_id, task_id, title, started_at, ended_at, work_minutes, break_minutes, status, interruptions) VALUES
+            (1, 1, 1, 'Write report', '2026-03-02T09:00:00', '2026-03-02T09:25:00', 25, 5, 'COMPLETED', 1),
+            (1, 1, 1, 'Write report', '2026-03-02T09:30:00', '2026-03-02T09:40:00', 25, 5, 'CANCELLED', 2),
+            (1, 2, 2, 'Review', '2026-03-03T02:00:00', '2026-03-03T02:50:00', 50, 10, 'COMPLETED', 0),
+            (1, 2, 2, 'Review', '2026-03-03T10:00:00', NULL, 25, 5, 'RUNNING', 0)").execute(&pool).await.unwrap();
+
+        let days = get_focus_stats(&pool, 1, "2026-03-02", "2026-03-03").await.unwrap();
+        assert_eq!(days.len(), 2);
+        // The 02:00 session still belongs to March 2nd
+        let day = &days[0];
+        assert_eq!((day.completed_sessions, day.cancelled_sessions, day.focus_minutes, day.interruptions), (2, 1, 85, 3));
+        assert_eq!(day.tasks.len(), 2);
+        assert_eq!((day.tasks[0].title.as_str(), day.tasks[0].completed_sessions, day.tasks[0].focus_minutes), ("Write report", 1, 35));
+        assert_eq!(days[1].completed_sessions, 0);
+
+        assert!(matches!(get_focus_stats(&pool, 1, "2026-03-03", "2026-03-02").await, Err(AppError::InvalidInput(_))));
+    }
+}
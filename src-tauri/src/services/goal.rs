@@ -0,0 +1,243 @@
+use sqlx::SqlitePool;
+use chrono::Local;
+use crate::domain::{Goal, GoalInput, KeyResultInput, KeyResultCheckIn, CheckInInput, KEY_RESULT_METRICS, Result, AppError};
+use crate::database;
+use crate::services::timeline::{logical_day_bounds, parse_date};
+
+/// Goals of a workspace with their key results and progress, latest period first.
+pub async fn get_goals(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<Goal>> {
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let mut goals = Vec::new();
+    for goal in database::goal::get_goals(pool, workspace_id).await? {
+        goals.push(with_progress(pool, goal, &user.day_start_time).await?);
+    }
+    Ok(goals)
+}
+
+pub async fn get_goal(pool: &SqlitePool, id: i64) -> Result<Goal> {
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let goal = database::goal::get_goal(pool, id).await?.ok_or_else(|| AppError::NotFound("Goal not found".to_string()))?;
+    with_progress(pool, goal, &user.day_start_time).await
+}
+
+pub async fn create_goal(pool: &SqlitePool, input: GoalInput) -> Result<i64> {
+    let input = validate_goal_input(input)?;
+    database::workspace::get_workspace(pool, input.workspace_id).await?.ok_or_else(|| AppError::NotFound("Workspace not found".to_string()))?;
+    database::goal::create_goal(pool, &input, &Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).await
+}
+
+pub async fn update_goal(pool: &SqlitePool, id: i64, input: GoalInput) -> Result<()> {
+    let input = validate_goal_input(input)?;
+    database::goal::get_goal(pool, id).await?.ok_or_else(|| AppError::NotFound("Goal not found".to_string()))?;
+    database::goal::update_goal(pool, id, &input).await
+}
+
+/// Removes the goal with its key results and check-ins; linked tasks are kept and unlinked.
+pub async fn delete_goal(pool: &SqlitePool, id: i64) -> Result<()> {
+    database::goal::delete_goal(pool, id).await
+}
+
+pub async fn create_key_result(pool: &SqlitePool, input: KeyResultInput) -> Result<i64> {
+    let input = validate_key_result_input(input)?;
+    database::goal::get_goal(pool, input.goal_id).await?.ok_or_else(|| AppError::NotFound("Goal not found".to_string()))?;
+    database::goal::create_key_result(pool, &input).await
+}
+
+pub async fn update_key_result(pool: &SqlitePool, id: i64, input: KeyResultInput) -> Result<()> {
+    let input = validate_key_result_input(input)?;
+    let current = database::goal::get_key_result(pool, id).await?.ok_or_else(|| AppError::NotFound("Key result not found".to_string()))?;
+    // Logged minutes and measured values cannot be read as one another
+    if current.metric != input.metric && database::goal::has_check_ins(pool, id).await? {
+        return Err(AppError::InvalidInput("Remove the check-ins before changing the metric.".to_string()));
+    }
+    database::goal::update_key_result(pool, id, &input).await
+}
+
+pub async fn delete_key_result(pool: &SqlitePool, id: i64) -> Result<()> {
+    database::goal::delete_key_result(pool, id).await
+}
+
+/// Logs progress by hand: extra minutes for a `TIME` key result, the new value for a `NUMBER` one.
+pub async fn add_check_in(pool: &SqlitePool, mut input: CheckInInput) -> Result<i64> {
+    let key_result = database::goal::get_key_result(pool, input.key_result_id).await?
+        .ok_or_else(|| AppError::NotFound("Key result not found".to_string()))?;
+    if !input.value.is_finite() {
+        return Err(AppError::InvalidInput("A check-in needs a number.".to_string()));
+    }
+    if key_result.metric == "TIME" && input.value <= 0.0 {
+        return Err(AppError::InvalidInput("Logged minutes must be positive.".to_string()));
+    }
+    input.note = input.note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    database::goal::add_check_in(pool, &input, &Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).await
+}
+
+pub async fn get_check_ins(pool: &SqlitePool, key_result_id: i64) -> Result<Vec<KeyResultCheckIn>> {
+    database::goal::get_check_ins(pool, key_result_id).await
+}
+
+pub async fn delete_check_in(pool: &SqlitePool, id: i64) -> Result<()> {
+    database::goal::delete_check_in(pool, id).await
+}
+
+/// Links a task to a key result of its own workspace, or unlinks it with `None`.
+pub async fn set_task_key_result(pool: &SqlitePool, task_id: i64, key_result_id: Option<i64>) -> Result<()> {
+    let task_workspace: (i64,) = sqlx::query_as("SELECT workspace_id FROM tasks WHERE id = ?1")
+        .bind(task_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+    if let Some(key_result_id) = key_result_id {
+        let key_result = database::goal::get_key_result(pool, key_result_id).await?
+            .ok_or_else(|| AppError::NotFound("Key result not found".to_string()))?;
+        let goal = database::goal::get_goal(pool, key_result.goal_id).await?
+            .ok_or_else(|| AppError::NotFound("Goal not found".to_string()))?;
+        if goal.workspace_id != task_workspace.0 {
+            return Err(AppError::InvalidInput("Tasks can only advance goals of their own workspace.".to_string()));
+        }
+    }
+    database::goal::set_task_key_result(pool, task_id, key_result_id).await
+}
+
+async fn with_progress(pool: &SqlitePool, mut goal: Goal, day_start_time: &str) -> Result<Goal> {
+    let (range_start, _) = logical_day_bounds(parse_date(&goal.period_start)?, day_start_time)?;
+    let (_, range_end) = logical_day_bounds(parse_date(&goal.period_end)?, day_start_time)?;
+
+    let mut key_results = database::goal::get_key_results_with_work(pool, goal.id, &range_start, &range_end).await?;
+    for key_result in key_results.iter_mut() {
+        key_result.progress_percent = if key_result.target_value > 0.0 {
+            round_tenth(key_result.current_value / key_result.target_value * 100.0)
+        } else {
+            0.0
+        };
+    }
+
+    goal.progress_percent = if key_results.is_empty() {
+        0.0
+    } else {
+        round_tenth(key_results.iter().map(|kr| kr.progress_percent.min(100.0)).sum::<f64>() / key_results.len() as f64)
+    };
+    goal.key_results = key_results;
+    Ok(goal)
+}
+
+fn validate_goal_input(mut input: GoalInput) -> Result<GoalInput> {
+    input.title = input.title.trim().to_string();
+    if input.title.is_empty() {
+        return Err(AppError::InvalidInput("A goal needs a title.".to_string()));
+    }
+    input.description = input.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    let start = parse_date(&input.period_start)?;
+    let end = parse_date(&input.period_end)?;
+    if end < start {
+        return Err(AppError::InvalidInput("The period must not end before it starts.".to_string()));
+    }
+    input.period_start = start.format("%Y-%m-%d").to_string();
+    input.period_end = end.format("%Y-%m-%d").to_string();
+    Ok(input)
+}
+
+fn validate_key_result_input(mut input: KeyResultInput) -> Result<KeyResultInput> {
+    input.title = input.title.trim().to_string();
+    if input.title.is_empty() {
+        return Err(AppError::InvalidInput("A key result needs a title.".to_string()));
+    }
+    input.metric = input.metric.trim().to_uppercase();
+    if !KEY_RESULT_METRICS.contains(&input.metric.as_str()) {
+        return Err(AppError::InvalidInput(format!("Unknown metric: {}", input.metric)));
+    }
+    if !input.target_value.is_finite() || input.target_value <= 0.0 {
+        return Err(AppError::InvalidInput("The target must be a positive number.".to_string()));
+    }
+    input.unit = input.unit.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    Ok(input)
+}
+
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = database::test_pool().await;
+        sqlx::query("INSERT INTO users (id, nickname) VALUES (1, 'TestUser')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test'), (2, 'Other')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Migrate billing'), (2, 1, 'Load test'), (3, 2, 'Elsewhere')").execute(&pool).await.unwrap();
+        pool
+    }
+
+    fn goal_input(period_start: &str, period_end: &str) -> GoalInput {
+        GoalInput { workspace_id: 1, title: " Ship billing v2 ".to_string(), description: None, period_start: period_start.to_string(), period_end: period_end.to_string() }
+    }
+
+    fn key_result_input(goal_id: i64, metric: &str, target_value: f64) -> KeyResultInput {
+        KeyResultInput { goal_id, title: "Key result".to_string(), metric: metric.to_string(), target_value, unit: None }
+    }
+
+    #[tokio::test]
+    async fn test_progress_from_linked_work_and_check_ins() {
+        let pool = setup_db().await;
+        let goal_id = create_goal(&pool, goal_input("2026-03-01", "2026-03-31")).await.unwrap();
+        let time_kr = create_key_result(&pool, key_result_input(goal_id, "time", 600.0)).await.unwrap();
+        let number_kr = create_key_result(&pool, key_result_input(goal_id, "NUMBER", 200.0)).await.unwrap();
+
+        set_task_key_result(&pool, 1, Some(time_kr)).await.unwrap();
+        set_task_key_result(&pool, 2, Some(time_kr)).await.unwrap();
+        // Paused for 30 minutes, so 90 minutes count; the block before the period and the unfinished one do not
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES
+            (1, 1, 1, 'Migrate billing', '2026-03-02T09:00:00', '2026-03-02T11:00:00', 'DONE'),
+            (2, 1, 1, 'Migrate billing', '2026-02-28T09:00:00', '2026-02-28T10:00:00', 'DONE'),
+            (3, 2, 1, 'Load test', '2026-04-01T03:00:00', '2026-04-01T03:30:00', 'DONE'),
+            (4, 2, 1, 'Load test', '2026-03-03T09:00:00', '2026-03-03T10:00:00', 'WILL')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at, ended_at) VALUES (1, '2026-03-02T09:00:00', '2026-03-02T10:00:00'), (1, '2026-03-02T10:30:00', '2026-03-02T11:00:00')").execute(&pool).await.unwrap();
+        add_check_in(&pool, CheckInInput { key_result_id: time_kr, value: 60.0, note: Some("  Offsite workshop ".to_string()) }).await.unwrap();
+
+        add_check_in(&pool, CheckInInput { key_result_id: number_kr, value: 120.0, note: None }).await.unwrap();
+        add_check_in(&pool, CheckInInput { key_result_id: number_kr, value: 300.0, note: None }).await.unwrap();
+
+        let goal = get_goal(&pool, goal_id).await.unwrap();
+        assert_eq!(goal.title, "Ship billing v2");
+        let time = &goal.key_results[0];
+        // Block 3 starts before the 04:00 day start, so it still belongs to March 31
+        assert_eq!((time.metric.as_str(), time.linked_task_count, time.linked_minutes), ("TIME", 2, 120));
+        assert_eq!((time.current_value, time.progress_percent), (180.0, 30.0));
+        let number = &goal.key_results[1];
+        assert_eq!((number.current_value, number.progress_percent), (300.0, 150.0));
+        // The overshooting key result counts as 100
+        assert_eq!(goal.progress_percent, 65.0);
+        assert_eq!(get_check_ins(&pool, time_kr).await.unwrap()[0].note.as_deref(), Some("Offsite workshop"));
+
+        delete_goal(&pool, goal_id).await.unwrap();
+        assert!(get_goals(&pool, 1).await.unwrap().is_empty());
+        let (linked,): (Option<i64>,) = sqlx::query_as("SELECT key_result_id FROM tasks WHERE id = 1").fetch_one(&pool).await.unwrap();
+        assert_eq!(linked, None);
+    }
+
+    #[tokio::test]
+    async fn test_validation_and_workspace_boundaries() {
+        let pool = setup_db().await;
+        assert!(matches!(create_goal(&pool, goal_input("2026-03-31", "2026-03-01")).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(create_goal(&pool, GoalInput { title: " ".to_string(), ..goal_input("2026-03-01", "2026-03-31") }).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(create_goal(&pool, GoalInput { workspace_id: 9, ..goal_input("2026-03-01", "2026-03-31") }).await, Err(AppError::NotFound(_))));
+
+        let goal_id = create_goal(&pool, goal_input("2026-03-01", "2026-03-31")).await.unwrap();
+        assert!(matches!(create_key_result(&pool, key_result_input(goal_id, "SPEED", 10.0)).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(create_key_result(&pool, key_result_input(goal_id, "TIME", 0.0)).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(create_key_result(&pool, key_result_input(99, "TIME", 60.0)).await, Err(AppError::NotFound(_))));
+
+        let kr = create_key_result(&pool, key_result_input(goal_id, "TIME", 60.0)).await.unwrap();
+        assert!(matches!(add_check_in(&pool, CheckInInput { key_result_id: kr, value: -5.0, note: None }).await, Err(AppError::InvalidInput(_))));
+        update_key_result(&pool, kr, key_result_input(goal_id, "NUMBER", 60.0)).await.unwrap();
+        add_check_in(&pool, CheckInInput { key_result_id: kr, value: 12.0, note: None }).await.unwrap();
+        assert!(matches!(update_key_result(&pool, kr, key_result_input(goal_id, "TIME", 60.0)).await, Err(AppError::InvalidInput(_))));
+        update_key_result(&pool, kr, key_result_input(goal_id, "NUMBER", 90.0)).await.unwrap();
+        assert!(matches!(set_task_key_result(&pool, 3, Some(kr)).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(set_task_key_result(&pool, 99, Some(kr)).await, Err(AppError::NotFound(_))));
+
+        set_task_key_result(&pool, 1, Some(kr)).await.unwrap();
+        set_task_key_result(&pool, 1, None).await.unwrap();
+        assert_eq!(get_goal(&pool, goal_id).await.unwrap().key_results[0].linked_task_count, 0);
+    }
+}
@@ -0,0 +1,118 @@
+use sqlx::SqlitePool;
+use crate::domain::{InterruptionGroup, InterruptionReport, Result, AppError};
+use crate::database;
+use crate::services::timeline::{logical_range, parse_datetime};
+
+/// Longest window `get_interruption_report` serves in one call.
+pub const MAX_INTERRUPTION_REPORT_DAYS: i64 = 366;
+
+/// Interruptions over the logical days `start_date..=end_date`, totalled per day and
+/// per project / label of the urgent task that caused them.
+pub async fn get_interruption_report(pool: &SqlitePool, workspace_id: i64, start_date: &str, end_date: &str) -> Result<InterruptionReport> {
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let range = logical_range(start_date, end_date, MAX_INTERRUPTION_REPORT_DAYS, &user.day_start_time)?;
+    let items = database::interruption::get_interruptions_in_range(pool, workspace_id, &range.start_bound, &range.end_bound).await?;
+
+    let mut report = InterruptionReport {
+        by_day: range
+            .days()
+            .map(|date| InterruptionGroup { key: date.format("%Y-%m-%d").to_string(), ..Default::default() })
+            .collect(),
+        ..Default::default()
+    };
+    for item in &items {
+        let occurred = parse_datetime(&item.occurred_at)?;
+        if let Some(day) = range.day_index(occurred).and_then(|i| report.by_day.get_mut(i)) {
+            day.count += 1;
+            day.lost_minutes += item.lost_minutes;
+        }
+        if let Some(project) = &item.project_name {
+            add_to_group(&mut report.by_project, project, item.lost_minutes);
+        }
+        if let Some(label) = &item.label_name {
+            add_to_group(&mut report.by_label, label, item.lost_minutes);
+        }
+        report.total_count += 1;
+        report.total_lost_minutes += item.lost_minutes;
+    }
+    for groups in [&mut report.by_project, &mut report.by_label] {
+        groups.sort_by(|a, b| b.lost_minutes.cmp(&a.lost_minutes).then_with(|| b.count.cmp(&a.count)).then_with(|| a.key.cmp(&b.key)));
+    }
+    report.items = items;
+    Ok(report)
+}
+
+fn add_to_group(groups: &mut Vec<InterruptionGroup>, key: &str, lost_minutes: i64) {
+    match groups.iter_mut().find(|g| g.key == key) {
+        Some(group) => {
+            group.count += 1;
+            group.lost_minutes += lost_minutes;
+        }
+        None => groups.push(InterruptionGroup { key: key.to_string(), count: 1, lost_minutes }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = database::test_pool().await;
+        sqlx::query("INSERT INTO users (id, nickname) VALUES (1, 'TestUser')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test'), (2, 'Other')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO projects (id, workspace_id, name, last_used) VALUES (1, 1, 'Platform', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO labels (id, workspace_id, name, color, last_used) VALUES (1, 1, 'Support', '#f00', '2026-03-01T09:00:00'), (2, 1, 'Meeting', '#00f', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, project_id, label_id) VALUES
+            (1, 1, 'Write report', 1, NULL),
+            (2, 1, 'Hotfix', 1, 1),
+            (3, 1, 'Sync call', NULL, 2),
+            (4, 1, 'Customer ticket', NULL, 1)").execute(&pool).await.unwrap();
+        pool
+    }
+
+    async fn interruption(pool: &SqlitePool, workspace_id: i64, interrupting_task_id: i64, occurred_at: &str, lost_minutes: i64) {
+        sqlx::query("INSERT INTO interruptions (workspace_id, interrupting_task_id, interrupting_title, interrupted_task_id, interrupted_title, occurred_at, lost_minutes) VALUES (?1, ?2, 'Urgent', 1, 'Write report', ?3, ?4)")
+            .bind(workspace_id).bind(interrupting_task_id).bind(occurred_at).bind(lost_minutes).execute(pool).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_report_groups_by_logical_day_project_and_label() {
+        let pool = setup_db().await;
+        interruption(&pool, 1, 2, "2026-03-02T10:00:00", 30).await;
+        interruption(&pool, 1, 3, "2026-03-02T15:00:00", 45).await;
+        // Before the 04:00 day start, so it belongs to March 2
+        interruption(&pool, 1, 4, "2026-03-03T02:00:00", 20).await;
+        interruption(&pool, 1, 2, "2026-03-03T11:00:00", 10).await;
+        interruption(&pool, 1, 2, "2026-03-05T11:00:00", 10).await;
+        interruption(&pool, 2, 2, "2026-03-02T11:00:00", 60).await;
+
+        let report = get_interruption_report(&pool, 1, "2026-03-02", "2026-03-03").await.unwrap();
+        assert_eq!((report.total_count, report.total_lost_minutes), (4, 105));
+        assert_eq!(report.items.len(), 4);
+        assert_eq!(report.by_day, vec![
+            InterruptionGroup { key: "2026-03-02".to_string(), count: 3, lost_minutes: 95 },
+            InterruptionGroup { key: "2026-03-03".to_string(), count: 1, lost_minutes: 10 },
+        ]);
+        assert_eq!(report.by_project, vec![InterruptionGroup { key: "Platform".to_string(), count: 2, lost_minutes: 40 }]);
+        assert_eq!(report.by_label, vec![
+            InterruptionGroup { key: "Support".to_string(), count: 3, lost_minutes: 60 },
+            InterruptionGroup { key: "Meeting".to_string(), count: 1, lost_minutes: 45 },
+        ]);
+
+        // Deleting the urgent task keeps the record but drops it from the project and label totals
+        sqlx::query("DELETE FROM tasks WHERE id = 3").execute(&pool).await.unwrap();
+        let report = get_interruption_report(&pool, 1, "2026-03-02", "2026-03-03").await.unwrap();
+        assert_eq!(report.total_count, 4);
+        assert_eq!(report.by_label.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_report_rejects_invalid_ranges() {
+        let pool = setup_db().await;
+        assert!(matches!(get_interruption_report(&pool, 1, "2026-03-03", "2026-03-02").await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(get_interruption_report(&pool, 1, "2026-01-01", "2027-01-02").await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(get_interruption_report(&pool, 1, "03/02/2026", "2026-03-02").await, Err(AppError::DateParse(_))));
+        let report = get_interruption_report(&pool, 1, "2026-03-02", "2026-03-02").await.unwrap();
+        assert_eq!((report.total_count, report.by_day.len()), (0, 1));
+    }
+}
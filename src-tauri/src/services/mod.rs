@@ -3,3 +3,18 @@ pub mod workspace;
 pub mod timeline;
 pub mod achievement;
 pub mod gemini;
+pub mod analytics;
+pub mod checklist;
+pub mod dependency;
+pub mod tag;
+pub mod quick_add;
+pub mod search;
+pub mod rollover;
+pub mod notification;
+pub mod overrun;
+pub mod focus;
+pub mod interruption;
+pub mod retrospective;
+pub mod goal;
+pub mod standup;
+pub mod evaluation;
@@ -0,0 +1,300 @@
+use sqlx::SqlitePool;
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use crate::domain::{NotificationSettings, NotificationSettingsInput, ScheduledNotification, TimeBlock, UnpluggedTime, FocusSession, Result, AppError};
+use crate::database;
+use crate::services::timeline::parse_datetime;
+
+/// Longest reminder lead time and overrun repeat interval; larger values would overflow the date arithmetic.
+pub const MAX_NOTIFICATION_MINUTES: i64 = 24 * 60;
+
+pub async fn get_notification_settings(pool: &SqlitePool, workspace_id: i64) -> Result<NotificationSettings> {
+    Ok(database::notification::get_settings(pool, workspace_id).await?
+        .unwrap_or_else(|| NotificationSettings::defaults(workspace_id)))
+}
+
+pub async fn update_notification_settings(pool: &SqlitePool, workspace_id: i64, input: NotificationSettingsInput) -> Result<NotificationSettings> {
+    if database::workspace::get_workspace(pool, workspace_id).await?.is_none() {
+        return Err(AppError::NotFound("Workspace not found".to_string()));
+    }
+    let mut settings = get_notification_settings(pool, workspace_id).await?;
+    if let Some(v) = input.notify_at_start { settings.notify_at_start = v; }
+    if let Some(v) = input.lead_minutes { settings.lead_minutes = v; }
+    if let Some(v) = input.notify_at_end { settings.notify_at_end = v; }
+    if let Some(v) = input.overrun_interval_minutes { settings.overrun_interval_minutes = v; }
+    if let Some(v) = input.quiet_start { settings.quiet_start = (!v.is_empty()).then_some(v); }
+    if let Some(v) = input.quiet_end { settings.quiet_end = (!v.is_empty()).then_some(v); }
+
+    if [settings.lead_minutes, settings.overrun_interval_minutes].iter().any(|m| !(0..=MAX_NOTIFICATION_MINUTES).contains(m)) {
+        return Err(AppError::InvalidInput(format!("Notification minutes must be between 0 and {}", MAX_NOTIFICATION_MINUTES)));
+    }
+    for time in [&settings.quiet_start, &settings.quiet_end].into_iter().flatten() {
+        NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| AppError::InvalidInput(format!("Invalid quiet hour: {}", time)))?;
+    }
+    if settings.quiet_start.is_some() != settings.quiet_end.is_some() {
+        return Err(AppError::InvalidInput("Quiet hours need both a start and an end".to_string()));
+    }
+
+    database::notification::save_settings(pool, &settings).await?;
+    Ok(settings)
+}
+
+/// Every notification due in `(from, to]` across all workspaces, oldest first.
+/// Empty while notifications are switched off for the user.
+pub async fn notifications_between(pool: &SqlitePool, from: NaiveDateTime, to: NaiveDateTime) -> Result<Vec<ScheduledNotification>> {
+    let Some(user) = database::user::get_user(pool).await? else { return Ok(Vec::new()) };
+    if !user.is_notification_enabled {
+        return Ok(Vec::new());
+    }
+    let is_ko = user.lang == "ko";
+    let from_str = from.format("%Y-%m-%dT%H:%M:%S").to_string();
+
+    let mut notifications = Vec::new();
+    for workspace in database::workspace::get_workspaces(pool).await? {
+        let settings = get_notification_settings(pool, workspace.id).await?;
+        let (now_block, next_block) = database::notification::get_watched_blocks(pool, workspace.id, &from_str).await?;
+        let unplugged = database::workspace::get_unplugged_times(pool, workspace.id).await?;
+        notifications.extend(plan_notifications(&settings, now_block.as_ref(), next_block.as_ref(), &unplugged, is_ko, from, to)?);
+        let sessions = database::focus::get_running_sessions(pool, Some(workspace.id)).await?;
+        notifications.extend(plan_focus_notifications(&settings, &sessions, &unplugged, is_ko, from, to)?);
+    }
+    notifications.sort_by(|a, b| a.fire_at.cmp(&b.fire_at));
+    Ok(notifications)
+}
+
+/// Notifications of one workspace due in `(from, to]`, minus those in quiet hours or unplugged times.
+/// A paused NOW block gets no end or overrun reminders.
+fn plan_notifications(
+    settings: &NotificationSettings,
+    now_block: Option<&TimeBlock>,
+    next_block: Option<&TimeBlock>,
+    unplugged: &[UnpluggedTime],
+    is_ko: bool,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> Result<Vec<ScheduledNotification>> {
+    let mut planned: Vec<(&str, NaiveDateTime, &TimeBlock)> = Vec::new();
+
+    if let Some(block) = next_block.filter(|_| settings.notify_at_start) {
+        planned.push(("BLOCK_START", parse_datetime(&block.start_time)?, block));
+    }
+    if let Some(block) = now_block.filter(|b| !b.is_paused) {
+        let end = parse_datetime(&block.end_time)?;
+        if settings.lead_minutes > 0 {
+            planned.push(("BEFORE_END", end - Duration::minutes(settings.lead_minutes), block));
+        }
+        if settings.notify_at_end {
+            planned.push(("AT_END", end, block));
+        }
+        if settings.overrun_interval_minutes > 0 {
+            let interval = settings.overrun_interval_minutes;
+            // First reminder after `from`, then every interval up to `to`
+            let mut k = ((from - end).num_minutes() / interval + 1).max(1);
+            while end + Duration::minutes(k * interval) <= to {
+                planned.push(("OVERRUN", end + Duration::minutes(k * interval), block));
+                k += 1;
+            }
+        }
+    }
+
+    let notifications = planned.into_iter()
+        .filter(|(_, at, _)| *at > from && *at <= to)
+        .filter(|(_, at, _)| !is_muted(settings, unplugged, *at))
+        .map(|(kind, at, block)| {
+            let (title, body) = notification_text(kind, block, at, is_ko);
+            ScheduledNotification {
+                workspace_id: block.workspace_id,
+                block_id: block.id,
+                kind: kind.to_string(),
+                fire_at: at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                title,
+                body,
+            }
+        })
+        .collect();
+    Ok(notifications)
+}
+
+/// `FOCUS_END` when a running focus session's work time is over; sessions without a block are skipped.
+fn plan_focus_notifications(
+    settings: &NotificationSettings,
+    sessions: &[FocusSession],
+    unplugged: &[UnpluggedTime],
+    is_ko: bool,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> Result<Vec<ScheduledNotification>> {
+    let mut notifications = Vec::new();
+    for session in sessions {
+        // Sessions whose block was deleted are cancelled on their next check
+        let Some(block_id) = session.block_id else { continue; };
+        let at = parse_datetime(&session.started_at)? + Duration::minutes(session.work_minutes);
+        if at <= from || at > to || is_muted(settings, unplugged, at) {
+            continue;
+        }
+        let (title, body) = match (is_ko, session.break_minutes > 0) {
+            (true, true) => ("집중 세션 완료".to_string(), format!("'{}' 작업에서 잠시 {}분 쉬어가세요.", session.title, session.break_minutes)),
+            (true, false) => ("집중 세션 완료".to_string(), format!("'{}' 작업의 집중 세션을 마쳤어요.", session.title)),
+            (false, true) => ("Focus session complete".to_string(), format!("Take a {}-minute break from '{}'.", session.break_minutes, session.title)),
+            (false, false) => ("Focus session complete".to_string(), format!("You finished a focus session on '{}'.", session.title)),
+        };
+        notifications.push(ScheduledNotification {
+            workspace_id: session.workspace_id,
+            block_id,
+            kind: "FOCUS_END".to_string(),
+            fire_at: at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            title,
+            body,
+        });
+    }
+    Ok(notifications)
+}
+
+/// Quiet hours may wrap past midnight (e.g. 22:00–07:00); unplugged times never do.
+fn is_muted(settings: &NotificationSettings, unplugged: &[UnpluggedTime], at: NaiveDateTime) -> bool {
+    let time = at.time();
+    let parse = |s: &str| NaiveTime::parse_from_str(s, "%H:%M").ok();
+
+    if let (Some(start), Some(end)) = (settings.quiet_start.as_deref().and_then(parse), settings.quiet_end.as_deref().and_then(parse)) {
+        let quiet = if start <= end { time >= start && time < end } else { time >= start || time < end };
+        if quiet && start != end {
+            return true;
+        }
+    }
+    unplugged.iter().any(|ut| match (parse(&ut.start_time), parse(&ut.end_time)) {
+        (Some(start), Some(end)) => time >= start && time < end,
+        _ => false,
+    })
+}
+
+fn notification_text(kind: &str, block: &TimeBlock, at: NaiveDateTime, is_ko: bool) -> (String, String) {
+    let title = &block.title;
+    let end = parse_datetime(&block.end_time).map(|e| e.format("%H:%M").to_string()).unwrap_or_default();
+    let overrun = parse_datetime(&block.end_time).map(|e| (at - e).num_minutes()).unwrap_or_default();
+    let left = parse_datetime(&block.end_time).map(|e| (e - at).num_minutes()).unwrap_or_default();
+
+    match kind {
+        "BLOCK_START" => if is_ko { ("시작할 시간입니다".to_string(), format!("'{}' 작업을 시작할 시간이에요.", title)) } else { ("Time to start".to_string(), format!("'{}' is scheduled to start now.", title)) },
+        "BEFORE_END" => if is_ko { (format!("{}분 남았습니다", left), format!("'{}' 작업이 {}에 끝나요.", title, end)) } else { (format!("{} minutes left", left), format!("'{}' ends at {}.", title, end)) },
+        "AT_END" => if is_ko { ("예정 시간이 끝났습니다".to_string(), format!("'{}' 작업은 어떻게 되었나요?", title)) } else { ("Time's up".to_string(), format!("'{}' has reached its planned end. How did it go?", title)) },
+        _ => if is_ko { (format!("{}분 초과했습니다", overrun), format!("'{}' 작업이 예정보다 {}분 지났어요.", title, overrun)) } else { (format!("Running over by {} minutes", overrun), format!("'{}' is {} minutes past its planned end.", title, overrun)) },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new().connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY CHECK (id = 1), nickname TEXT NOT NULL, gemini_api_key TEXT, lang TEXT NOT NULL DEFAULT 'en', last_successful_model TEXT, is_notification_enabled BOOLEAN NOT NULL DEFAULT 0, is_free_user BOOLEAN NOT NULL DEFAULT 1, day_start_time TEXT NOT NULL DEFAULT '04:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO users (id, nickname, lang, is_notification_enabled) VALUES (1, 'TestUser', 'en', 1)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT, rollover_policy TEXT NOT NULL DEFAULT 'INBOX', last_rollover_date TEXT, overrun_policy TEXT NOT NULL DEFAULT 'FLAG', overrun_extend_minutes INTEGER NOT NULL DEFAULT 15)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE unplugged_times (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, label TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL, review_memo TEXT, planning_memo TEXT, is_urgent BOOLEAN NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query(database::timeline::CREATE_BLOCK_INTERVALS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::notification::CREATE_NOTIFICATION_SETTINGS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query(database::focus::CREATE_FOCUS_SESSIONS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        pool
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    fn kinds(notifications: &[ScheduledNotification]) -> Vec<(String, String)> {
+        notifications.iter().map(|n| (n.kind.clone(), n.fire_at.clone())).collect()
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_bounds_minutes() {
+        let pool = setup_db().await;
+        let input = |lead: i64, overrun: i64| NotificationSettingsInput { lead_minutes: Some(lead), overrun_interval_minutes: Some(overrun), ..Default::default() };
+
+        for (lead, overrun) in [(-1, 15), (5, -1), (MAX_NOTIFICATION_MINUTES + 1, 15), (5, i64::MAX)] {
+            let result = update_notification_settings(&pool, 1, input(lead, overrun)).await;
+            assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        }
+        let settings = update_notification_settings(&pool, 1, input(MAX_NOTIFICATION_MINUTES, 0)).await.unwrap();
+        assert_eq!((settings.lead_minutes, settings.overrun_interval_minutes), (MAX_NOTIFICATION_MINUTES, 0));
+    }
+
+    #[tokio::test]
+    async fn test_reminders_around_the_now_block_and_the_next_start() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO time_blocks (id, workspace_id, title, start_time, end_time, status) VALUES
+            (1, 1, 'Write report', '2026-03-02T09:00:00', '2026-03-02T10:00:00', 'NOW'),
+            (2, 1, 'Review', '2026-03-02T10:30:00', '2026-03-02T11:00:00', 'WILL')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at) VALUES (1, '2026-03-02T09:00:00')").execute(&pool).await.unwrap();
+
+        let fired = notifications_between(&pool, at("2026-03-02T09:00:00"), at("2026-03-02T10:45:00")).await.unwrap();
+        assert_eq!(kinds(&fired), vec![
+            ("BEFORE_END".to_string(), "2026-03-02T09:55:00".to_string()),
+            ("AT_END".to_string(), "2026-03-02T10:00:00".to_string()),
+            ("OVERRUN".to_string(), "2026-03-02T10:15:00".to_string()),
+            ("BLOCK_START".to_string(), "2026-03-02T10:30:00".to_string()),
+            ("OVERRUN".to_string(), "2026-03-02T10:30:00".to_string()),
+            ("OVERRUN".to_string(), "2026-03-02T10:45:00".to_string()),
+        ]);
+        assert_eq!(fired[0].title, "5 minutes left");
+        assert_eq!(fired[0].block_id, 1);
+
+        // A window that starts mid-overrun only yields the reminders after it
+        let fired = notifications_between(&pool, at("2026-03-02T10:40:00"), at("2026-03-02T11:00:00")).await.unwrap();
+        assert_eq!(kinds(&fired), vec![
+            ("OVERRUN".to_string(), "2026-03-02T10:45:00".to_string()),
+            ("OVERRUN".to_string(), "2026-03-02T11:00:00".to_string()),
+        ]);
+        assert_eq!(fired[0].body, "'Write report' is 45 minutes past its planned end.");
+
+        // A running focus session announces the end of its work time
+        sqlx::query("INSERT INTO focus_sessions (workspace_id, block_id, task_id, title, started_at, work_minutes, break_minutes) VALUES (1, 1, NULL, 'Write report', '2026-03-02T09:10:00', 25, 5)").execute(&pool).await.unwrap();
+        let fired = notifications_between(&pool, at("2026-03-02T09:30:00"), at("2026-03-02T09:40:00")).await.unwrap();
+        assert_eq!(kinds(&fired), vec![("FOCUS_END".to_string(), "2026-03-02T09:35:00".to_string())]);
+        assert_eq!(fired[0].body, "Take a 5-minute break from 'Write report'.");
+        sqlx::query("UPDATE focus_sessions SET status = 'COMPLETED'").execute(&pool).await.unwrap();
+
+        // Paused blocks stay quiet, and so does everything when the master switch is off
+        sqlx::query("UPDATE block_intervals SET ended_at = '2026-03-02T09:30:00'").execute(&pool).await.unwrap();
+        let fired = notifications_between(&pool, at("2026-03-02T09:00:00"), at("2026-03-02T10:45:00")).await.unwrap();
+        assert_eq!(kinds(&fired), vec![("BLOCK_START".to_string(), "2026-03-02T10:30:00".to_string())]);
+        sqlx::query("UPDATE users SET is_notification_enabled = 0").execute(&pool).await.unwrap();
+        assert!(notifications_between(&pool, at("2026-03-02T09:00:00"), at("2026-03-02T10:45:00")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_quiet_hours_and_unplugged_times_mute_notifications() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO time_blocks (id, workspace_id, title, start_time, end_time, status) VALUES
+            (1, 1, 'Late push', '2026-03-02T21:00:00', '2026-03-02T22:00:00', 'NOW')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO unplugged_times (workspace_id, label, start_time, end_time) VALUES (1, 'Dinner', '21:50', '21:58')").execute(&pool).await.unwrap();
+        update_notification_settings(&pool, 1, NotificationSettingsInput {
+            overrun_interval_minutes: Some(60),
+            quiet_start: Some("23:00".to_string()),
+            quiet_end: Some("07:00".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+
+        let fired = notifications_between(&pool, at("2026-03-02T21:00:00"), at("2026-03-03T08:00:00")).await.unwrap();
+        assert_eq!(kinds(&fired), vec![
+            ("AT_END".to_string(), "2026-03-02T22:00:00".to_string()),
+            ("OVERRUN".to_string(), "2026-03-03T07:00:00".to_string()),
+            ("OVERRUN".to_string(), "2026-03-03T08:00:00".to_string()),
+        ]);
+
+        // Empty strings clear the quiet hours; a lone bound is rejected
+        let settings = update_notification_settings(&pool, 1, NotificationSettingsInput {
+            quiet_start: Some(String::new()),
+            quiet_end: Some(String::new()),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(settings.quiet_start, None);
+        assert_eq!(settings.overrun_interval_minutes, 60);
+        let lone = update_notification_settings(&pool, 1, NotificationSettingsInput { quiet_start: Some("22:00".to_string()), ..Default::default() }).await;
+        assert!(matches!(lone, Err(AppError::InvalidInput(_))));
+        let missing = update_notification_settings(&pool, 9, NotificationSettingsInput::default()).await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,183 @@
+use sqlx::SqlitePool;
+use chrono::{NaiveDateTime, Local, Timelike};
+use crate::domain::{OverrunAction, TaskTransitionInput, TimeBlock, Result, AppError};
+use crate::database;
+use crate::services::timeline;
+
+pub async fn apply_overrun_policies(pool: &SqlitePool) -> Result<Vec<OverrunAction>> {
+    apply_overrun_policies_at(pool, Local::now().naive_local()).await
+}
+
+/// Applies each workspace's overrun policy to its NOW block once that block reaches its end.
+/// Paused blocks are left alone; resuming already pushes their end back. A block that fails
+/// is reported and skipped so the other workspaces are still handled.
+pub async fn apply_overrun_policies_at(pool: &SqlitePool, now_dt: NaiveDateTime) -> Result<Vec<OverrunAction>> {
+    let now_dt = now_dt.with_second(0).and_then(|dt| dt.with_nanosecond(0)).unwrap_or(now_dt);
+    let now_str = now_dt.format("%Y-%m-%dT%H:%M:00").to_string();
+
+    let mut actions = Vec::new();
+    for (block, policy, extend_minutes) in database::overrun::get_overdue_blocks(pool, &now_str).await? {
+        match apply_overrun_policy(pool, &block, &policy, extend_minutes, now_dt).await {
+            Ok(Some(overrun_minutes)) => actions.push(OverrunAction { workspace_id: block.workspace_id, block_id: block.id, policy, overrun_minutes }),
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to apply the overrun policy to block {}: {}", block.id, e),
+        }
+    }
+    Ok(actions)
+}
+
+/// The recorded overrun minutes after applying `policy`, or `None` when there was nothing new to do.
+async fn apply_overrun_policy(pool: &SqlitePool, block: &TimeBlock, policy: &str, extend_minutes: i64, now_dt: NaiveDateTime) -> Result<Option<i64>> {
+    let end = NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S").map_err(|e| AppError::DateParse(e.to_string()))?;
+    let past_minutes = (now_dt - end).num_minutes();
+
+    let overrun_minutes = match policy {
+        "EXTEND" => {
+            // Enough whole steps to put the end back in the future
+            let step = extend_minutes.max(1);
+            let extra = step.saturating_mul(past_minutes / step + 1);
+            let extra_minutes = i32::try_from(extra).map_err(|_| AppError::InvalidInput(format!("Cannot extend '{}' by {} minutes", block.title, extra)))?;
+            timeline::process_task_transition_at(pool, TaskTransitionInput {
+                block_id: block.id,
+                action: "DELAY".to_string(),
+                extra_minutes: Some(extra_minutes),
+                review_memo: None,
+            }, now_dt).await?;
+            database::overrun::record_overrun(pool, block.id, block.overrun_minutes + extra).await?
+        }
+        "COMPLETE" => {
+            complete_at_planned_end(pool, block.id, block.task_id, now_dt).await?;
+            block.overrun_minutes
+        }
+        _ => {
+            if past_minutes <= block.overrun_minutes {
+                return Ok(None);
+            }
+            database::overrun::record_overrun(pool, block.id, past_minutes).await?
+        }
+    };
+    Ok(Some(overrun_minutes))
+}
+
+/// Completes the task like "complete on time", except that a split task only
+/// closes this block so its later parts stay planned.
+async fn complete_at_planned_end(pool: &SqlitePool, block_id: i64, task_id: Option<i64>, now_dt: NaiveDateTime) -> Result<()> {
+    let later_parts: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM time_blocks WHERE task_id = ?1 AND id != ?2 AND status IN ('WILL', 'PENDING')")
+        .bind(task_id).bind(block_id).fetch_one(pool).await?;
+    if later_parts.0 > 0 {
+        return timeline::complete_block_part_at(pool, block_id, now_dt).await;
+    }
+    timeline::process_task_transition_at(pool, TaskTransitionInput {
+        block_id,
+        action: "COMPLETE_ON_TIME".to_string(),
+        extra_minutes: None,
+        review_memo: None,
+    }, now_dt).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new().connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY CHECK (id = 1), nickname TEXT NOT NULL, gemini_api_key TEXT, lang TEXT NOT NULL DEFAULT 'en', last_successful_model TEXT, is_notification_enabled BOOLEAN NOT NULL DEFAULT 0, is_free_user BOOLEAN NOT NULL DEFAULT 1, day_start_time TEXT NOT NULL DEFAULT '04:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO users (id, nickname) VALUES (1, 'TestUser')").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT, rollover_policy TEXT NOT NULL DEFAULT 'INBOX', last_rollover_date TEXT, overrun_policy TEXT NOT NULL DEFAULT 'FLAG', overrun_extend_minutes INTEGER NOT NULL DEFAULT 15)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, estimated_minutes INTEGER NOT NULL DEFAULT 0, position INTEGER NOT NULL DEFAULT 0, due_at TEXT, is_at_risk BOOLEAN NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL, review_memo TEXT, planning_memo TEXT, is_urgent BOOLEAN NOT NULL DEFAULT 0, overrun_minutes INTEGER NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query(database::timeline::CREATE_BLOCK_INTERVALS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::analytics::CREATE_PLAN_SNAPSHOTS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::analytics::CREATE_PLAN_SNAPSHOT_BLOCKS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Write report'), (2, 1, 'Review')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES
+            (1, 1, 1, 'Write report', '2026-03-02T09:00:00', '2026-03-02T10:00:00', 'NOW'),
+            (2, 2, 1, 'Review', '2026-03-02T10:00:00', '2026-03-02T10:30:00', 'WILL')").execute(&pool).await.unwrap();
+        pool
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    async fn block(pool: &SqlitePool, id: i64) -> TimeBlock {
+        sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1").bind(id).fetch_one(pool).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_extend_pushes_the_end_and_the_following_blocks() {
+        let pool = setup_db().await;
+        sqlx::query("UPDATE workspaces SET overrun_policy = 'EXTEND', overrun_extend_minutes = 10").execute(&pool).await.unwrap();
+
+        assert!(apply_overrun_policies_at(&pool, at("2026-03-02T09:59:30")).await.unwrap().is_empty());
+        let actions = apply_overrun_policies_at(&pool, at("2026-03-02T10:12:00")).await.unwrap();
+        assert_eq!(actions, vec![OverrunAction { workspace_id: 1, block_id: 1, policy: "EXTEND".to_string(), overrun_minutes: 20 }]);
+
+        let now_block = block(&pool, 1).await;
+        assert_eq!(now_block.end_time, "2026-03-02T10:20:00");
+        assert_eq!(now_block.status, "NOW");
+        assert_eq!(block(&pool, 2).await.start_time, "2026-03-02T10:20:00");
+
+        let actions = apply_overrun_policies_at(&pool, at("2026-03-02T10:20:00")).await.unwrap();
+        assert_eq!(actions[0].overrun_minutes, 30);
+        assert_eq!(block(&pool, 1).await.end_time, "2026-03-02T10:30:00");
+    }
+
+    #[tokio::test]
+    async fn test_a_failing_block_does_not_stop_other_workspaces() {
+        let pool = setup_db().await;
+        // Stored before the extension was bounded; the step no longer fits an i32
+        sqlx::query("UPDATE workspaces SET overrun_policy = 'EXTEND', overrun_extend_minutes = ?1 WHERE id = 1").bind(i64::from(i32::MAX) + 1).execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (2, 'Side')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (3, 2, 'Side task')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (3, 3, 2, 'Side task', '2026-03-02T09:00:00', '2026-03-02T10:00:00', 'NOW')").execute(&pool).await.unwrap();
+
+        let actions = apply_overrun_policies_at(&pool, at("2026-03-02T10:10:00")).await.unwrap();
+        assert_eq!(actions, vec![OverrunAction { workspace_id: 2, block_id: 3, policy: "FLAG".to_string(), overrun_minutes: 10 }]);
+        assert_eq!(block(&pool, 1).await.end_time, "2026-03-02T10:00:00");
+    }
+
+    #[tokio::test]
+    async fn test_complete_and_flag_policies() {
+        let pool = setup_db().await;
+
+        // FLAG only records how far the block ran over, and only when that grows
+        let actions = apply_overrun_policies_at(&pool, at("2026-03-02T10:25:00")).await.unwrap();
+        assert_eq!(actions[0].overrun_minutes, 25);
+        assert!(apply_overrun_policies_at(&pool, at("2026-03-02T10:25:40")).await.unwrap().is_empty());
+        assert_eq!(block(&pool, 1).await.end_time, "2026-03-02T10:00:00");
+
+        // A paused block is left alone
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at, ended_at) VALUES (1, '2026-03-02T09:00:00', '2026-03-02T09:40:00')").execute(&pool).await.unwrap();
+        sqlx::query("UPDATE workspaces SET overrun_policy = 'COMPLETE'").execute(&pool).await.unwrap();
+        assert!(apply_overrun_policies_at(&pool, at("2026-03-02T10:30:00")).await.unwrap().is_empty());
+
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at) VALUES (1, '2026-03-02T09:45:00')").execute(&pool).await.unwrap();
+        let actions = apply_overrun_policies_at(&pool, at("2026-03-02T10:31:00")).await.unwrap();
+        assert_eq!(actions[0].policy, "COMPLETE");
+        let done = block(&pool, 1).await;
+        assert_eq!((done.status.as_str(), done.end_time.as_str(), done.overrun_minutes), ("DONE", "2026-03-02T10:00:00", 25));
+        let (open,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM block_intervals WHERE ended_at IS NULL").fetch_one(&pool).await.unwrap();
+        assert_eq!(open, 0);
+        // The next block takes over right after the planned end
+        let next = block(&pool, 2).await;
+        assert_eq!((next.status.as_str(), next.start_time.as_str()), ("NOW", "2026-03-02T10:00:00"));
+    }
+
+    #[tokio::test]
+    async fn test_complete_policy_promotes_the_next_part_of_a_split_task() {
+        let pool = setup_db().await;
+        sqlx::query("UPDATE workspaces SET overrun_policy = 'COMPLETE'").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (3, 1, 1, 'Write report', '2026-03-02T10:30:00', '2026-03-02T11:00:00', 'WILL')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at) VALUES (1, '2026-03-02T09:00:00')").execute(&pool).await.unwrap();
+
+        apply_overrun_policies_at(&pool, at("2026-03-02T10:05:00")).await.unwrap();
+        assert_eq!(block(&pool, 1).await.status, "DONE");
+        let next = block(&pool, 2).await;
+        assert_eq!((next.status.as_str(), next.start_time.as_str()), ("NOW", "2026-03-02T10:00:00"));
+        // The later part of the split task stays planned
+        assert_eq!(block(&pool, 3).await.status, "WILL");
+    }
+}
@@ -0,0 +1,225 @@
+use sqlx::SqlitePool;
+use crate::domain::{AddTaskInput, QuickAddResult, Result, AppError, DEFAULT_TASK_PRIORITY};
+use crate::services;
+
+const DEFAULT_MINUTES: i32 = 30;
+/// Longest duration a quick-add line may give a task.
+pub const MAX_QUICK_ADD_MINUTES: i64 = 24 * 60;
+
+/// Parses a single line such as `Fix login bug 1h30m #backend @will-done !urgent >inbox // memo`
+/// and adds the resulting task.
+pub async fn quick_add_task(pool: &SqlitePool, workspace_id: i64, text: &str) -> Result<QuickAddResult> {
+    let parsed = parse_quick_add(workspace_id, text)?;
+    services::timeline::add_task(pool, parsed.input.clone()).await?;
+    Ok(parsed)
+}
+
+/// Supported tokens:
+/// - `1h30m`, `90min`, `1시간 30분` — duration (summed when split over several tokens)
+/// - `#label`, `@project`, `+tag` (repeatable)
+/// - `!urgent`, `!p0`..`!p3` (priority), `>inbox`
+/// - `// memo` — everything after it becomes the planning memo
+///
+/// Anything else is part of the title.
+pub fn parse_quick_add(workspace_id: i64, text: &str) -> Result<QuickAddResult> {
+    let mut diagnostics = Vec::new();
+
+    let (body, planning_memo) = split_memo(text);
+
+    let mut title_words: Vec<&str> = Vec::new();
+    let mut total_minutes: Option<i32> = None;
+    let mut project_name: Option<String> = None;
+    let mut label_name: Option<String> = None;
+    let mut tags: Vec<String> = Vec::new();
+    let mut is_urgent = false;
+    let mut priority: Option<i64> = None;
+    let mut is_inbox = false;
+
+    for token in body.split_whitespace() {
+        if let Some(minutes) = parse_duration_token(token) {
+            let sum = total_minutes.unwrap_or(0) as i64 + minutes;
+            if sum <= MAX_QUICK_ADD_MINUTES {
+                total_minutes = Some(sum as i32);
+            } else {
+                diagnostics.push(format!("Duration '{}' would make the task longer than {}h and was ignored.", token, MAX_QUICK_ADD_MINUTES / 60));
+            }
+        } else if let Some(name) = token.strip_prefix('#').filter(|n| !n.is_empty()) {
+            if let Some(prev) = label_name.replace(name.to_string()) {
+                diagnostics.push(format!("Multiple labels given; '#{}' replaced '#{}'.", name, prev));
+            }
+        } else if let Some(name) = token.strip_prefix('+').filter(|n| !n.is_empty()) {
+            if !tags.iter().any(|t| t == name) {
+                tags.push(name.to_string());
+            }
+        } else if let Some(name) = token.strip_prefix('@').filter(|n| !n.is_empty()) {
+            if let Some(prev) = project_name.replace(name.to_string()) {
+                diagnostics.push(format!("Multiple projects given; '@{}' replaced '@{}'.", name, prev));
+            }
+        } else if let Some(flag) = token.strip_prefix('!').filter(|f| !f.is_empty()) {
+            match flag.to_lowercase().as_str() {
+                "urgent" => is_urgent = true,
+                "p0" | "p1" | "p2" | "p3" => {
+                    let level = flag[1..].parse::<i64>().unwrap_or(DEFAULT_TASK_PRIORITY);
+                    if let Some(prev) = priority.replace(level) {
+                        diagnostics.push(format!("Multiple priorities given; '!p{}' replaced '!p{}'.", level, prev));
+                    }
+                }
+                _ => diagnostics.push(format!("Unknown flag '{}' was ignored.", token)),
+            }
+        } else if let Some(target) = token.strip_prefix('>').filter(|t| !t.is_empty()) {
+            match target.to_lowercase().as_str() {
+                "inbox" => is_inbox = true,
+                _ => diagnostics.push(format!("Unknown destination '{}' was ignored.", token)),
+            }
+        } else {
+            title_words.push(token);
+        }
+    }
+
+    let title = title_words.join(" ");
+    if title.is_empty() {
+        return Err(AppError::InvalidInput("Quick add needs a task title.".to_string()));
+    }
+
+    let minutes = match total_minutes {
+        Some(m) if m > 0 => m,
+        Some(_) => {
+            diagnostics.push(format!("A zero duration is not allowed; defaulted to {} minutes.", DEFAULT_MINUTES));
+            DEFAULT_MINUTES
+        }
+        None => {
+            diagnostics.push(format!("No duration given; defaulted to {} minutes.", DEFAULT_MINUTES));
+            DEFAULT_MINUTES
+        }
+    };
+
+    if is_urgent && is_inbox {
+        diagnostics.push("'!urgent' is ignored for tasks sent to the inbox.".to_string());
+        is_urgent = false;
+    }
+
+    Ok(QuickAddResult {
+        input: AddTaskInput {
+            workspace_id,
+            title,
+            hours: minutes / 60,
+            minutes: minutes % 60,
+            planning_memo,
+            is_urgent,
+            is_inbox: Some(is_inbox),
+            project_name,
+            label_name,
+            due_at: None,
+            priority,
+            tags: if tags.is_empty() { None } else { Some(tags) },
+            target_date: None,
+        },
+        diagnostics,
+    })
+}
+
+/// `//` only starts a memo at the beginning of a word, so URLs in the title stay intact.
+fn split_memo(text: &str) -> (&str, Option<String>) {
+    let text = text.trim();
+    let pos = text
+        .match_indices("//")
+        .map(|(i, _)| i)
+        .find(|&i| i == 0 || text[..i].ends_with(char::is_whitespace));
+    match pos {
+        Some(i) => {
+            let memo = text[i + 2..].trim();
+            (&text[..i], if memo.is_empty() { None } else { Some(memo.to_string()) })
+        }
+        None => (text, None),
+    }
+}
+
+/// Accepts one or more `<number><unit>` pairs making up the whole token, e.g. `1h30m`, `90min`, `1시간`.
+/// Oversized values saturate instead of failing, so the caller can reject them as too long.
+fn parse_duration_token(token: &str) -> Option<i64> {
+    let mut total: i64 = 0;
+    let mut rest = token;
+    while !rest.is_empty() {
+        let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits_end == 0 {
+            return None;
+        }
+        let value: i64 = rest[..digits_end].parse().unwrap_or(i64::MAX);
+        rest = &rest[digits_end..];
+        let unit_end = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let factor = match rest[..unit_end].to_lowercase().as_str() {
+            "h" | "hr" | "hrs" | "hour" | "hours" | "시간" => 60,
+            "m" | "min" | "mins" | "minute" | "minutes" | "분" => 1,
+            _ => return None,
+        };
+        total = total.saturating_add(value.saturating_mul(factor));
+        rest = &rest[unit_end..];
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_line() {
+        let parsed = parse_quick_add(1, "Fix login bug 1h30m #backend @will-done +auth +hotfix !urgent // check the session cookie").unwrap();
+        assert_eq!(parsed.input.title, "Fix login bug");
+        assert_eq!((parsed.input.hours, parsed.input.minutes), (1, 30));
+        assert_eq!(parsed.input.label_name.as_deref(), Some("backend"));
+        assert_eq!(parsed.input.project_name.as_deref(), Some("will-done"));
+        assert!(parsed.input.is_urgent);
+        assert_eq!(parsed.input.tags, Some(vec!["auth".to_string(), "hotfix".to_string()]));
+        assert_eq!(parsed.input.is_inbox, Some(false));
+        assert_eq!(parsed.input.planning_memo.as_deref(), Some("check the session cookie"));
+        assert!(parsed.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_parse_korean_duration_and_inbox() {
+        let parsed = parse_quick_add(1, "보고서 작성 1시간 30분 >inbox !urgent").unwrap();
+        assert_eq!(parsed.input.title, "보고서 작성");
+        assert_eq!((parsed.input.hours, parsed.input.minutes), (1, 30));
+        assert_eq!(parsed.input.is_inbox, Some(true));
+        assert!(!parsed.input.is_urgent);
+        assert_eq!(parsed.diagnostics.len(), 1);
+
+        let parsed = parse_quick_add(1, "Read 90min !P1").unwrap();
+        assert_eq!((parsed.input.hours, parsed.input.minutes), (1, 30));
+        assert_eq!(parsed.input.priority, Some(1));
+    }
+
+    #[test]
+    fn test_parse_defaults_and_diagnostics() {
+        let parsed = parse_quick_add(1, "Check https://example.com/a !later").unwrap();
+        assert_eq!(parsed.input.title, "Check https://example.com/a");
+        assert_eq!((parsed.input.hours, parsed.input.minutes), (0, 30));
+        assert!(parsed.input.planning_memo.is_none());
+        assert_eq!(parsed.diagnostics.len(), 2);
+
+        assert!(parse_quick_add(1, "45m #label // memo only").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_overlong_durations() {
+        let parsed = parse_quick_add(1, "Marathon 30000000h 30000000h").unwrap();
+        assert_eq!(parsed.input.title, "Marathon");
+        assert_eq!((parsed.input.hours, parsed.input.minutes), (0, DEFAULT_MINUTES));
+        assert_eq!(parsed.diagnostics, vec![
+            "Duration '30000000h' would make the task longer than 24h and was ignored.".to_string(),
+            "Duration '30000000h' would make the task longer than 24h and was ignored.".to_string(),
+            format!("No duration given; defaulted to {} minutes.", DEFAULT_MINUTES),
+        ]);
+
+        // A token overflowing on its own is still read as a duration, and rejected
+        let parsed = parse_quick_add(1, "Marathon 99999999999999999999h").unwrap();
+        assert_eq!(parsed.input.title, "Marathon");
+        assert_eq!(parsed.diagnostics[0], "Duration '99999999999999999999h' would make the task longer than 24h and was ignored.");
+
+        // Up to the limit the parts add up; the part crossing it is dropped
+        let parsed = parse_quick_add(1, "Offsite 20h 4h 30m").unwrap();
+        assert_eq!((parsed.input.hours, parsed.input.minutes), (24, 0));
+        assert_eq!(parsed.diagnostics, vec!["Duration '30m' would make the task longer than 24h and was ignored.".to_string()]);
+    }
+}
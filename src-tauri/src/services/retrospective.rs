@@ -0,0 +1,155 @@
+use sqlx::SqlitePool;
+use chrono::Local;
+use crate::domain::{Retrospective, SaveRetrospectiveInput, MIN_RETROSPECTIVE_SCORE, MAX_RETROSPECTIVE_SCORE, Result, AppError};
+use crate::database;
+use crate::services::timeline::{logical_range, parse_date};
+
+/// Longest window `get_retrospectives` serves in one call.
+pub const MAX_RETROSPECTIVE_RANGE_DAYS: i64 = 366;
+
+/// Creates or replaces the entry of one logical day; blank text fields are cleared.
+pub async fn save_retrospective(pool: &SqlitePool, input: SaveRetrospectiveInput) -> Result<Retrospective> {
+    let logical_date = parse_date(&input.logical_date)?;
+    database::workspace::get_workspace(pool, input.workspace_id).await?.ok_or_else(|| AppError::NotFound("Workspace not found".to_string()))?;
+    for score in [input.mood_score, input.energy_score].into_iter().flatten() {
+        if !(MIN_RETROSPECTIVE_SCORE..=MAX_RETROSPECTIVE_SCORE).contains(&score) {
+            return Err(AppError::InvalidInput(format!("Scores range from {} to {}.", MIN_RETROSPECTIVE_SCORE, MAX_RETROSPECTIVE_SCORE)));
+        }
+    }
+
+    let now = Local::now().format("%Y-%m-%dT%H:%M:00").to_string();
+    let retro = Retrospective {
+        id: 0,
+        workspace_id: input.workspace_id,
+        logical_date: logical_date.format("%Y-%m-%d").to_string(),
+        went_well: non_blank(input.went_well),
+        blockers: non_blank(input.blockers),
+        mood_score: input.mood_score,
+        energy_score: input.energy_score,
+        learnings: non_blank(input.learnings),
+        tomorrow_focus: non_blank(input.tomorrow_focus),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    if retro.went_well.is_none() && retro.blockers.is_none() && retro.learnings.is_none() && retro.tomorrow_focus.is_none()
+        && retro.mood_score.is_none() && retro.energy_score.is_none() {
+        return Err(AppError::InvalidInput("A retrospective needs at least one answer.".to_string()));
+    }
+    database::retrospective::save_retrospective(pool, &retro).await
+}
+
+pub async fn get_retrospective(pool: &SqlitePool, workspace_id: i64, logical_date: &str) -> Result<Option<Retrospective>> {
+    let date = parse_date(logical_date)?;
+    database::retrospective::get_retrospective(pool, workspace_id, &date.format("%Y-%m-%d").to_string()).await
+}
+
+/// Entries for the logical dates `start_date..=end_date`; days without one are skipped.
+pub async fn get_retrospectives(pool: &SqlitePool, workspace_id: i64, start_date: &str, end_date: &str) -> Result<Vec<Retrospective>> {
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let range = logical_range(start_date, end_date, MAX_RETROSPECTIVE_RANGE_DAYS, &user.day_start_time)?;
+    database::retrospective::get_retrospectives_in_range(pool, workspace_id, &range.start.format("%Y-%m-%d").to_string(), &range.end.format("%Y-%m-%d").to_string()).await
+}
+
+pub async fn delete_retrospective(pool: &SqlitePool, workspace_id: i64, logical_date: &str) -> Result<()> {
+    let date = parse_date(logical_date)?;
+    if !database::retrospective::delete_retrospective(pool, workspace_id, &date.format("%Y-%m-%d").to_string()).await? {
+        return Err(AppError::NotFound("Retrospective not found".to_string()));
+    }
+    Ok(())
+}
+
+/// Retrospective notes as background for the achievement prompt. Scores and tomorrow's focus
+/// are left out because the report documents finished work only.
+pub fn build_retrospective_context(retros: &[Retrospective]) -> String {
+    let mut context = String::new();
+    for retro in retros {
+        let notes: Vec<(&str, &String)> = [("Went Well", &retro.went_well), ("Blockers", &retro.blockers), ("Learnings", &retro.learnings)]
+            .into_iter()
+            .filter_map(|(name, value)| value.as_ref().map(|v| (name, v)))
+            .collect();
+        if notes.is_empty() {
+            continue;
+        }
+        context.push_str(&format!("### {}\n", retro.logical_date));
+        for (name, value) in notes {
+            context.push_str(&format!("- **{}**: {}\n", name, value));
+        }
+        context.push('\n');
+    }
+    context
+}
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = database::test_pool().await;
+        sqlx::query("INSERT INTO users (id, nickname) VALUES (1, 'TestUser')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test'), (2, 'Other')").execute(&pool).await.unwrap();
+        pool
+    }
+
+    fn input(workspace_id: i64, logical_date: &str, went_well: &str) -> SaveRetrospectiveInput {
+        SaveRetrospectiveInput {
+            workspace_id,
+            logical_date: logical_date.to_string(),
+            went_well: Some(went_well.to_string()),
+            blockers: None,
+            mood_score: Some(4),
+            energy_score: None,
+            learnings: None,
+            tomorrow_focus: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_one_entry_per_workspace_and_day() {
+        let pool = setup_db().await;
+        let first = save_retrospective(&pool, input(1, "2026-03-02", "Shipped the release")).await.unwrap();
+        save_retrospective(&pool, input(2, "2026-03-02", "Other workspace")).await.unwrap();
+        save_retrospective(&pool, input(1, "2026-03-04", "Quiet day")).await.unwrap();
+
+        let updated = save_retrospective(&pool, SaveRetrospectiveInput {
+            blockers: Some("  Flaky CI  ".to_string()),
+            went_well: Some("   ".to_string()),
+            ..input(1, "2026-03-02", "")
+        }).await.unwrap();
+        assert_eq!(updated.id, first.id);
+        assert_eq!((updated.went_well, updated.blockers.as_deref()), (None, Some("Flaky CI")));
+
+        let listed = get_retrospectives(&pool, 1, "2026-03-01", "2026-03-04").await.unwrap();
+        assert_eq!(listed.iter().map(|r| r.logical_date.as_str()).collect::<Vec<_>>(), vec!["2026-03-02", "2026-03-04"]);
+        assert_eq!(get_retrospective(&pool, 2, "2026-03-02").await.unwrap().unwrap().went_well.as_deref(), Some("Other workspace"));
+
+        delete_retrospective(&pool, 1, "2026-03-04").await.unwrap();
+        assert!(get_retrospective(&pool, 1, "2026-03-04").await.unwrap().is_none());
+        assert!(matches!(delete_retrospective(&pool, 1, "2026-03-04").await, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_validation_and_achievement_context() {
+        let pool = setup_db().await;
+        assert!(matches!(save_retrospective(&pool, input(9, "2026-03-02", "x")).await, Err(AppError::NotFound(_))));
+        assert!(matches!(save_retrospective(&pool, input(1, "2026/03/02", "x")).await, Err(AppError::DateParse(_))));
+        assert!(matches!(save_retrospective(&pool, SaveRetrospectiveInput { energy_score: Some(6), ..input(1, "2026-03-02", "x") }).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(save_retrospective(&pool, SaveRetrospectiveInput { mood_score: None, ..input(1, "2026-03-02", " ") }).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(get_retrospectives(&pool, 1, "2026-03-02", "2026-03-01").await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(get_retrospectives(&pool, 1, "2026-01-01", "2027-01-02").await, Err(AppError::InvalidInput(_))));
+
+        let with_notes = save_retrospective(&pool, SaveRetrospectiveInput {
+            learnings: Some("Smaller PRs review faster".to_string()),
+            tomorrow_focus: Some("Start the migration".to_string()),
+            ..input(1, "2026-03-02", "Shipped the release")
+        }).await.unwrap();
+        let scores_only = save_retrospective(&pool, SaveRetrospectiveInput { went_well: None, ..input(1, "2026-03-03", "") }).await.unwrap();
+        assert_eq!(
+            build_retrospective_context(&[with_notes, scores_only]),
+            "### 2026-03-02\n- **Went Well**: Shipped the release\n- **Learnings**: Smaller PRs review faster\n\n"
+        );
+    }
+}
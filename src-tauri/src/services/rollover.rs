@@ -0,0 +1,272 @@
+use sqlx::{SqlitePool, Connection};
+use chrono::{NaiveDateTime, Local};
+use crate::domain::{TimeBlock, RolloverReport, RolloverItem, Result, AppError};
+use crate::database;
+use crate::services::timeline::{self, logical_date_of, logical_day_bounds};
+
+pub async fn run_rollover(pool: &SqlitePool, workspace_id: i64) -> Result<Option<RolloverReport>> {
+    run_rollover_at(pool, workspace_id, Local::now().naive_local()).await
+}
+
+/// Applies the workspace's rollover policy to the unfinished blocks left on past logical days.
+/// Runs at most once per logical day; returns the report when anything was rolled over.
+pub async fn run_rollover_at(pool: &SqlitePool, workspace_id: i64, now_dt: NaiveDateTime) -> Result<Option<RolloverReport>> {
+    let mut tx = pool.begin().await?;
+    let (policy, last_rollover_date) = database::rollover::get_rollover_state(&mut tx, workspace_id).await?
+        .ok_or_else(|| AppError::NotFound("Workspace not found".to_string()))?;
+    let day_start_time = timeline::day_start_time_in(&mut tx).await?;
+    let today = logical_date_of(now_dt, &day_start_time);
+    let today_str = today.format("%Y-%m-%d").to_string();
+    if last_rollover_date.as_deref().is_some_and(|d| d >= today_str.as_str()) {
+        return Ok(None);
+    }
+
+    let (start_of_today, _) = logical_day_bounds(today, &day_start_time)?;
+    let leftovers: Vec<TimeBlock> = sqlx::query_as(
+        "SELECT * FROM time_blocks WHERE workspace_id = ?1 AND task_id IS NOT NULL AND status IN ('NOW', 'WILL', 'PENDING') AND start_time < ?2 ORDER BY start_time ASC, id ASC"
+    )
+    .bind(workspace_id)
+    .bind(&start_of_today)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // One entry per task, in the order the leftovers were planned
+    let mut groups: Vec<(i64, Vec<TimeBlock>)> = Vec::new();
+    for block in leftovers {
+        let task_id = block.task_id.unwrap_or_default();
+        match groups.iter_mut().find(|(id, _)| *id == task_id) {
+            Some((_, blocks)) => blocks.push(block),
+            None => groups.push((task_id, vec![block])),
+        }
+    }
+
+    let mut items = Vec::new();
+    for (task_id, blocks) in groups {
+        let first_start = NaiveDateTime::parse_from_str(&blocks[0].start_time, "%Y-%m-%dT%H:%M:%S").map_err(|e| AppError::DateParse(e.to_string()))?;
+        let minutes: i64 = blocks.iter().map(block_minutes).sum();
+        let mut item = RolloverItem {
+            id: 0,
+            report_id: 0,
+            task_id: Some(task_id),
+            title: blocks[0].title.clone(),
+            from_date: logical_date_of(first_start, &day_start_time).format("%Y-%m-%d").to_string(),
+            minutes,
+            action: policy.clone(),
+            new_start: None,
+        };
+
+        if policy == "COMPLETE" {
+            for block in &blocks {
+                sqlx::query("UPDATE time_blocks SET status = 'DONE' WHERE id = ?1").bind(block.id).execute(&mut *tx).await?;
+                sqlx::query("UPDATE block_intervals SET ended_at = ?1 WHERE block_id = ?2 AND ended_at IS NULL").bind(&block.end_time).bind(block.id).execute(&mut *tx).await?;
+            }
+            sqlx::query("UPDATE time_blocks SET status = 'DONE' WHERE task_id = ?1 AND status = 'CONTINUED' AND start_time < ?2").bind(task_id).bind(&start_of_today).execute(&mut *tx).await?;
+        } else {
+            // A carry that would land before a prerequisite or after a dependent is undone, leaving the leftovers where they are
+            let mut carry = tx.begin().await?;
+            // Worked time stays on its day: a block is cut back to the end of its last interval and only the rest moves on
+            let mut rest_minutes = 0;
+            for block in &blocks {
+                sqlx::query("UPDATE block_intervals SET ended_at = ?1 WHERE block_id = ?2 AND ended_at IS NULL").bind(&block.end_time).bind(block.id).execute(&mut *carry).await?;
+                let worked_end: Option<String> = sqlx::query_scalar("SELECT MAX(ended_at) FROM block_intervals WHERE block_id = ?1").bind(block.id).fetch_one(&mut *carry).await?;
+                match worked_end {
+                    Some(worked_end) => {
+                        let rest = minutes_between(&worked_end, &block.end_time);
+                        sqlx::query("UPDATE time_blocks SET end_time = ?1, status = ?2 WHERE id = ?3")
+                            .bind(&worked_end)
+                            .bind(if rest > 0 { "CONTINUED" } else { "DONE" })
+                            .bind(block.id)
+                            .execute(&mut *carry)
+                            .await?;
+                        rest_minutes += rest;
+                    }
+                    None => {
+                        sqlx::query("DELETE FROM time_blocks WHERE id = ?1").bind(block.id).execute(&mut *carry).await?;
+                        rest_minutes += block_minutes(block);
+                    }
+                }
+            }
+            let (remaining,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM time_blocks WHERE task_id = ?1").bind(task_id).fetch_one(&mut *carry).await?;
+            if rest_minutes == 0 {
+                // Worked through every leftover block: nothing is left to move
+                sqlx::query("UPDATE time_blocks SET status = 'DONE' WHERE task_id = ?1 AND status = 'CONTINUED' AND start_time < ?2").bind(task_id).bind(&start_of_today).execute(&mut *carry).await?;
+                item.action = "COMPLETE".to_string();
+            } else if policy == "INBOX" && remaining == 0 {
+                sqlx::query("UPDATE tasks SET position = (SELECT COALESCE(MAX(position), 0) + 1 FROM tasks WHERE workspace_id = ?1 AND id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL)) WHERE id = ?2")
+                    .bind(workspace_id)
+                    .bind(task_id)
+                    .execute(&mut *carry)
+                    .await?;
+            } else {
+                // Finished parts keep a task on the timeline, so the inbox policy carries it instead
+                // The new blocks keep the urgency and planning memo of the ones they replace
+                let is_urgent = blocks.iter().any(|b| b.is_urgent);
+                let planning_memo = blocks.iter().find_map(|b| b.planning_memo.clone());
+                let start = timeline::append_start(&mut carry, workspace_id, None, now_dt).await?;
+                let end = timeline::schedule_task_blocks(&mut carry, workspace_id, task_id, &item.title, start, rest_minutes, is_urgent, "WILL").await?;
+                if planning_memo.is_some() {
+                    sqlx::query("UPDATE time_blocks SET planning_memo = ?1 WHERE task_id = ?2 AND status = 'WILL' AND start_time >= ?3 AND start_time < ?4")
+                        .bind(&planning_memo)
+                        .bind(task_id)
+                        .bind(start.format("%Y-%m-%dT%H:%M:00").to_string())
+                        .bind(end.format("%Y-%m-%dT%H:%M:00").to_string())
+                        .execute(&mut *carry)
+                        .await?;
+                }
+                item.action = "CARRY".to_string();
+                item.minutes = rest_minutes;
+                item.new_start = Some(start.format("%Y-%m-%dT%H:%M:00").to_string());
+            }
+            if let Err(e) = timeline::ensure_dependency_order(&mut carry, task_id).await {
+                eprintln!("Left '{}' on its past day: {}", item.title, e);
+                carry.rollback().await?;
+                continue;
+            }
+            carry.commit().await?;
+        }
+        items.push(item);
+    }
+
+    timeline::refresh_deadline_risk(&mut tx, workspace_id).await?;
+    database::rollover::set_last_rollover_date(&mut tx, workspace_id, &today_str).await?;
+    if items.is_empty() {
+        tx.commit().await?;
+        return Ok(None);
+    }
+
+    let report_id = database::rollover::create_report(&mut tx, workspace_id, &today_str, &policy, &now_dt.format("%Y-%m-%dT%H:%M:00").to_string()).await?;
+    for item in &items {
+        database::rollover::create_report_item(&mut tx, report_id, item).await?;
+    }
+    tx.commit().await?;
+    database::rollover::get_report(pool, report_id).await
+}
+
+fn block_minutes(block: &TimeBlock) -> i64 {
+    minutes_between(&block.start_time, &block.end_time)
+}
+
+fn minutes_between(start: &str, end: &str) -> i64 {
+    let start = NaiveDateTime::parse_from_str(start, "%Y-%m-%dT%H:%M:%S");
+    let end = NaiveDateTime::parse_from_str(end, "%Y-%m-%dT%H:%M:%S");
+    match (start, end) {
+        (Ok(start), Ok(end)) => (end - start).num_minutes().max(0),
+        _ => 0,
+    }
+}
+
+pub async fn get_rollover_reports(pool: &SqlitePool, workspace_id: i64, pending_only: bool) -> Result<Vec<RolloverReport>> {
+    database::rollover::get_reports(pool, workspace_id, pending_only).await
+}
+
+pub async fn acknowledge_rollover_report(pool: &SqlitePool, id: i64) -> Result<()> {
+    database::rollover::get_report(pool, id).await?.ok_or_else(|| AppError::NotFound("Rollover report not found".to_string()))?;
+    database::rollover::acknowledge_report(pool, id, &Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = database::test_pool().await;
+        sqlx::query("INSERT INTO users (id, nickname) VALUES (1, 'TestUser')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        pool
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_inbox_policy_returns_untouched_tasks_and_carries_started_ones() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Stale plan'), (2, 1, 'Half done'), (3, 1, 'Forgotten')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES
+            (10, 1, 1, 'Stale plan', '2026-03-01T15:00:00', '2026-03-01T16:00:00', 'WILL'),
+            (20, 2, 1, 'Half done', '2026-03-01T10:00:00', '2026-03-01T10:30:00', 'DONE'),
+            (21, 2, 1, 'Half done', '2026-03-01T11:00:00', '2026-03-01T11:45:00', 'WILL'),
+            (30, 3, 1, 'Forgotten', '2026-03-01T20:00:00', '2026-03-01T21:00:00', 'NOW')").execute(&pool).await.unwrap();
+        sqlx::query("UPDATE time_blocks SET is_urgent = 1, planning_memo = 'Finish the tests' WHERE id = 21").execute(&pool).await.unwrap();
+
+        // 03:00 on the 2nd still belongs to the logical 1st: nothing is left over yet.
+        assert!(run_rollover_at(&pool, 1, at("2026-03-02T03:00:00")).await.unwrap().is_none());
+
+        let report = run_rollover_at(&pool, 1, at("2026-03-02T09:00:00")).await.unwrap().unwrap();
+        assert_eq!(report.logical_date, "2026-03-02");
+        assert_eq!(report.policy, "INBOX");
+        let summary: Vec<(&str, &str, i64)> = report.items.iter().map(|i| (i.title.as_str(), i.action.as_str(), i.minutes)).collect();
+        assert_eq!(summary, vec![("Half done", "CARRY", 45), ("Stale plan", "INBOX", 60), ("Forgotten", "INBOX", 60)]);
+        assert_eq!(report.items[0].new_start.as_deref(), Some("2026-03-02T09:00:00"));
+
+        let inbox: Vec<(i64,)> = sqlx::query_as("SELECT id FROM tasks WHERE id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL) ORDER BY position").fetch_all(&pool).await.unwrap();
+        assert_eq!(inbox, vec![(1,), (3,)]);
+        let carried: Vec<(String, String, bool, Option<String>)> = sqlx::query_as("SELECT start_time, status, is_urgent, planning_memo FROM time_blocks WHERE task_id = 2 ORDER BY start_time").fetch_all(&pool).await.unwrap();
+        assert_eq!(carried[1], ("2026-03-02T09:00:00".to_string(), "WILL".to_string(), true, Some("Finish the tests".to_string())));
+
+        // Later accesses on the same logical day do nothing.
+        assert!(run_rollover_at(&pool, 1, at("2026-03-02T18:00:00")).await.unwrap().is_none());
+
+        assert_eq!(get_rollover_reports(&pool, 1, true).await.unwrap().len(), 1);
+        acknowledge_rollover_report(&pool, report.id).await.unwrap();
+        assert!(get_rollover_reports(&pool, 1, true).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_worked_minutes_survive_a_rollover() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Paused'), (2, 1, 'Overran')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES
+            (10, 1, 1, 'Paused', '2026-03-01T20:00:00', '2026-03-01T21:00:00', 'NOW'),
+            (20, 2, 1, 'Overran', '2026-03-01T22:00:00', '2026-03-01T22:30:00', 'NOW')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at, ended_at) VALUES
+            (10, '2026-03-01T20:00:00', '2026-03-01T20:10:00'),
+            (10, '2026-03-01T20:15:00', '2026-03-01T20:25:00'),
+            (20, '2026-03-01T22:00:00', NULL)").execute(&pool).await.unwrap();
+
+        let report = run_rollover_at(&pool, 1, at("2026-03-02T09:00:00")).await.unwrap().unwrap();
+        let summary: Vec<(&str, &str, i64)> = report.items.iter().map(|i| (i.title.as_str(), i.action.as_str(), i.minutes)).collect();
+        assert_eq!(summary, vec![("Paused", "CARRY", 35), ("Overran", "COMPLETE", 30)]);
+
+        let kept: Vec<(i64, String, String)> = sqlx::query_as("SELECT id, end_time, status FROM time_blocks WHERE start_time < '2026-03-02' ORDER BY id").fetch_all(&pool).await.unwrap();
+        assert_eq!(kept, vec![(10, "2026-03-01T20:25:00".to_string(), "CONTINUED".to_string()), (20, "2026-03-01T22:30:00".to_string(), "DONE".to_string())]);
+        let (intervals,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM block_intervals WHERE ended_at IS NOT NULL").fetch_one(&pool).await.unwrap();
+        assert_eq!(intervals, 3);
+        let carried: Vec<(String, String)> = sqlx::query_as("SELECT start_time, end_time FROM time_blocks WHERE task_id = 1 AND status = 'WILL'").fetch_all(&pool).await.unwrap();
+        assert_eq!(carried, vec![("2026-03-02T09:00:00".to_string(), "2026-03-02T09:35:00".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_carry_never_lands_before_a_prerequisite() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Ship'), (2, 1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO task_dependencies (task_id, blocked_by_task_id) VALUES (1, 2)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES
+            (10, 1, 1, 'Ship', '2026-03-01T09:00:00', '2026-03-01T09:30:00', 'DONE'),
+            (11, 1, 1, 'Ship', '2026-03-01T10:00:00', '2026-03-01T10:30:00', 'WILL'),
+            (20, 2, 1, 'Test', '2026-03-03T10:00:00', '2026-03-03T11:00:00', 'WILL')").execute(&pool).await.unwrap();
+
+        // Carrying 'Ship' to today would put it before 'Test', planned for tomorrow
+        assert!(run_rollover_at(&pool, 1, at("2026-03-02T09:00:00")).await.unwrap().is_none());
+        let left: Vec<(i64, String)> = sqlx::query_as("SELECT id, start_time FROM time_blocks WHERE task_id = 1 ORDER BY id").fetch_all(&pool).await.unwrap();
+        assert_eq!(left, vec![(10, "2026-03-01T09:00:00".to_string()), (11, "2026-03-01T10:00:00".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_complete_policy_closes_leftovers_at_planned_end() {
+        let pool = setup_db().await;
+        sqlx::query("UPDATE workspaces SET rollover_policy = 'COMPLETE' WHERE id = 1").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Forgotten')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (10, 1, 1, 'Forgotten', '2026-03-01T20:00:00', '2026-03-01T21:00:00', 'NOW')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at) VALUES (10, '2026-03-01T20:00:00')").execute(&pool).await.unwrap();
+
+        let report = run_rollover_at(&pool, 1, at("2026-03-02T09:00:00")).await.unwrap().unwrap();
+        assert_eq!(report.items[0].action, "COMPLETE");
+
+        let block: (String, String) = sqlx::query_as("SELECT status, end_time FROM time_blocks WHERE id = 10").fetch_one(&pool).await.unwrap();
+        assert_eq!(block, ("DONE".to_string(), "2026-03-01T21:00:00".to_string()));
+        let ended_at: (Option<String>,) = sqlx::query_as("SELECT ended_at FROM block_intervals WHERE block_id = 10").fetch_one(&pool).await.unwrap();
+        assert_eq!(ended_at.0.as_deref(), Some("2026-03-01T21:00:00"));
+    }
+}
@@ -0,0 +1,62 @@
+use sqlx::SqlitePool;
+use crate::domain::{SearchHit, SearchQuery, SEARCH_KINDS, Result, AppError};
+use crate::database;
+use crate::services::timeline::{logical_day_bounds, parse_date};
+
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+const MAX_SEARCH_LIMIT: i64 = 200;
+
+/// Ranked full-text search over tasks, review memos and achievements.
+pub async fn search(pool: &SqlitePool, mut query: SearchQuery) -> Result<Vec<SearchHit>> {
+    let Some(match_expr) = to_match_expr(&query.text) else { return Ok(Vec::new()) };
+
+    if let Some(kinds) = query.kinds.as_mut() {
+        for kind in kinds.iter_mut() {
+            *kind = kind.trim().to_uppercase();
+            if !SEARCH_KINDS.contains(&kind.as_str()) {
+                return Err(AppError::InvalidInput(format!("Unknown search kind: {}", kind)));
+            }
+        }
+    }
+
+    let day_start_time = match database::user::get_user(pool).await? {
+        Some(user) => user.day_start_time,
+        None => "04:00".to_string(),
+    };
+    let start_bound = match query.start_date.as_deref() {
+        Some(date) => Some(logical_day_bounds(parse_date(date)?, &day_start_time)?.0),
+        None => None,
+    };
+    let end_bound = match query.end_date.as_deref() {
+        Some(date) => Some(logical_day_bounds(parse_date(date)?, &day_start_time)?.1),
+        None => None,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_SEARCH_LIMIT);
+
+    database::search::search(pool, &match_expr, &query, start_bound.as_deref(), end_bound.as_deref(), limit).await
+}
+
+/// Turns free text into an FTS5 expression: every word becomes a quoted prefix
+/// term and all of them must match. Quotes are dropped so user input can never
+/// produce FTS5 syntax errors.
+fn to_match_expr(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|word| word.replace('"', ""))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"*", word))
+        .collect();
+    if terms.is_empty() { None } else { Some(terms.join(" ")) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_match_expr_quotes_terms() {
+        assert_eq!(to_match_expr("  sqlite 회의 "), Some("\"sqlite\"* \"회의\"*".to_string()));
+        assert_eq!(to_match_expr("say \"NEAR(a b)\" -x"), Some("\"say\"* \"NEAR(a\"* \"b)\"* \"-x\"*".to_string()));
+        assert_eq!(to_match_expr(" \"\" "), None);
+    }
+}
@@ -0,0 +1,279 @@
+use sqlx::SqlitePool;
+use chrono::{Duration, Local, NaiveDateTime};
+use crate::domain::{Standup, StandupInput, StandupItem, TimeBlock, STANDUP_MODES, Result, AppError};
+use crate::database;
+use crate::services;
+use crate::services::timeline::{logical_date_of, logical_day_bounds, parse_date, parse_datetime};
+
+/// How far back "yesterday" may reach to skip days without finished work (e.g. weekends).
+pub const STANDUP_LOOKBACK_DAYS: i64 = 7;
+
+pub async fn generate_standup(pool: &SqlitePool, input: StandupInput) -> Result<Standup> {
+    generate_standup_at(pool, input, Local::now().naive_local()).await
+}
+
+/// Builds the standup of a logical day: finished work of the last earlier day that had some,
+/// the day's open plan, and tasks waiting on unfinished prerequisites.
+pub async fn generate_standup_at(pool: &SqlitePool, input: StandupInput, now_dt: NaiveDateTime) -> Result<Standup> {
+    let mode = input.mode.as_deref().map(|m| m.trim().to_uppercase()).unwrap_or_else(|| "TEMPLATE".to_string());
+    if !STANDUP_MODES.contains(&mode.as_str()) {
+        return Err(AppError::InvalidInput(format!("Unknown standup mode: {}", mode)));
+    }
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let day_start_time = user.day_start_time.clone();
+    let date = match input.logical_date.as_deref() {
+        Some(d) => parse_date(d)?,
+        None => logical_date_of(now_dt, &day_start_time),
+    };
+
+    let (start_of_day, _) = logical_day_bounds(date, &day_start_time)?;
+    let (lookback_start, _) = logical_day_bounds(date - Duration::days(STANDUP_LOOKBACK_DAYS), &day_start_time)?;
+    let previous_date = match database::standup::get_last_done_start(pool, input.workspace_id, &lookback_start, &start_of_day).await? {
+        Some(start) => logical_date_of(parse_datetime(&start)?, &day_start_time),
+        None => date - Duration::days(1),
+    };
+
+    let previous_blocks = services::timeline::get_timeline_at(pool, input.workspace_id, Some(previous_date.format("%Y-%m-%d").to_string()), None, now_dt).await?;
+    let yesterday = merge_blocks(previous_blocks.iter().filter(|b| b.status == "DONE"));
+    let today_blocks = services::timeline::get_timeline_at(pool, input.workspace_id, Some(date.format("%Y-%m-%d").to_string()), None, now_dt).await?;
+    let today = merge_blocks(today_blocks.iter().filter(|b| matches!(b.status.as_str(), "NOW" | "WILL" | "PENDING")));
+    let blocked = database::standup::get_blocked_tasks(pool, input.workspace_id).await?;
+
+    let is_ko = user.lang == "ko";
+    let template = build_markdown(&previous_date.format("%Y-%m-%d").to_string(), &yesterday, &today, &blocked, is_ko);
+    let (markdown, used_model) = if mode == "AI" {
+        let (text, model) = write_with_ai(pool, &template, &yesterday, is_ko, input.target_model, input.force_retry).await?;
+        (text, Some(model))
+    } else {
+        (template, None)
+    };
+
+    Ok(Standup {
+        logical_date: date.format("%Y-%m-%d").to_string(),
+        previous_date: previous_date.format("%Y-%m-%d").to_string(),
+        plain_text: to_slack_text(&markdown),
+        markdown,
+        yesterday,
+        today,
+        blocked,
+        used_model,
+    })
+}
+
+async fn write_with_ai(pool: &SqlitePool, template: &str, yesterday: &[StandupItem], is_ko: bool, target_model: Option<String>, force_retry: bool) -> Result<(String, String)> {
+    let system_prompt = format!(
+        "You write a team member's daily standup update from their task log.
+
+RULES:
+1. Keep the three sections and their bold headings exactly as given, in the same order.
+2. One short line per bullet, starting with \"- \". Merge closely related tasks, keep durations only where they add information.
+3. Use the review notes to say what came out of finished work. Never invent work, plans or blockers.
+4. No greeting, no closing remarks, no extra sections.
+
+CRITICAL RULE: Write the output entirely in [{}].",
+        if is_ko { "Korean" } else { "English" }
+    );
+    let mut user_content = format!("**Draft**:\n{}", template);
+    let notes: Vec<String> = yesterday.iter()
+        .filter_map(|item| item.note.as_ref().map(|n| format!("- {}: {}", item.title, n)))
+        .collect();
+    if !notes.is_empty() {
+        user_content.push_str(&format!("\n**Review Notes of Finished Work**:\n{}\n", notes.join("\n")));
+    }
+
+    let (text, model) = match target_model {
+        Some(model_name) => {
+            let text = services::gemini::execute_single_model(pool, &model_name, &system_prompt, &user_content).await?;
+            (text, model_name)
+        }
+        None => services::gemini::execute_with_fallback(pool, &system_prompt, &user_content, force_retry).await?,
+    };
+    database::user::save_last_model(pool, &model).await?;
+    Ok((text.trim().to_string(), model))
+}
+
+/// One item per task, in order of first appearance; status `NOW` wins over later parts and
+/// the review memos of all parts are kept, joined with "; ".
+fn merge_blocks<'a>(blocks: impl Iterator<Item = &'a TimeBlock>) -> Vec<StandupItem> {
+    let mut items: Vec<StandupItem> = Vec::new();
+    for block in blocks {
+        let Some(task_id) = block.task_id else { continue; };
+        let minutes = block_minutes(block);
+        match items.iter_mut().find(|i| i.task_id == Some(task_id)) {
+            Some(item) => {
+                item.minutes += minutes;
+                if block.status == "NOW" {
+                    item.status = block.status.clone();
+                }
+                if let Some(memo) = block.review_memo.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+                    match &mut item.note {
+                        Some(note) if note.contains(memo) => {}
+                        Some(note) => note.push_str(&format!("; {}", memo)),
+                        None => item.note = Some(memo.to_string()),
+                    }
+                }
+            }
+            None => items.push(StandupItem {
+                task_id: Some(task_id),
+                title: block.title.clone(),
+                status: block.status.clone(),
+                minutes,
+                project_name: block.project_name.clone(),
+                note: block.review_memo.as_deref().map(str::trim).filter(|m| !m.is_empty()).map(str::to_string),
+            }),
+        }
+    }
+    items
+}
+
+/// Worked minutes for DONE blocks, planned minutes otherwise.
+fn block_minutes(block: &TimeBlock) -> i64 {
+    let (Ok(start), Ok(end)) = (parse_datetime(&block.start_time), parse_datetime(&block.end_time)) else { return 0; };
+    let planned = (end - start).num_minutes();
+    if block.status == "DONE" { (planned - block.paused_minutes).max(0) } else { planned }
+}
+
+fn build_markdown(previous_date: &str, yesterday: &[StandupItem], today: &[StandupItem], blocked: &[StandupItem], is_ko: bool) -> String {
+    let (yesterday_title, today_title, blocked_title) = if is_ko { ("어제", "오늘", "블로커") } else { ("Yesterday", "Today", "Blockers") };
+    let mut text = format!("**{} ({})**\n", yesterday_title, previous_date);
+    push_items(&mut text, yesterday, if is_ko { "기록된 작업 없음" } else { "Nothing recorded" }, is_ko);
+    text.push_str(&format!("\n**{}**\n", today_title));
+    push_items(&mut text, today, if is_ko { "계획된 작업 없음" } else { "Nothing planned" }, is_ko);
+    text.push_str(&format!("\n**{}**\n", blocked_title));
+    push_items(&mut text, blocked, if is_ko { "없음" } else { "None" }, is_ko);
+    text
+}
+
+fn push_items(text: &mut String, items: &[StandupItem], empty: &str, is_ko: bool) {
+    if items.is_empty() {
+        text.push_str(&format!("- {}\n", empty));
+        return;
+    }
+    for item in items {
+        let mut line = match &item.project_name {
+            Some(project) => format!("- [{}] {}", project, item.title),
+            None => format!("- {}", item.title),
+        };
+        if item.minutes > 0 {
+            line.push_str(&format!(" ({})", format_minutes(item.minutes, is_ko)));
+        }
+        if item.status == "NOW" {
+            line.push_str(if is_ko { " — 진행 중" } else { " — in progress" });
+        }
+        if item.status == "BLOCKED" {
+            if let Some(waiting_on) = &item.note {
+                line.push_str(&format!("{}{}", if is_ko { " — 대기: " } else { " — waiting on " }, waiting_on));
+            }
+        }
+        text.push_str(&line);
+        text.push('\n');
+    }
+}
+
+fn format_minutes(minutes: i64, is_ko: bool) -> String {
+    let (h, m) = (minutes / 60, minutes % 60);
+    match (h, m, is_ko) {
+        (0, m, true) => format!("{}분", m),
+        (h, 0, true) => format!("{}시간", h),
+        (h, m, true) => format!("{}시간 {}분", h, m),
+        (0, m, false) => format!("{}m", m),
+        (h, 0, false) => format!("{}h", h),
+        (h, m, false) => format!("{}h {}m", h, m),
+    }
+}
+
+/// Markdown to Slack's mrkdwn: headings and `**bold**` become `*bold*`, list markers become bullets.
+fn to_slack_text(markdown: &str) -> String {
+    markdown
+        .lines()
+        .map(|line| {
+            let indent = line.len() - line.trim_start().len();
+            let trimmed = line.trim_start();
+            let converted = if let Some(heading) = trimmed.strip_prefix('#').map(|h| h.trim_start_matches('#').trim()) {
+                format!("*{}*", heading.replace("**", ""))
+            } else if let Some(rest) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+                format!("{} {}", if indent > 0 { "◦" } else { "•" }, rest.replace("**", "*"))
+            } else {
+                trimmed.replace("**", "*")
+            };
+            format!("{}{}", " ".repeat(indent), converted)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> SqlitePool {
+        let pool = database::test_pool().await;
+        sqlx::query("INSERT INTO users (id, nickname) VALUES (1, 'TestUser')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO projects (id, workspace_id, name, last_used) VALUES (1, 1, 'Billing', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        pool
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_template_standup_skips_days_without_work() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, project_id, estimated_minutes) VALUES
+            (1, 1, 'Fix invoice rounding', 1, 0), (2, 1, 'Write migration', NULL, 0), (3, 1, 'Deploy', NULL, 30), (4, 1, 'Old work', NULL, 0)").execute(&pool).await.unwrap();
+        // Friday's work; the weekend is empty
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status, review_memo) VALUES
+            (1, 1, 1, 'Fix invoice rounding', '2026-03-06T09:00:00', '2026-03-06T10:00:00', 'DONE', 'Found the rounding bug'),
+            (2, 1, 1, 'Fix invoice rounding', '2026-03-06T13:00:00', '2026-03-06T13:30:00', 'DONE', 'Rounded per line item'),
+            (3, 4, 1, 'Old work', '2026-02-20T09:00:00', '2026-02-20T10:00:00', 'DONE', NULL),
+            (4, 2, 1, 'Write migration', '2026-03-09T09:00:00', '2026-03-09T10:30:00', 'NOW', NULL),
+            (5, 2, 1, 'Write migration', '2026-03-09T11:00:00', '2026-03-09T11:30:00', 'WILL', NULL)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO block_intervals (block_id, started_at, ended_at) VALUES (1, '2026-03-06T09:00:00', '2026-03-06T09:40:00')").execute(&pool).await.unwrap();
+        // Deploy waits for the migration, which is not finished
+        sqlx::query("INSERT INTO task_dependencies (task_id, blocked_by_task_id) VALUES (3, 2)").execute(&pool).await.unwrap();
+
+        let standup = generate_standup_at(&pool, StandupInput { workspace_id: 1, ..Default::default() }, at("2026-03-09T09:15:00")).await.unwrap();
+        assert_eq!((standup.logical_date.as_str(), standup.previous_date.as_str()), ("2026-03-09", "2026-03-06"));
+        assert_eq!((standup.yesterday.len(), standup.yesterday[0].minutes, standup.yesterday[0].note.as_deref()), (1, 70, Some("Found the rounding bug; Rounded per line item")));
+        assert_eq!((standup.today.len(), standup.today[0].status.as_str(), standup.today[0].minutes), (1, "NOW", 120));
+        assert_eq!(standup.used_model, None);
+        assert_eq!(standup.markdown, "**Yesterday (2026-03-06)**
+- [Billing] Fix invoice rounding (1h 10m)
+
+**Today**
+- Write migration (2h) — in progress
+
+**Blockers**
+- Deploy (30m) — waiting on Write migration
+");
+        assert_eq!(standup.plain_text, "*Yesterday (2026-03-06)*
+• [Billing] Fix invoice rounding (1h 10m)
+
+*Today*
+• Write migration (2h) — in progress
+
+*Blockers*
+• Deploy (30m) — waiting on Write migration");
+
+        // Once the migration is done nothing is blocked any more
+        sqlx::query("UPDATE time_blocks SET status = 'DONE' WHERE task_id = 2").execute(&pool).await.unwrap();
+        sqlx::query("UPDATE users SET lang = 'ko'").execute(&pool).await.unwrap();
+        let standup = generate_standup_at(&pool, StandupInput { workspace_id: 1, logical_date: Some("2026-03-10".to_string()), ..Default::default() }, at("2026-03-09T12:00:00")).await.unwrap();
+        assert_eq!(standup.previous_date, "2026-03-09");
+        assert!(standup.blocked.is_empty());
+        assert!(standup.markdown.ends_with("**오늘**\n- 계획된 작업 없음\n\n**블로커**\n- 없음\n"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_mode_and_bad_dates() {
+        let pool = setup_db().await;
+        let now = at("2026-03-09T09:00:00");
+        assert!(matches!(generate_standup_at(&pool, StandupInput { workspace_id: 1, mode: Some("poem".to_string()), ..Default::default() }, now).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(generate_standup_at(&pool, StandupInput { workspace_id: 1, logical_date: Some("09.03.2026".to_string()), ..Default::default() }, now).await, Err(AppError::DateParse(_))));
+        // Without earlier work "yesterday" is simply the previous day
+        let standup = generate_standup_at(&pool, StandupInput { workspace_id: 1, mode: Some("template".to_string()), ..Default::default() }, now).await.unwrap();
+        assert_eq!(standup.previous_date, "2026-03-08");
+    }
+}
@@ -0,0 +1,32 @@
+use sqlx::SqlitePool;
+use chrono::NaiveDate;
+use crate::domain::{Tag, TagStat, Result, AppError};
+use crate::database;
+use crate::services::timeline::logical_day_bounds;
+
+pub async fn get_tags(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<Tag>> {
+    database::tag::get_tags(pool, workspace_id).await
+}
+
+/// Worked minutes per tag over the logical days `start_date..=end_date`. A task with
+/// several tags counts towards each of them.
+pub async fn get_tag_stats(pool: &SqlitePool, workspace_id: i64, start_date: &str, end_date: &str) -> Result<Vec<TagStat>> {
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d").map_err(|e| AppError::DateParse(e.to_string()))?;
+    let end = NaiveDate::parse_from_str(end_date, "%Y-%m-%d").map_err(|e| AppError::DateParse(e.to_string()))?;
+    let (start_of_range, _) = logical_day_bounds(start, &user.day_start_time)?;
+    let (_, end_of_range) = logical_day_bounds(end, &user.day_start_time)?;
+    database::tag::get_tag_stats(pool, workspace_id, &start_of_range, &end_of_range).await
+}
+
+/// Trims names, drops empty ones and a leading `#`/`+`, and removes duplicates while keeping order.
+pub fn normalize_tags(names: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for name in names {
+        let name = name.trim().trim_start_matches(['#', '+']).trim();
+        if !name.is_empty() && !tags.iter().any(|t| t == name) {
+            tags.push(name.to_string());
+        }
+    }
+    tags
+}
@@ -1,33 +1,245 @@
 use sqlx::{SqlitePool, Sqlite, Transaction};
 use chrono::{NaiveDateTime, NaiveDate, Local, NaiveTime, Duration, Timelike};
-use crate::domain::{Task, TimeBlock, AddTaskInput, TaskTransitionInput, UpdateTaskInput, Result, AppError, UnpluggedTime};
+use crate::domain::{Task, TimeBlock, AddTaskInput, TaskTransitionInput, UpdateTaskInput, Result, AppError, UnpluggedTime, Interruption, BlockInterval, DayPlanPreview, PlannedTask, DayTotals, TimelineDay, DEFAULT_TASK_PRIORITY};
+use std::collections::HashSet;
 use crate::database;
+use crate::services;
 
 pub async fn get_today_completed_duration(pool: &SqlitePool, workspace_id: i64) -> Result<i64> {
     let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
     database::timeline::get_today_completed_duration(pool, workspace_id, &user.day_start_time).await
 }
 
-pub async fn get_timeline(pool: &SqlitePool, workspace_id: i64, date: Option<String>) -> Result<Vec<TimeBlock>> {
+/// `tag` narrows the task blocks to one tag; unplugged times are always kept.
+pub async fn get_timeline(pool: &SqlitePool, workspace_id: i64, date: Option<String>, tag: Option<String>) -> Result<Vec<TimeBlock>> {
+    get_timeline_at(pool, workspace_id, date, tag, Local::now().naive_local()).await
+}
+
+/// `get_timeline` as seen at `now_dt`, which picks the default day and measures the NOW block.
+pub async fn get_timeline_at(pool: &SqlitePool, workspace_id: i64, date: Option<String>, tag: Option<String>, now_dt: NaiveDateTime) -> Result<Vec<TimeBlock>> {
     let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
     let day_start_time = user.day_start_time;
     
-    let target_date = if let Some(d) = date {
-        NaiveDate::parse_from_str(&d, "%Y-%m-%d").map_err(|e| AppError::DateParse(e.to_string()))?
-    } else {
-        let now = Local::now();
-        let current_time = now.format("%H:%M").to_string();
-        if current_time < day_start_time {
-            now.date_naive() - Duration::days(1)
-        } else {
-            now.date_naive()
+    let target_date = match date {
+        Some(d) => parse_date(&d)?,
+        None => logical_date_of(now_dt, &day_start_time),
+    };
+    let mut blocks = database::timeline::get_timeline(pool, workspace_id, target_date, &day_start_time).await?;
+
+    let (start_of_day, end_of_day) = logical_day_bounds(target_date, &day_start_time)?;
+    let intervals = database::timeline::get_block_intervals_in_range(pool, workspace_id, &start_of_day, &end_of_day).await?;
+    apply_interval_stats(&mut blocks, &intervals, now_dt);
+
+    let task_ids: Vec<i64> = blocks.iter().filter_map(|b| b.task_id).collect();
+    let tag_map = database::tag::get_task_tag_map(pool, &task_ids).await?;
+    for block in blocks.iter_mut() {
+        block.tags = block.task_id.and_then(|id| tag_map.get(&id)).cloned().unwrap_or_default();
+    }
+    if let Some(tag) = tag.filter(|t| !t.trim().is_empty()) {
+        blocks.retain(|b| b.status == "UNPLUGGED" || b.tags.iter().any(|t| t == tag.trim()));
+    }
+    Ok(blocks)
+}
+
+/// Longest window `get_timeline_range` serves in one call (a month view plus margins).
+pub const MAX_TIMELINE_RANGE_DAYS: i64 = 62;
+
+/// Blocks of the logical days `start_date..=end_date`, grouped per day with totals.
+/// Every day of the range is present, and carries its expanded unplugged times even
+/// without task blocks. `tag` filters like in `get_timeline`.
+pub async fn get_timeline_range(pool: &SqlitePool, workspace_id: i64, start_date: &str, end_date: &str, tag: Option<String>) -> Result<Vec<TimelineDay>> {
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let day_start_time = user.day_start_time;
+    let range = logical_range(start_date, end_date, MAX_TIMELINE_RANGE_DAYS, &day_start_time)?;
+
+    let mut blocks = database::timeline::get_blocks_in_range(pool, workspace_id, &range.start_bound, &range.end_bound).await?;
+    let intervals = database::timeline::get_block_intervals_in_range(pool, workspace_id, &range.start_bound, &range.end_bound).await?;
+    apply_interval_stats(&mut blocks, &intervals, Local::now().naive_local());
+
+    let task_ids: Vec<i64> = blocks.iter().filter_map(|b| b.task_id).collect();
+    let tag_map = database::tag::get_task_tag_map(pool, &task_ids).await?;
+    for block in blocks.iter_mut() {
+        block.tags = block.task_id.and_then(|id| tag_map.get(&id)).cloned().unwrap_or_default();
+    }
+    if let Some(tag) = tag.filter(|t| !t.trim().is_empty()) {
+        blocks.retain(|b| b.tags.iter().any(|t| t == tag.trim()));
+    }
+
+    let unplugged = database::workspace::get_unplugged_times(pool, workspace_id).await?;
+    let mut days: Vec<TimelineDay> = range
+        .days()
+        .map(|date| TimelineDay {
+            logical_date: date.format("%Y-%m-%d").to_string(),
+            blocks: database::timeline::unplugged_blocks(workspace_id, &unplugged, date, &day_start_time),
+            totals: DayTotals::default(),
+        })
+        .collect();
+    for block in blocks {
+        let Ok(block_start) = parse_datetime(&block.start_time) else { continue; };
+        if let Some(day) = range.day_index(block_start).and_then(|i| days.get_mut(i)) {
+            day.blocks.push(block);
+        }
+    }
+    for day in days.iter_mut() {
+        day.blocks.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+        day.totals = day_totals(&day.blocks);
+    }
+    Ok(days)
+}
+
+pub fn day_totals(blocks: &[TimeBlock]) -> DayTotals {
+    let mut totals = DayTotals::default();
+    for block in blocks {
+        let (Ok(start), Ok(end)) = (
+            NaiveDateTime::parse_from_str(&block.start_time, "%Y-%m-%dT%H:%M:%S"),
+            NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S"),
+        ) else { continue; };
+        let minutes = ((end - start).num_minutes() - block.paused_minutes).max(0);
+        *totals.by_status.entry(block.status.clone()).or_insert(0) += minutes;
+        if let Some(project) = &block.project_name {
+            *totals.by_project.entry(project.clone()).or_insert(0) += minutes;
         }
+        if let Some(label) = &block.label_name {
+            *totals.by_label.entry(label.clone()).or_insert(0) += minutes;
+        }
+        totals.overrun_minutes += block.overrun_minutes;
+    }
+    totals
+}
+
+/// Parses a "YYYY-MM-DD" date.
+pub fn parse_date(date: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| AppError::DateParse(e.to_string()))
+}
+
+/// Parses a stored "YYYY-MM-DDTHH:MM:SS" timestamp.
+pub fn parse_datetime(s: &str) -> Result<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").map_err(|e| AppError::DateParse(e.to_string()))
+}
+
+/// The inclusive logical days `start..=end` with the timestamps bounding them.
+#[derive(Debug, Clone)]
+pub struct LogicalRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// First minute of `start`, formatted like `time_blocks.start_time`.
+    pub start_bound: String,
+    /// Last minute of `end`.
+    pub end_bound: String,
+    day_start_time: String,
+}
+
+impl LogicalRange {
+    /// Every logical date of the range, in order.
+    pub fn days(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.start.iter_days().take_while(move |d| *d <= self.end)
+    }
+
+    /// Position in `days()` of the logical day `dt` belongs to, or `None` outside the range.
+    pub fn day_index(&self, dt: NaiveDateTime) -> Option<usize> {
+        let date = logical_date_of(dt, &self.day_start_time);
+        if date > self.end {
+            return None;
+        }
+        usize::try_from((date - self.start).num_days()).ok()
+    }
+}
+
+/// Parses and checks the logical dates `start_date..=end_date` of a range spanning at most `max_days`.
+pub fn logical_range(start_date: &str, end_date: &str, max_days: i64, day_start_time: &str) -> Result<LogicalRange> {
+    let start = parse_date(start_date)?;
+    let end = parse_date(end_date)?;
+    if end < start {
+        return Err(AppError::InvalidInput("The end date must not be before the start date.".to_string()));
+    }
+    if (end - start).num_days() + 1 > max_days {
+        return Err(AppError::InvalidInput(format!("The range may span at most {} days.", max_days)));
+    }
+    let (start_bound, _) = logical_day_bounds(start, day_start_time)?;
+    let (_, end_bound) = logical_day_bounds(end, day_start_time)?;
+    Ok(LogicalRange { start, end, start_bound, end_bound, day_start_time: day_start_time.to_string() })
+}
+
+/// Logical date a moment belongs to: before `day_start_time` it still counts as the previous day.
+pub fn logical_date_of(dt: NaiveDateTime, day_start_time: &str) -> NaiveDate {
+    if dt.format("%H:%M").to_string().as_str() < day_start_time {
+        dt.date() - Duration::days(1)
+    } else {
+        dt.date()
+    }
+}
+
+/// First and last minute of a logical date, formatted for comparison against `time_blocks.start_time`.
+pub fn logical_day_bounds(date: NaiveDate, day_start_time: &str) -> Result<(String, String)> {
+    let start_of_day = NaiveDateTime::parse_from_str(&format!("{}T{}", date.format("%Y-%m-%d"), day_start_time), "%Y-%m-%dT%H:%M")
+        .map_err(|e| AppError::DateParse(e.to_string()))?;
+    let end_of_day = start_of_day + Duration::days(1) - Duration::seconds(1);
+    Ok((start_of_day.format("%Y-%m-%dT%H:%M:00").to_string(), end_of_day.format("%Y-%m-%dT%H:%M:00").to_string()))
+}
+
+/// Fills `paused_minutes` / `is_paused` from the recorded work intervals.
+/// A NOW block is measured up to `now_dt`, every other block up to its end time.
+pub fn apply_interval_stats(blocks: &mut [TimeBlock], intervals: &[BlockInterval], now_dt: NaiveDateTime) {
+    for block in blocks.iter_mut() {
+        let own: Vec<&BlockInterval> = intervals.iter().filter(|i| i.block_id == block.id).collect();
+        let Some(last) = own.last() else { continue; };
+        let (Ok(start), Ok(end)) = (
+            NaiveDateTime::parse_from_str(&block.start_time, "%Y-%m-%dT%H:%M:%S"),
+            NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S"),
+        ) else { continue; };
+
+        let reference_end = if block.status == "NOW" { now_dt } else { end };
+        let worked: i64 = own.iter().map(|i| {
+            let i_start = NaiveDateTime::parse_from_str(&i.started_at, "%Y-%m-%dT%H:%M:%S").unwrap_or(reference_end);
+            let i_end = i.ended_at.as_ref()
+                .and_then(|e| NaiveDateTime::parse_from_str(e, "%Y-%m-%dT%H:%M:%S").ok())
+                .map_or(reference_end, |e| e.min(reference_end));
+            (i_end - i_start).num_minutes().max(0)
+        }).sum();
+
+        block.paused_minutes = ((reference_end - start).num_minutes() - worked).max(0);
+        block.is_paused = block.status == "NOW" && last.ended_at.is_some();
+    }
+}
+
+/// `sort_by`: "due" (default), "priority" or "manual". `tag` keeps only tasks carrying it.
+pub async fn get_inbox(pool: &SqlitePool, workspace_id: i64, sort_by: Option<String>, tag: Option<String>) -> Result<Vec<Task>> {
+    get_inbox_at(pool, workspace_id, sort_by, tag, Local::now().naive_local()).await
+}
+
+/// `get_inbox` as seen at `now_dt`; an inbox task is at risk once its due date has passed.
+pub async fn get_inbox_at(pool: &SqlitePool, workspace_id: i64, sort_by: Option<String>, tag: Option<String>, now_dt: NaiveDateTime) -> Result<Vec<Task>> {
+    let order_sql = match sort_by.as_deref().unwrap_or("due") {
+        "due" => database::timeline::INBOX_ORDER_SQL,
+        "priority" => database::timeline::INBOX_PRIORITY_ORDER_SQL,
+        "manual" => database::timeline::INBOX_MANUAL_ORDER_SQL,
+        other => return Err(AppError::InvalidInput(format!("Unknown inbox sort '{}'", other))),
     };
-    database::timeline::get_timeline(pool, workspace_id, target_date, &day_start_time).await
+    let mut tasks = database::timeline::get_inbox(pool, workspace_id, order_sql).await?;
+    let task_ids: Vec<i64> = tasks.iter().map(|t| t.id).collect();
+    let tag_map = database::tag::get_task_tag_map(pool, &task_ids).await?;
+    let now_str = now_dt.format("%Y-%m-%dT%H:%M:00").to_string();
+    for task in tasks.iter_mut() {
+        task.tags = tag_map.get(&task.id).cloned().unwrap_or_default();
+        task.is_at_risk = task.due_at.as_deref().is_some_and(|due| due < now_str.as_str());
+    }
+    if let Some(tag) = tag.filter(|t| !t.trim().is_empty()) {
+        tasks.retain(|t| t.tags.iter().any(|name| name == tag.trim()));
+    }
+    Ok(tasks)
 }
 
-pub async fn get_inbox(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<Task>> {
-    database::timeline::get_inbox(pool, workspace_id).await
+pub async fn get_at_risk_tasks(pool: &SqlitePool, workspace_id: i64) -> Result<Vec<Task>> {
+    get_at_risk_tasks_at(pool, workspace_id, Local::now().naive_local()).await
+}
+
+/// `get_at_risk_tasks` as seen at `now_dt`, so overdue tasks are listed even when no mutation refreshed the flag.
+pub async fn get_at_risk_tasks_at(pool: &SqlitePool, workspace_id: i64, now_dt: NaiveDateTime) -> Result<Vec<Task>> {
+    let mut tasks = database::timeline::get_at_risk_tasks(pool, workspace_id, &now_dt.format("%Y-%m-%dT%H:%M:00").to_string()).await?;
+    for task in tasks.iter_mut() {
+        task.is_at_risk = true;
+    }
+    Ok(tasks)
 }
 
 pub async fn add_task(pool: &SqlitePool, input: AddTaskInput) -> Result<()> {
@@ -36,35 +248,22 @@ pub async fn add_task(pool: &SqlitePool, input: AddTaskInput) -> Result<()> {
 }
 
 pub async fn add_task_at(pool: &SqlitePool, input: AddTaskInput, now_dt: NaiveDateTime) -> Result<()> {
+    let due_at = normalize_due_at(input.due_at.as_deref())?;
+    let priority = validate_priority(input.priority)?.unwrap_or(DEFAULT_TASK_PRIORITY);
+    let target_date = parse_target_date(input.target_date.as_deref())?;
     let mut tx = pool.begin().await?;
 
-    let mut project_id = None;
-    if let Some(p_name) = &input.project_name {
-        if !p_name.trim().is_empty() {
-            let existing: Option<(i64,)> = sqlx::query_as("SELECT id FROM projects WHERE name = ?1").bind(p_name).fetch_optional(&mut *tx).await?;
-            if let Some((id,)) = existing {
-                project_id = Some(id);
-                sqlx::query("UPDATE projects SET last_used = ?1 WHERE id = ?2").bind(Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).bind(id).execute(&mut *tx).await?;
-            } else {
-                let res = sqlx::query("INSERT INTO projects (name, last_used) VALUES (?1, ?2)").bind(p_name).bind(Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).execute(&mut *tx).await?;
-                project_id = Some(res.last_insert_rowid());
+    if input.is_urgent && !input.is_inbox.unwrap_or(false) {
+        if let Some(date) = target_date {
+            let day_start_time = day_start_time_in(&mut tx).await?;
+            if date != logical_date_of(now_dt, &day_start_time) {
+                return Err(AppError::InvalidInput("An urgent task starts now and cannot be planned for another day.".to_string()));
             }
         }
     }
 
-    let mut label_id = None;
-    if let Some(l_name) = &input.label_name {
-        if !l_name.trim().is_empty() {
-            let existing: Option<(i64,)> = sqlx::query_as("SELECT id FROM labels WHERE name = ?1").bind(l_name).fetch_optional(&mut *tx).await?;
-            if let Some((id,)) = existing {
-                label_id = Some(id);
-                sqlx::query("UPDATE labels SET last_used = ?1 WHERE id = ?2").bind(Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).bind(id).execute(&mut *tx).await?;
-            } else {
-                let res = sqlx::query("INSERT INTO labels (name, color, last_used) VALUES (?1, '#808080', ?2)").bind(l_name).bind(Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).execute(&mut *tx).await?;
-                label_id = Some(res.last_insert_rowid());
-            }
-        }
-    }
+    let project_id = resolve_project_id(&mut tx, input.workspace_id, input.project_name.as_deref()).await?;
+    let label_id = resolve_label_id(&mut tx, input.workspace_id, input.label_name.as_deref()).await?;
 
     let position = if input.is_inbox.unwrap_or(false) {
         let max_pos: (Option<i64>,) = sqlx::query_as("SELECT MAX(position) FROM tasks WHERE workspace_id = ?1 AND id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL)")
@@ -77,7 +276,7 @@ pub async fn add_task_at(pool: &SqlitePool, input: AddTaskInput, now_dt: NaiveDa
     };
 
     let task_result = sqlx::query(
-        "INSERT INTO tasks (workspace_id, title, planning_memo, estimated_minutes, project_id, label_id, position) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        "INSERT INTO tasks (workspace_id, title, planning_memo, estimated_minutes, project_id, label_id, position, due_at, priority) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
     )
     .bind(input.workspace_id)
     .bind(&input.title)
@@ -86,10 +285,15 @@ pub async fn add_task_at(pool: &SqlitePool, input: AddTaskInput, now_dt: NaiveDa
     .bind(project_id)
     .bind(label_id)
     .bind(position)
+    .bind(&due_at)
+    .bind(priority)
     .execute(&mut *tx)
     .await?;
 
     let task_id = task_result.last_insert_rowid();
+    if let Some(tags) = &input.tags {
+        database::tag::set_task_tags(&mut tx, input.workspace_id, task_id, &services::tag::normalize_tags(tags)).await?;
+    }
 
     if input.is_inbox.unwrap_or(false) {
         tx.commit().await?;
@@ -101,20 +305,12 @@ pub async fn add_task_at(pool: &SqlitePool, input: AddTaskInput, now_dt: NaiveDa
     let current_start = if input.is_urgent {
         now_dt
     } else {
-        let last_block: Option<(String,)> = sqlx::query_as("SELECT end_time FROM time_blocks WHERE workspace_id = ?1 AND status != 'UNPLUGGED' ORDER BY end_time DESC LIMIT 1")
-            .bind(input.workspace_id)
-            .fetch_optional(&mut *tx)
-            .await?;
-
-        if let Some((last_end,)) = last_block {
-            let le = NaiveDateTime::parse_from_str(&last_end, "%Y-%m-%dT%H:%M:%S").unwrap_or(now_dt);
-            if le < now_dt { now_dt } else { le }
-        } else {
-            now_dt
-        }
+        append_start(&mut tx, input.workspace_id, target_date, now_dt).await?
     };
 
     if input.is_urgent {
+        take_plan_snapshot_if_needed(&mut tx, input.workspace_id, now_dt).await?;
+
         let current_now: Option<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status = 'NOW' LIMIT 1")
             .bind(input.workspace_id)
             .fetch_optional(&mut *tx)
@@ -128,6 +324,11 @@ pub async fn add_task_at(pool: &SqlitePool, input: AddTaskInput, now_dt: NaiveDa
                 .bind(block.id)
                 .execute(&mut *tx)
                 .await?;
+            sqlx::query("UPDATE block_intervals SET ended_at = ?1 WHERE block_id = ?2 AND ended_at IS NULL")
+                .bind(now_dt.format("%Y-%m-%dT%H:%M:00").to_string())
+                .bind(block.id)
+                .execute(&mut *tx)
+                .await?;
 
             let original_end = NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S").unwrap();
             let remaining_duration = (original_end - now_dt).num_minutes();
@@ -139,19 +340,65 @@ pub async fn add_task_at(pool: &SqlitePool, input: AddTaskInput, now_dt: NaiveDa
 
             if remaining_duration > 0 {
                 schedule_task_blocks(&mut tx, input.workspace_id, block.task_id.unwrap(), &block.title, urgent_end, remaining_duration, block.is_urgent, "PENDING").await?;
+                ensure_dependency_order(&mut tx, block.task_id.unwrap()).await?;
             }
+
+            database::interruption::record_interruption(&mut tx, &Interruption {
+                id: 0,
+                workspace_id: input.workspace_id,
+                interrupting_task_id: Some(task_id),
+                interrupting_title: input.title.clone(),
+                interrupted_task_id: block.task_id,
+                interrupted_title: block.title.clone(),
+                occurred_at: now_dt.format("%Y-%m-%dT%H:%M:00").to_string(),
+                lost_minutes: urgent_duration,
+                project_name: None,
+                label_name: None,
+            }).await?;
         } else {
             shift_future_blocks(&mut tx, input.workspace_id, now_dt, urgent_duration).await?;
             schedule_task_blocks(&mut tx, input.workspace_id, task_id, &input.title, now_dt, urgent_duration, true, "NOW").await?;
         }
     } else {
         schedule_task_blocks(&mut tx, input.workspace_id, task_id, &input.title, current_start, duration, false, "WILL").await?;
+        place_by_priority(&mut tx, input.workspace_id, task_id, priority, current_start).await?;
     }
 
     tx.commit().await?;
     Ok(())
 }
 
+/// Moves a freshly appended task in front of the first WILL block of a lower-priority task
+/// planned on the same logical day as `start_dt`.
+async fn place_by_priority(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, task_id: i64, priority: i64, start_dt: NaiveDateTime) -> Result<()> {
+    let (start_of_day, end_of_day) = day_bounds_in(tx, start_dt).await?;
+    let blocks: Vec<(i64, Option<i64>, String, Option<i64>)> = sqlx::query_as(
+        "SELECT tb.id, tb.task_id, tb.status, t.priority FROM time_blocks tb LEFT JOIN tasks t ON tb.task_id = t.id
+         WHERE tb.workspace_id = ?1 AND tb.status NOT IN ('DONE', 'BREAK') AND tb.start_time >= ?2 AND tb.start_time <= ?3 ORDER BY tb.start_time ASC"
+    )
+    .bind(workspace_id)
+    .bind(&start_of_day)
+    .bind(&end_of_day)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let Some(insert_at) = blocks.iter().position(|(_, tid, status, p)| {
+        status == "WILL" && *tid != Some(task_id) && p.unwrap_or(DEFAULT_TASK_PRIORITY) > priority
+    }) else { return Ok(()); };
+
+    let own: Vec<i64> = blocks.iter().filter(|(_, tid, _, _)| *tid == Some(task_id)).map(|(id, _, _, _)| *id).collect();
+    let mut ids: Vec<i64> = blocks.iter().filter(|(_, tid, _, _)| *tid != Some(task_id)).map(|(id, _, _, _)| *id).collect();
+    ids.splice(insert_at..insert_at, own);
+    reorder_internal(tx, workspace_id, ids).await
+}
+
+fn validate_priority(priority: Option<i64>) -> Result<Option<i64>> {
+    match priority {
+        Some(p) if !(0..=3).contains(&p) => Err(AppError::InvalidInput("Priority must be between 0 (P0) and 3 (P3).".to_string())),
+        _ => Ok(priority),
+    }
+}
+
 pub async fn move_to_inbox(pool: &SqlitePool, block_id: i64) -> Result<()> {
     let mut tx = pool.begin().await?;
     
@@ -176,13 +423,16 @@ pub async fn move_to_inbox(pool: &SqlitePool, block_id: i64) -> Result<()> {
                 shift_future_blocks(&mut tx, workspace_id, end, -duration).await?;
             }
             sqlx::query("DELETE FROM time_blocks WHERE task_id = ?1").bind(task_id).execute(&mut *tx).await?;
+            refresh_deadline_risk(&mut tx, workspace_id).await?;
         }
     }
     tx.commit().await?;
     Ok(())
 }
 
-pub async fn move_to_timeline(pool: &SqlitePool, task_id: i64, workspace_id: i64) -> Result<()> {
+/// Appends an inbox task to the plan of `target_date` ("YYYY-MM-DD"), today when omitted.
+pub async fn move_to_timeline(pool: &SqlitePool, task_id: i64, workspace_id: i64, target_date: Option<String>) -> Result<()> {
+    let target_date = parse_target_date(target_date.as_deref())?;
     let mut tx = pool.begin().await?;
 
     let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = ?1")
@@ -190,56 +440,250 @@ pub async fn move_to_timeline(pool: &SqlitePool, task_id: i64, workspace_id: i64
         .fetch_one(&mut *tx)
         .await?;
 
-    let last_block: Option<(String,)> = sqlx::query_as("SELECT end_time FROM time_blocks WHERE workspace_id = ?1 AND status != 'UNPLUGGED' ORDER BY end_time DESC LIMIT 1")
-        .bind(workspace_id)
-        .fetch_optional(&mut *tx)
-        .await?;
-
-    let now_dt = Local::now().naive_local();
-    let current_start = if let Some((last_end,)) = last_block {
-        let le = NaiveDateTime::parse_from_str(&last_end, "%Y-%m-%dT%H:%M:%S").unwrap_or(now_dt);
-        if le < now_dt { now_dt } else { le }
-    } else {
-        now_dt
-    };
+    let current_start = append_start(&mut tx, workspace_id, target_date, Local::now().naive_local()).await?;
 
     let duration = if task.estimated_minutes > 0 { task.estimated_minutes as i64 } else { 30 };
     schedule_task_blocks(&mut tx, workspace_id, task_id, &task.title, current_start, duration, false, "WILL").await?;
+    ensure_dependency_order(&mut tx, task_id).await?;
 
     tx.commit().await?;
     Ok(())
 }
 
-pub async fn move_all_to_timeline(pool: &SqlitePool, workspace_id: i64) -> Result<()> {
+pub async fn move_all_to_timeline(pool: &SqlitePool, workspace_id: i64, target_date: Option<String>) -> Result<()> {
+    let target_date = parse_target_date(target_date.as_deref())?;
     let mut tx = pool.begin().await?;
-    let tasks = sqlx::query_as::<_, Task>(
-        "SELECT * FROM tasks WHERE workspace_id = ?1 AND id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL) ORDER BY position ASC, id ASC"
-    )
+    let tasks = sqlx::query_as::<_, Task>(&format!(
+        "SELECT * FROM tasks WHERE workspace_id = ?1 AND id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL) {}",
+        database::timeline::INBOX_ORDER_SQL
+    ))
     .bind(workspace_id)
     .fetch_all(&mut *tx)
     .await?;
+    let edges = dependency_edges(&mut tx, workspace_id).await?;
+
+    for task in order_by_prerequisites(tasks, |t| t.id, &edges) {
+        let current_start = append_start(&mut tx, workspace_id, target_date, Local::now().naive_local()).await?;
+
+        let duration = if task.estimated_minutes > 0 { task.estimated_minutes as i64 } else { 30 };
+        schedule_task_blocks(&mut tx, workspace_id, task.id, &task.title, current_start, duration, false, "WILL").await?;
+        ensure_dependency_order(&mut tx, task.id).await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn preview_day_plan(pool: &SqlitePool, workspace_id: i64) -> Result<DayPlanPreview> {
+    preview_day_plan_at(pool, workspace_id, Local::now().naive_local()).await
+}
+
+/// Proposes which inbox tasks fit into what is left of today's core time (or the whole
+/// logical day when no core time is set). Nothing is written; see `apply_day_plan`.
+pub async fn preview_day_plan_at(pool: &SqlitePool, workspace_id: i64, now_dt: NaiveDateTime) -> Result<DayPlanPreview> {
+    let mut tx = pool.begin().await?;
+    let window = plan_window(&mut tx, workspace_id, now_dt).await?;
+
+    let scheduled: Vec<(String, String)> = sqlx::query_as("SELECT start_time, end_time FROM time_blocks WHERE workspace_id = ?1 AND status IN ('NOW', 'WILL', 'PENDING') AND end_time > ?2 AND start_time < ?3")
+        .bind(workspace_id)
+        .bind(window.from.format("%Y-%m-%dT%H:%M:00").to_string())
+        .bind(window.end.format("%Y-%m-%dT%H:%M:00").to_string())
+        .fetch_all(&mut *tx)
+        .await?;
+    let scheduled_minutes: i64 = scheduled.iter().filter_map(|(s, e)| {
+        let s = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").ok()?;
+        let e = NaiveDateTime::parse_from_str(e, "%Y-%m-%dT%H:%M:%S").ok()?;
+        Some((e.min(window.end) - s.max(window.from)).num_minutes().max(0))
+    }).sum();
+
+    let capacity_minutes = ((window.end - window.from).num_minutes() - unplugged_minutes_between(window.from, window.end, &window.unplugged)).max(0);
+
+    let inbox: Vec<Task> = sqlx::query_as(&format!(
+        "SELECT * FROM tasks WHERE workspace_id = ?1 AND id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL) {}",
+        database::timeline::INBOX_PRIORITY_ORDER_SQL
+    ))
+        .bind(workspace_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+    // A task waits for its prerequisites: they must be finished or scheduled before the cursor, or planned earlier here
+    let edges = dependency_edges(&mut tx, workspace_id).await?;
+    let mut ready: HashSet<i64> = sqlx::query_scalar("SELECT DISTINCT task_id FROM time_blocks WHERE workspace_id = ?1 AND task_id IS NOT NULL AND task_id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL AND status != 'DONE' AND end_time > ?2)")
+        .bind(workspace_id)
+        .bind(window.cursor.format("%Y-%m-%dT%H:%M:00").to_string())
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut planned = Vec::new();
+    let mut skipped = Vec::new();
+    let mut cursor = window.cursor;
+    let mut waiting = inbox;
+    while let Some(pos) = waiting.iter().position(|t| edges.iter().all(|&(task_id, blocked_by)| task_id != t.id || ready.contains(&blocked_by))) {
+        let task = waiting.remove(pos);
+        let minutes = if task.estimated_minutes > 0 { task.estimated_minutes } else { 30 };
+        let end = plan_segments(cursor, minutes, &window.unplugged).last().map_or(cursor, |(_, end)| *end);
+        if end <= window.end {
+            planned.push(PlannedTask {
+                task_id: task.id,
+                title: task.title.clone(),
+                minutes,
+                start_time: cursor.format("%Y-%m-%dT%H:%M:00").to_string(),
+                end_time: end.format("%Y-%m-%dT%H:%M:00").to_string(),
+            });
+            cursor = end;
+            ready.insert(task.id);
+        } else {
+            skipped.push(task);
+        }
+    }
+    skipped.append(&mut waiting);
+
+    Ok(DayPlanPreview {
+        window_start: window.from.format("%Y-%m-%dT%H:%M:00").to_string(),
+        window_end: window.end.format("%Y-%m-%dT%H:%M:00").to_string(),
+        capacity_minutes,
+        scheduled_minutes,
+        remaining_minutes: (capacity_minutes - scheduled_minutes).max(0),
+        planned,
+        skipped,
+    })
+}
+
+pub async fn apply_day_plan(pool: &SqlitePool, workspace_id: i64, task_ids: Vec<i64>) -> Result<()> {
+    apply_day_plan_at(pool, workspace_id, task_ids, Local::now().naive_local()).await
+}
 
-    for task in tasks {
-        let last_block: Option<(String,)> = sqlx::query_as("SELECT end_time FROM time_blocks WHERE workspace_id = ?1 AND status != 'UNPLUGGED' ORDER BY end_time DESC LIMIT 1")
+/// Schedules the accepted tasks back to back in the given order, moved behind their prerequisites where needed.
+/// Either all of them land on the timeline or none do; a task that would run past the plan window rejects the lot.
+pub async fn apply_day_plan_at(pool: &SqlitePool, workspace_id: i64, task_ids: Vec<i64>, now_dt: NaiveDateTime) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let window = plan_window(&mut tx, workspace_id, now_dt).await?;
+    let edges = dependency_edges(&mut tx, workspace_id).await?;
+
+    let mut cursor = window.cursor;
+    for task_id in order_by_prerequisites(task_ids, |&id| id, &edges) {
+        let task: Option<Task> = sqlx::query_as("SELECT * FROM tasks WHERE id = ?1 AND workspace_id = ?2 AND id NOT IN (SELECT task_id FROM time_blocks WHERE task_id IS NOT NULL)")
+            .bind(task_id)
             .bind(workspace_id)
             .fetch_optional(&mut *tx)
             .await?;
+        let task = task.ok_or_else(|| AppError::InvalidInput(format!("Task {} is no longer in the inbox.", task_id)))?;
 
-        let now_dt = Local::now().naive_local();
-        let current_start = if let Some((last_end,)) = last_block {
-            let le = NaiveDateTime::parse_from_str(&last_end, "%Y-%m-%dT%H:%M:%S").unwrap_or(now_dt);
-            if le < now_dt { now_dt } else { le }
-        } else {
-            now_dt
-        };
-
-        let duration = if task.estimated_minutes > 0 { task.estimated_minutes as i64 } else { 30 };
-        schedule_task_blocks(&mut tx, workspace_id, task.id, &task.title, current_start, duration, false, "WILL").await?;
+        let duration = if task.estimated_minutes > 0 { task.estimated_minutes } else { 30 };
+        let end = plan_segments(cursor, duration, &window.unplugged).last().map_or(cursor, |(_, end)| *end);
+        if end > window.end {
+            return Err(AppError::InvalidInput(format!("'{}' no longer fits into today's plan.", task.title)));
+        }
+        cursor = schedule_task_blocks(&mut tx, workspace_id, task.id, &task.title, cursor, duration, false, "WILL").await?;
+        ensure_dependency_order(&mut tx, task.id).await?;
     }
+
     tx.commit().await?;
     Ok(())
 }
 
+struct PlanWindow {
+    from: NaiveDateTime,   // Start of the usable window (not before now)
+    end: NaiveDateTime,
+    cursor: NaiveDateTime, // Where the next planned task would start
+    unplugged: Vec<UnpluggedTime>,
+}
+
+async fn plan_window(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, now_dt: NaiveDateTime) -> Result<PlanWindow> {
+    let day_start_time = day_start_time_in(tx).await?;
+    let today = logical_date_of(now_dt, &day_start_time);
+
+    let (window_start, window_end) = match core_time_in(tx, workspace_id).await? {
+        Some((start, end)) => (
+            at_logical_time(today, &start, &day_start_time)?,
+            at_logical_time(today, &end, &day_start_time)?,
+        ),
+        None => {
+            let start = at_logical_time(today, &day_start_time, &day_start_time)?;
+            (start, start + Duration::days(1))
+        }
+    };
+
+    let now_minute = now_dt.with_second(0).and_then(|d| d.with_nanosecond(0)).unwrap_or(now_dt);
+    let from = now_minute.max(window_start);
+
+    let cursor = append_start(tx, workspace_id, Some(today), now_dt).await?.max(from);
+
+    let unplugged: Vec<UnpluggedTime> = sqlx::query_as("SELECT * FROM unplugged_times WHERE workspace_id = ?1").bind(workspace_id).fetch_all(&mut **tx).await?;
+
+    Ok(PlanWindow { from, end: window_end.max(from), cursor, unplugged })
+}
+
+/// Core time of the workspace as `HH:MM` start and end, when both are set.
+async fn core_time_in(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64) -> Result<Option<(String, String)>> {
+    let core_time: Option<(Option<String>, Option<String>)> = sqlx::query_as("SELECT core_time_start, core_time_end FROM workspaces WHERE id = ?1")
+        .bind(workspace_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+    Ok(match core_time {
+        Some((Some(start), Some(end))) if !start.is_empty() && !end.is_empty() => Some((start, end)),
+        _ => None,
+    })
+}
+
+/// Where a task appended to the plan of `target_date` (today when `None`) starts: after the
+/// last block of that logical day, but not before now for today, and not before the core time
+/// start (or the day start without core time) for a future day.
+pub async fn append_start(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, target_date: Option<NaiveDate>, now_dt: NaiveDateTime) -> Result<NaiveDateTime> {
+    let day_start_time = day_start_time_in(tx).await?;
+    let today = logical_date_of(now_dt, &day_start_time);
+    let date = target_date.unwrap_or(today);
+    let earliest = if date == today {
+        now_dt
+    } else if date > today {
+        let opening = core_time_in(tx, workspace_id).await?.map_or_else(|| day_start_time.clone(), |(start, _)| start);
+        at_logical_time(date, &opening, &day_start_time)?
+    } else {
+        return Err(AppError::InvalidInput("Tasks can only be planned for today or a future day.".to_string()));
+    };
+
+    let (start_of_day, end_of_day) = logical_day_bounds(date, &day_start_time)?;
+    let last_block: Option<(String,)> = sqlx::query_as("SELECT end_time FROM time_blocks WHERE workspace_id = ?1 AND status != 'UNPLUGGED' AND start_time >= ?2 AND start_time <= ?3 ORDER BY end_time DESC LIMIT 1")
+        .bind(workspace_id)
+        .bind(&start_of_day)
+        .bind(&end_of_day)
+        .fetch_optional(&mut **tx)
+        .await?;
+    Ok(last_block
+        .and_then(|(end,)| NaiveDateTime::parse_from_str(&end, "%Y-%m-%dT%H:%M:%S").ok())
+        .map_or(earliest, |end| end.max(earliest)))
+}
+
+fn parse_target_date(target_date: Option<&str>) -> Result<Option<NaiveDate>> {
+    target_date
+        .filter(|d| !d.trim().is_empty())
+        .map(|d| NaiveDate::parse_from_str(d.trim(), "%Y-%m-%d").map_err(|e| AppError::DateParse(e.to_string())))
+        .transpose()
+}
+
+/// Places an `HH:MM` clock time on a logical date; times before `day_start_time` fall on the next calendar day.
+fn at_logical_time(date: NaiveDate, time: &str, day_start_time: &str) -> Result<NaiveDateTime> {
+    let t = NaiveTime::parse_from_str(time, "%H:%M").map_err(|e| AppError::DateParse(e.to_string()))?;
+    let date = if time < day_start_time { date + Duration::days(1) } else { date };
+    Ok(date.and_time(t))
+}
+
+fn unplugged_minutes_between(from: NaiveDateTime, to: NaiveDateTime, unplugged: &[UnpluggedTime]) -> i64 {
+    let mut total = 0;
+    let mut date = from.date() - Duration::days(1);
+    while date <= to.date() {
+        for ut in unplugged {
+            let (Ok(s), Ok(e)) = (NaiveTime::parse_from_str(&ut.start_time, "%H:%M"), NaiveTime::parse_from_str(&ut.end_time, "%H:%M")) else { continue; };
+            if e <= s { continue; }
+            let (s, e) = (date.and_time(s), date.and_time(e));
+            total += (e.min(to) - s.max(from)).num_minutes().max(0);
+        }
+        date += Duration::days(1);
+    }
+    total
+}
+
 pub async fn delete_task(pool: &SqlitePool, id: i64) -> Result<()> {
     let mut tx = pool.begin().await?;
     let blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE task_id = ?1 ORDER BY start_time DESC")
@@ -298,6 +742,10 @@ pub async fn handle_split_task_deletion(pool: &SqlitePool, task_id: i64, keep_pa
 }
 
 pub async fn process_task_transition(pool: &SqlitePool, input: TaskTransitionInput) -> Result<()> {
+    process_task_transition_at(pool, input, Local::now().naive_local()).await
+}
+
+pub async fn process_task_transition_at(pool: &SqlitePool, input: TaskTransitionInput, now_dt: NaiveDateTime) -> Result<()> {
     let mut tx = pool.begin().await?;
     let block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1").bind(input.block_id).fetch_one(&mut *tx).await?;
 
@@ -318,57 +766,17 @@ pub async fn process_task_transition(pool: &SqlitePool, input: TaskTransitionInp
     match input.action.as_str() {
         "COMPLETE_ON_TIME" | "COMPLETE_NOW" | "COMPLETE_AGO" => {
             let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
-            let day_start_time = user.day_start_time;
 
             let end_dt = if input.action == "COMPLETE_NOW" {
-                Local::now().naive_local()
+                now_dt
             } else if input.action == "COMPLETE_AGO" {
-                Local::now().naive_local() - Duration::minutes(input.extra_minutes.unwrap_or(0) as i64)
+                now_dt - Duration::minutes(input.extra_minutes.unwrap_or(0) as i64)
             } else {
                 NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S").unwrap()
             };
 
             let end_dt = NaiveDateTime::new(end_dt.date(), NaiveTime::from_hms_opt(end_dt.hour(), end_dt.minute(), 0).unwrap());
-
-            if let Some(task_id) = block.task_id {
-                sqlx::query("UPDATE time_blocks SET status = 'DONE' WHERE task_id = ?1").bind(task_id).execute(&mut *tx).await?;
-            }
-
-            sqlx::query("UPDATE time_blocks SET status = 'DONE', end_time = ?1, review_memo = ?2 WHERE id = ?3")
-                .bind(end_dt.format("%Y-%m-%dT%H:%M:00").to_string()).bind(input.review_memo).bind(input.block_id).execute(&mut *tx).await?;
-
-            // Logical Day calculation
-            let now = Local::now();
-            let current_time_str = now.format("%H:%M").to_string();
-            let current_logical_date = if current_time_str < day_start_time {
-                now.date_naive() - Duration::days(1)
-            } else {
-                now.date_naive()
-            };
-
-            let block_start = NaiveDateTime::parse_from_str(&block.start_time, "%Y-%m-%dT%H:%M:%S").unwrap();
-            let block_time_str = block_start.format("%H:%M").to_string();
-            let block_logical_date = if block_time_str < day_start_time {
-                block_start.date() - Duration::days(1)
-            } else {
-                block_start.date()
-            };
-
-            // Only promote next tasks if the completed task belongs to the current logical day or future
-            if block_logical_date >= current_logical_date {
-                let next_block: Option<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status IN ('WILL', 'PENDING') AND id != ?2 AND start_time >= ?3 ORDER BY start_time ASC LIMIT 1")
-                    .bind(block.workspace_id).bind(input.block_id).bind(&block.start_time).fetch_optional(&mut *tx).await?;
-
-                if let Some(nb) = next_block {
-                    let nb_start = NaiveDateTime::parse_from_str(&nb.start_time, "%Y-%m-%dT%H:%M:%S").unwrap();
-                    let diff = (end_dt - nb_start).num_minutes();
-                    if diff != 0 { shift_future_blocks(&mut tx, block.workspace_id, nb_start, diff).await?; }
-                    sqlx::query("UPDATE time_blocks SET status = 'NOW' WHERE id = ?1").bind(nb.id).execute(&mut *tx).await?;
-                    if let Some(tid) = nb.task_id {
-                        sqlx::query("UPDATE time_blocks SET status = 'CONTINUED' WHERE task_id = ?1 AND status = 'PENDING' AND id < ?2").bind(tid).bind(nb.id).execute(&mut *tx).await?;
-                    }
-                }
-            }
+            complete_block(&mut tx, &block, end_dt, input.review_memo, true, &user.day_start_time, now_dt).await?;
         },
         "DELAY" => {
             let extra = input.extra_minutes.unwrap_or(0) as i64;
@@ -379,6 +787,184 @@ pub async fn process_task_transition(pool: &SqlitePool, input: TaskTransitionInp
         },
         _ => return Err(AppError::InvalidInput("Invalid action".to_string())),
     }
+    refresh_deadline_risk(&mut tx, block.workspace_id).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Marks `block` DONE at `end_dt` and, when it belongs to today or later, lets the next block
+/// of today take over as NOW. `whole_task` also closes the task's other blocks; otherwise the
+/// later parts of a split task stay planned.
+async fn complete_block(tx: &mut Transaction<'_, Sqlite>, block: &TimeBlock, end_dt: NaiveDateTime, review_memo: Option<String>, whole_task: bool, day_start_time: &str, now_dt: NaiveDateTime) -> Result<()> {
+    if whole_task {
+        if let Some(task_id) = block.task_id {
+            sqlx::query("UPDATE time_blocks SET status = 'DONE' WHERE task_id = ?1").bind(task_id).execute(&mut **tx).await?;
+        }
+    }
+
+    sqlx::query("UPDATE time_blocks SET status = 'DONE', end_time = ?1, review_memo = ?2 WHERE id = ?3")
+        .bind(end_dt.format("%Y-%m-%dT%H:%M:00").to_string()).bind(review_memo).bind(block.id).execute(&mut **tx).await?;
+    sqlx::query("UPDATE block_intervals SET ended_at = ?1 WHERE block_id = ?2 AND ended_at IS NULL")
+        .bind(end_dt.format("%Y-%m-%dT%H:%M:00").to_string()).bind(block.id).execute(&mut **tx).await?;
+
+    // Logical Day calculation
+    let current_time_str = now_dt.format("%H:%M").to_string();
+    let current_logical_date = if current_time_str.as_str() < day_start_time {
+        now_dt.date() - Duration::days(1)
+    } else {
+        now_dt.date()
+    };
+
+    let block_start = NaiveDateTime::parse_from_str(&block.start_time, "%Y-%m-%dT%H:%M:%S").unwrap();
+    let block_time_str = block_start.format("%H:%M").to_string();
+    let block_logical_date = if block_time_str.as_str() < day_start_time {
+        block_start.date() - Duration::days(1)
+    } else {
+        block_start.date()
+    };
+
+    // Only promote next tasks if the completed task belongs to the current logical day or future,
+    // and never pull a block planned for a later day into today
+    if block_logical_date >= current_logical_date {
+        let (_, end_of_today) = logical_day_bounds(current_logical_date, day_start_time)?;
+        let next_block: Option<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status IN ('WILL', 'PENDING') AND id != ?2 AND start_time >= ?3 AND start_time <= ?4 ORDER BY start_time ASC LIMIT 1")
+            .bind(block.workspace_id).bind(block.id).bind(&block.start_time).bind(&end_of_today).fetch_optional(&mut **tx).await?;
+
+        if let Some(nb) = next_block {
+            take_plan_snapshot_if_needed(tx, block.workspace_id, now_dt).await?;
+            let nb_start = NaiveDateTime::parse_from_str(&nb.start_time, "%Y-%m-%dT%H:%M:%S").unwrap();
+            let diff = (end_dt - nb_start).num_minutes();
+            if diff != 0 { shift_future_blocks(tx, block.workspace_id, nb_start, diff).await?; }
+            sqlx::query("UPDATE time_blocks SET status = 'NOW' WHERE id = ?1").bind(nb.id).execute(&mut **tx).await?;
+            if let Some(tid) = nb.task_id {
+                sqlx::query("UPDATE time_blocks SET status = 'CONTINUED' WHERE task_id = ?1 AND status = 'PENDING' AND id < ?2").bind(tid).bind(nb.id).execute(&mut **tx).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Completes only `block_id` at its planned end. The later parts of a split task stay planned,
+/// and the next block takes over exactly like after a regular completion.
+pub async fn complete_block_part_at(pool: &SqlitePool, block_id: i64, now_dt: NaiveDateTime) -> Result<()> {
+    let user = database::user::get_user(pool).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let mut tx = pool.begin().await?;
+    let block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1")
+        .bind(block_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Block not found".to_string()))?;
+    let end_dt = NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S").map_err(|e| AppError::DateParse(e.to_string()))?;
+    complete_block(&mut tx, &block, end_dt, block.review_memo.clone(), false, &user.day_start_time, now_dt).await?;
+    refresh_deadline_risk(&mut tx, block.workspace_id).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn pause_block(pool: &SqlitePool, block_id: i64) -> Result<()> {
+    pause_block_at(pool, block_id, Local::now().naive_local()).await
+}
+
+pub async fn pause_block_at(pool: &SqlitePool, block_id: i64, now_dt: NaiveDateTime) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1").bind(block_id).fetch_one(&mut *tx).await?;
+    if block.status != "NOW" {
+        return Err(AppError::InvalidInput("Only the active (NOW) task can be paused.".to_string()));
+    }
+
+    let now_str = now_dt.format("%Y-%m-%dT%H:%M:00").to_string();
+    let last: Option<BlockInterval> = sqlx::query_as("SELECT * FROM block_intervals WHERE block_id = ?1 ORDER BY started_at DESC, id DESC LIMIT 1")
+        .bind(block_id).fetch_optional(&mut *tx).await?;
+
+    match last {
+        Some(interval) if interval.ended_at.is_some() => {
+            return Err(AppError::InvalidInput("The task is already paused.".to_string()));
+        }
+        Some(interval) => {
+            sqlx::query("UPDATE block_intervals SET ended_at = ?1 WHERE id = ?2").bind(&now_str).bind(interval.id).execute(&mut *tx).await?;
+        }
+        None => {
+            // First pause: the block has been worked on since its start
+            sqlx::query("INSERT INTO block_intervals (block_id, started_at, ended_at) VALUES (?1, ?2, ?3)")
+                .bind(block_id).bind(&block.start_time).bind(&now_str).execute(&mut *tx).await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn resume_block(pool: &SqlitePool, block_id: i64) -> Result<()> {
+    resume_block_at(pool, block_id, Local::now().naive_local()).await
+}
+
+pub async fn resume_block_at(pool: &SqlitePool, block_id: i64, now_dt: NaiveDateTime) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1").bind(block_id).fetch_one(&mut *tx).await?;
+    if block.status != "NOW" {
+        return Err(AppError::InvalidInput("Only the active (NOW) task can be resumed.".to_string()));
+    }
+
+    let last: Option<BlockInterval> = sqlx::query_as("SELECT * FROM block_intervals WHERE block_id = ?1 ORDER BY started_at DESC, id DESC LIMIT 1")
+        .bind(block_id).fetch_optional(&mut *tx).await?;
+    let paused_since = match last.and_then(|i| i.ended_at) {
+        Some(ended_at) => NaiveDateTime::parse_from_str(&ended_at, "%Y-%m-%dT%H:%M:%S").map_err(|e| AppError::DateParse(e.to_string()))?,
+        None => return Err(AppError::InvalidInput("The task is not paused.".to_string())),
+    };
+
+    let now_dt = NaiveDateTime::new(now_dt.date(), NaiveTime::from_hms_opt(now_dt.hour(), now_dt.minute(), 0).unwrap());
+    sqlx::query("INSERT INTO block_intervals (block_id, started_at) VALUES (?1, ?2)")
+        .bind(block_id).bind(now_dt.format("%Y-%m-%dT%H:%M:00").to_string()).execute(&mut *tx).await?;
+
+    // The pause pushes the rest of the plan back by the same amount
+    let pause_minutes = (now_dt - paused_since).num_minutes();
+    if pause_minutes > 0 {
+        let current_end = NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S").unwrap();
+        let new_end = current_end + Duration::minutes(pause_minutes);
+        sqlx::query("UPDATE time_blocks SET end_time = ?1 WHERE id = ?2").bind(new_end.format("%Y-%m-%dT%H:%M:00").to_string()).bind(block_id).execute(&mut *tx).await?;
+        shift_future_blocks(&mut tx, block.workspace_id, current_end, pause_minutes).await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Puts a `BREAK` block of `break_minutes` at `break_start` inside the NOW block: the work
+/// interval closes at `break_start` and reopens when the break ends, and the rest of the
+/// plan is pushed back by the break, like a pause followed by a resume.
+pub async fn insert_break_at(pool: &SqlitePool, block_id: i64, title: &str, break_start: NaiveDateTime, break_minutes: i64) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1").bind(block_id).fetch_one(&mut *tx).await?;
+    if block.status != "NOW" {
+        return Err(AppError::InvalidInput("Breaks are taken inside the active (NOW) task.".to_string()));
+    }
+
+    let start_str = break_start.format("%Y-%m-%dT%H:%M:00").to_string();
+    let end_str = (break_start + Duration::minutes(break_minutes)).format("%Y-%m-%dT%H:%M:00").to_string();
+    let last: Option<BlockInterval> = sqlx::query_as("SELECT * FROM block_intervals WHERE block_id = ?1 ORDER BY started_at DESC, id DESC LIMIT 1")
+        .bind(block_id).fetch_optional(&mut *tx).await?;
+    match last {
+        Some(interval) if interval.ended_at.is_some() => {
+            return Err(AppError::InvalidInput("The task is paused.".to_string()));
+        }
+        Some(interval) => {
+            sqlx::query("UPDATE block_intervals SET ended_at = ?1 WHERE id = ?2").bind(&start_str).bind(interval.id).execute(&mut *tx).await?;
+        }
+        None => {
+            sqlx::query("INSERT INTO block_intervals (block_id, started_at, ended_at) VALUES (?1, ?2, ?3)")
+                .bind(block_id).bind(&block.start_time).bind(&start_str).execute(&mut *tx).await?;
+        }
+    }
+    sqlx::query("INSERT INTO block_intervals (block_id, started_at) VALUES (?1, ?2)")
+        .bind(block_id).bind(&end_str).execute(&mut *tx).await?;
+    sqlx::query("INSERT INTO time_blocks (workspace_id, title, start_time, end_time, status) VALUES (?1, ?2, ?3, ?4, 'BREAK')")
+        .bind(block.workspace_id).bind(title).bind(&start_str).bind(&end_str).execute(&mut *tx).await?;
+
+    let current_end = NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S").map_err(|e| AppError::DateParse(e.to_string()))?;
+    let new_end = current_end + Duration::minutes(break_minutes);
+    sqlx::query("UPDATE time_blocks SET end_time = ?1 WHERE id = ?2").bind(new_end.format("%Y-%m-%dT%H:%M:00").to_string()).bind(block_id).execute(&mut *tx).await?;
+    shift_future_blocks(&mut tx, block.workspace_id, current_end, break_minutes).await?;
+
     tx.commit().await?;
     Ok(())
 }
@@ -401,6 +987,9 @@ pub async fn update_block_status(pool: &SqlitePool, block_id: i64, status: Strin
         }
     }
 
+    if status == "NOW" {
+        take_plan_snapshot_if_needed(&mut tx, block.workspace_id, Local::now().naive_local()).await?;
+    }
     sqlx::query("UPDATE time_blocks SET status = ?1 WHERE id = ?2").bind(&status).bind(block_id).execute(&mut *tx).await?;
     if status == "NOW" {
         if let Some(task_id) = block.task_id {
@@ -429,7 +1018,7 @@ pub async fn reorder_inbox(pool: &SqlitePool, workspace_id: i64, task_ids: Vec<i
 
 pub async fn move_task_step(pool: &SqlitePool, workspace_id: i64, block_id: i64, direction: String) -> Result<()> {
     let mut tx = pool.begin().await?;
-    let all_blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status != 'DONE' ORDER BY start_time ASC").bind(workspace_id).fetch_all(&mut *tx).await?;
+    let all_blocks = open_blocks_of_day(&mut tx, workspace_id, block_id).await?;
     if all_blocks.is_empty() { return Ok(()); }
     let mut ids: Vec<i64> = all_blocks.iter().map(|b| b.id).collect();
     let index = ids.iter().position(|&id| id == block_id).ok_or_else(|| AppError::NotFound("Block not found".to_string()))?;
@@ -441,7 +1030,7 @@ pub async fn move_task_step(pool: &SqlitePool, workspace_id: i64, block_id: i64,
 
 pub async fn move_task_to_priority(pool: &SqlitePool, workspace_id: i64, block_id: i64) -> Result<()> {
     let mut tx = pool.begin().await?;
-    let all_blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status != 'DONE' ORDER BY start_time ASC").bind(workspace_id).fetch_all(&mut *tx).await?;
+    let all_blocks = open_blocks_of_day(&mut tx, workspace_id, block_id).await?;
     if all_blocks.is_empty() { return Ok(()); }
     let mut ids: Vec<i64> = all_blocks.iter().map(|b| b.id).collect();
     let index = ids.iter().position(|&id| id == block_id).ok_or_else(|| AppError::NotFound("Block not found".to_string()))?;
@@ -456,7 +1045,7 @@ pub async fn move_task_to_priority(pool: &SqlitePool, workspace_id: i64, block_i
 
 pub async fn move_task_to_bottom(pool: &SqlitePool, workspace_id: i64, block_id: i64) -> Result<()> {
     let mut tx = pool.begin().await?;
-    let all_blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status != 'DONE' ORDER BY start_time ASC").bind(workspace_id).fetch_all(&mut *tx).await?;
+    let all_blocks = open_blocks_of_day(&mut tx, workspace_id, block_id).await?;
     if all_blocks.is_empty() { return Ok(()); }
     let mut ids: Vec<i64> = all_blocks.iter().map(|b| b.id).collect();
     let index = ids.iter().position(|&id| id == block_id).ok_or_else(|| AppError::NotFound("Block not found".to_string()))?;
@@ -467,8 +1056,28 @@ pub async fn move_task_to_bottom(pool: &SqlitePool, workspace_id: i64, block_id:
     Ok(())
 }
 
+/// Unfinished blocks planned on the same logical day as `block_id`, in timeline order.
+/// Focus breaks stay where they were taken and are left out.
+async fn open_blocks_of_day(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, block_id: i64) -> Result<Vec<TimeBlock>> {
+    let block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1 AND workspace_id = ?2")
+        .bind(block_id)
+        .bind(workspace_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Block not found".to_string()))?;
+    let start_dt = NaiveDateTime::parse_from_str(&block.start_time, "%Y-%m-%dT%H:%M:%S").map_err(|e| AppError::DateParse(e.to_string()))?;
+    let (start_of_day, end_of_day) = day_bounds_in(tx, start_dt).await?;
+    let blocks = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status NOT IN ('DONE', 'BREAK') AND start_time >= ?2 AND start_time <= ?3 ORDER BY start_time ASC")
+        .bind(workspace_id)
+        .bind(&start_of_day)
+        .bind(&end_of_day)
+        .fetch_all(&mut **tx)
+        .await?;
+    Ok(blocks)
+}
+
 async fn reorder_internal(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, block_ids: Vec<i64>) -> Result<()> {
-    let all_blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status != 'DONE'").bind(workspace_id).fetch_all(&mut **tx).await?;
+    let all_blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND status NOT IN ('DONE', 'BREAK')").bind(workspace_id).fetch_all(&mut **tx).await?;
     if all_blocks.is_empty() { return Ok(()); }
     if let Some(now_block) = all_blocks.iter().find(|b| b.status == "NOW") {
         if let Some(pos) = block_ids.iter().position(|&id| id == now_block.id) {
@@ -481,9 +1090,10 @@ async fn reorder_internal(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, b
             }
         }
     }
-    let mut current_blocks: Vec<TimeBlock> = all_blocks.clone();
-    current_blocks.sort_by(|a, b| a.start_time.cmp(&b.start_time));
-    let start_dt = NaiveDateTime::parse_from_str(&current_blocks[0].start_time, "%Y-%m-%dT%H:%M:%S").unwrap();
+    validate_dependency_order(tx, workspace_id, &block_ids, &all_blocks).await?;
+    // Lay the given blocks out from the earliest of them, so reordering one day leaves other days alone
+    let Some(first_start) = all_blocks.iter().filter(|b| block_ids.contains(&b.id)).map(|b| b.start_time.as_str()).min() else { return Ok(()); };
+    let start_dt = NaiveDateTime::parse_from_str(first_start, "%Y-%m-%dT%H:%M:%S").unwrap();
     let mut current_time = start_dt;
     let unplugged: Vec<UnpluggedTime> = sqlx::query_as("SELECT * FROM unplugged_times WHERE workspace_id = ?1").bind(workspace_id).fetch_all(&mut **tx).await?;
 
@@ -508,20 +1118,85 @@ async fn reorder_internal(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, b
             current_time = new_end;
         }
     }
+    refresh_deadline_risk(tx, workspace_id).await
+}
+
+/// Rejects an order in which a task's first block comes before the last block of one of its prerequisites.
+async fn validate_dependency_order(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, block_ids: &[i64], all_blocks: &[TimeBlock]) -> Result<()> {
+    let edges = dependency_edges(tx, workspace_id).await?;
+    if edges.is_empty() { return Ok(()); }
+
+    let task_at = |pos: usize| all_blocks.iter().find(|b| b.id == block_ids[pos]).and_then(|b| b.task_id);
+    let first_pos = |task_id: i64| (0..block_ids.len()).find(|&p| task_at(p) == Some(task_id));
+    let last_pos = |task_id: i64| (0..block_ids.len()).rev().find(|&p| task_at(p) == Some(task_id));
+
+    for (task_id, blocked_by) in edges {
+        if let (Some(first), Some(last)) = (first_pos(task_id), last_pos(blocked_by)) {
+            if first < last {
+                let title = |id: i64| all_blocks.iter().find(|b| b.task_id == Some(id)).map(|b| b.title.clone()).unwrap_or_default();
+                return Err(AppError::InvalidInput(format!("'{}' cannot be scheduled before its prerequisite '{}'.", title(task_id), title(blocked_by))));
+            }
+        }
+    }
     Ok(())
 }
 
-async fn schedule_task_blocks(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, task_id: i64, title: &str, start_dt: NaiveDateTime, mut remaining_minutes: i64, is_urgent: bool, status: &str) -> Result<()> {
-    let mut current_start = start_dt;
-    let mut first = true;
+/// Rejects a placement of `task_id` that leaves an open block of it before one of its prerequisites,
+/// or after one of its dependents; every path that inserts blocks keeps the order `reorder_blocks` accepts.
+pub async fn ensure_dependency_order(tx: &mut Transaction<'_, Sqlite>, task_id: i64) -> Result<()> {
+    match database::dependency::find_order_conflict(tx, task_id).await? {
+        Some((title, prerequisite)) => Err(AppError::InvalidInput(format!("'{}' cannot be scheduled before its prerequisite '{}'.", title, prerequisite))),
+        None => Ok(()),
+    }
+}
+
+/// `(task_id, blocked_by_task_id)` pairs of the workspace.
+async fn dependency_edges(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64) -> Result<Vec<(i64, i64)>> {
+    let edges = sqlx::query_as("SELECT d.task_id, d.blocked_by_task_id FROM task_dependencies d JOIN tasks t ON d.task_id = t.id WHERE t.workspace_id = ?1")
+        .bind(workspace_id)
+        .fetch_all(&mut **tx)
+        .await?;
+    Ok(edges)
+}
+
+/// Keeps the given order, except that an item waits until its prerequisites among the items come first.
+fn order_by_prerequisites<T>(mut items: Vec<T>, id_of: impl Fn(&T) -> i64, edges: &[(i64, i64)]) -> Vec<T> {
+    let mut ordered: Vec<T> = Vec::with_capacity(items.len());
+    while !items.is_empty() {
+        let waits = |item: &T| edges.iter().any(|&(task_id, blocked_by)| task_id == id_of(item) && items.iter().any(|other| id_of(other) == blocked_by));
+        // A cycle cannot be created, but fall back to the given order rather than loop
+        let pos = items.iter().position(|item| !waits(item)).unwrap_or(0);
+        ordered.push(items.remove(pos));
+    }
+    ordered
+}
+
+/// Inserts the blocks of a task starting at `start_dt`, split around unplugged times.
+/// Returns the end of the last inserted block.
+pub async fn schedule_task_blocks(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, task_id: i64, title: &str, start_dt: NaiveDateTime, remaining_minutes: i64, is_urgent: bool, status: &str) -> Result<NaiveDateTime> {
     let unplugged: Vec<UnpluggedTime> = sqlx::query_as("SELECT * FROM unplugged_times WHERE workspace_id = ?1").bind(workspace_id).fetch_all(&mut **tx).await?;
 
+    let segments = plan_segments(start_dt, remaining_minutes, &unplugged);
+    for (index, (start, end)) in segments.iter().enumerate() {
+        let block_status = if index == 0 { status } else { "WILL" };
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status, is_urgent) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)")
+            .bind(task_id).bind(workspace_id).bind(title).bind(start.format("%Y-%m-%dT%H:%M:00").to_string()).bind(end.format("%Y-%m-%dT%H:%M:00").to_string()).bind(block_status).bind(is_urgent).execute(&mut **tx).await?;
+    }
+    refresh_deadline_risk(tx, workspace_id).await?;
+    Ok(segments.last().map_or(start_dt, |(_, end)| *end))
+}
+
+/// Splits `remaining_minutes` starting at `start_dt` into the spans between unplugged times.
+fn plan_segments(start_dt: NaiveDateTime, mut remaining_minutes: i64, unplugged: &[UnpluggedTime]) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    let mut segments = Vec::new();
+    let mut current_start = start_dt;
+
     while remaining_minutes > 0 {
         let current_end = current_start + Duration::minutes(remaining_minutes);
         let mut split_at = None;
         let mut resume_at = None;
 
-        for ut in &unplugged {
+        for ut in unplugged {
             let ut_start = current_start.date().and_time(NaiveTime::parse_from_str(&ut.start_time, "%H:%M").unwrap());
             let ut_end = current_start.date().and_time(NaiveTime::parse_from_str(&ut.end_time, "%H:%M").unwrap());
             if current_start < ut_start && current_end > ut_start { split_at = Some(ut_start); resume_at = Some(ut_end); break; }
@@ -531,26 +1206,129 @@ async fn schedule_task_blocks(tx: &mut Transaction<'_, Sqlite>, workspace_id: i6
         let end = split_at.unwrap_or(current_end);
         let duration = (end - current_start).num_minutes();
         if duration > 0 {
-            let block_status = if first { status } else { "WILL" };
-            sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status, is_urgent) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)")
-                .bind(task_id).bind(workspace_id).bind(title).bind(current_start.format("%Y-%m-%dT%H:%M:00").to_string()).bind(end.format("%Y-%m-%dT%H:%M:00").to_string()).bind(block_status).bind(is_urgent).execute(&mut **tx).await?;
-            first = false;
+            segments.push((current_start, end));
         }
         remaining_minutes -= duration;
         if let Some(r) = resume_at { current_start = r; } else { break; }
     }
-    Ok(())
+    segments
 }
 
+/// Shifts the unfinished blocks from `after_dt` to the end of its logical day; plans of later days keep their times.
 async fn shift_future_blocks(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, after_dt: NaiveDateTime, shift_minutes: i64) -> Result<()> {
-    let blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND start_time >= ?2 AND status IN ('WILL', 'PENDING')")
-        .bind(workspace_id).bind(after_dt.format("%Y-%m-%dT%H:%M:00").to_string()).fetch_all(&mut **tx).await?;
+    let (_, end_of_day) = day_bounds_in(tx, after_dt).await?;
+    let blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE workspace_id = ?1 AND start_time >= ?2 AND start_time <= ?3 AND status IN ('WILL', 'PENDING')")
+        .bind(workspace_id).bind(after_dt.format("%Y-%m-%dT%H:%M:00").to_string()).bind(&end_of_day).fetch_all(&mut **tx).await?;
 
     for block in blocks {
         let new_start = NaiveDateTime::parse_from_str(&block.start_time, "%Y-%m-%dT%H:%M:%S").unwrap() + Duration::minutes(shift_minutes);
         let new_end = NaiveDateTime::parse_from_str(&block.end_time, "%Y-%m-%dT%H:%M:%S").unwrap() + Duration::minutes(shift_minutes);
         sqlx::query("UPDATE time_blocks SET start_time = ?1, end_time = ?2 WHERE id = ?3").bind(new_start.format("%Y-%m-%dT%H:%M:00").to_string()).bind(new_end.format("%Y-%m-%dT%H:%M:00").to_string()).bind(block.id).execute(&mut **tx).await?;
     }
+    refresh_deadline_risk(tx, workspace_id).await
+}
+
+/// Finds the project named `name` visible from the workspace, preferring a
+/// workspace-local one over a shared one, or creates it local to the workspace.
+async fn resolve_project_id(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, name: Option<&str>) -> Result<Option<i64>> {
+    let Some(name) = name.filter(|n| !n.trim().is_empty()) else { return Ok(None) };
+    let now = Local::now().format("%Y-%m-%dT%H:%M:00").to_string();
+    let existing: Option<(i64,)> = sqlx::query_as("SELECT id FROM projects WHERE name = ?1 AND (workspace_id = ?2 OR workspace_id IS NULL) ORDER BY workspace_id IS NULL LIMIT 1")
+        .bind(name)
+        .bind(workspace_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+    if let Some((id,)) = existing {
+        sqlx::query("UPDATE projects SET last_used = ?1 WHERE id = ?2").bind(&now).bind(id).execute(&mut **tx).await?;
+        return Ok(Some(id));
+    }
+    let res = sqlx::query("INSERT INTO projects (workspace_id, name, last_used) VALUES (?1, ?2, ?3)").bind(workspace_id).bind(name).bind(&now).execute(&mut **tx).await?;
+    Ok(Some(res.last_insert_rowid()))
+}
+
+/// Label counterpart of `resolve_project_id`; new labels get the default grey.
+async fn resolve_label_id(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, name: Option<&str>) -> Result<Option<i64>> {
+    let Some(name) = name.filter(|n| !n.trim().is_empty()) else { return Ok(None) };
+    let now = Local::now().format("%Y-%m-%dT%H:%M:00").to_string();
+    let existing: Option<(i64,)> = sqlx::query_as("SELECT id FROM labels WHERE name = ?1 AND (workspace_id = ?2 OR workspace_id IS NULL) ORDER BY workspace_id IS NULL LIMIT 1")
+        .bind(name)
+        .bind(workspace_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+    if let Some((id,)) = existing {
+        sqlx::query("UPDATE labels SET last_used = ?1 WHERE id = ?2").bind(&now).bind(id).execute(&mut **tx).await?;
+        return Ok(Some(id));
+    }
+    let res = sqlx::query("INSERT INTO labels (workspace_id, name, color, last_used) VALUES (?1, ?2, '#808080', ?3)").bind(workspace_id).bind(name).bind(&now).execute(&mut **tx).await?;
+    Ok(Some(res.last_insert_rowid()))
+}
+
+/// Flags tasks whose last unfinished block now ends after their due date.
+pub async fn refresh_deadline_risk(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64) -> Result<()> {
+    sqlx::query(
+        "UPDATE tasks SET is_at_risk = COALESCE(due_at < (SELECT MAX(tb.end_time) FROM time_blocks tb WHERE tb.task_id = tasks.id AND tb.status IN ('NOW', 'WILL', 'PENDING')), 0)
+         WHERE workspace_id = ?1 AND (due_at IS NOT NULL OR is_at_risk = 1)"
+    )
+    .bind(workspace_id)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Accepts `YYYY-MM-DD` (end of that day) or `YYYY-MM-DDTHH:MM[:SS]`; empty means no due date.
+fn normalize_due_at(due_at: Option<&str>) -> Result<Option<String>> {
+    let Some(raw) = due_at.map(str::trim).filter(|d| !d.is_empty()) else { return Ok(None); };
+    let dt = if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        date.and_hms_opt(23, 59, 0).unwrap()
+    } else {
+        NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S")
+            .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M"))
+            .map_err(|e| AppError::DateParse(e.to_string()))?
+    };
+    Ok(Some(dt.format("%Y-%m-%dT%H:%M:00").to_string()))
+}
+
+pub async fn day_start_time_in(tx: &mut Transaction<'_, Sqlite>) -> Result<String> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT day_start_time FROM users WHERE id = 1").fetch_optional(&mut **tx).await?;
+    Ok(row.map(|(d,)| d).unwrap_or_else(|| "04:00".to_string()))
+}
+
+/// `logical_day_bounds` of the logical day `dt` falls on.
+async fn day_bounds_in(tx: &mut Transaction<'_, Sqlite>, dt: NaiveDateTime) -> Result<(String, String)> {
+    let day_start_time = day_start_time_in(tx).await?;
+    logical_day_bounds(logical_date_of(dt, &day_start_time), &day_start_time)
+}
+
+/// Freezes the planned timeline of the logical day the first time a block becomes NOW,
+/// so it can later be compared with what actually happened.
+async fn take_plan_snapshot_if_needed(tx: &mut Transaction<'_, Sqlite>, workspace_id: i64, now_dt: NaiveDateTime) -> Result<()> {
+    let day_start_time = day_start_time_in(tx).await?;
+    let logical_date = logical_date_of(now_dt, &day_start_time);
+    let (start_of_day, end_of_day) = logical_day_bounds(logical_date, &day_start_time)?;
+
+    let result = sqlx::query("INSERT OR IGNORE INTO plan_snapshots (workspace_id, logical_date, taken_at) VALUES (?1, ?2, ?3)")
+        .bind(workspace_id)
+        .bind(logical_date.format("%Y-%m-%d").to_string())
+        .bind(now_dt.format("%Y-%m-%dT%H:%M:00").to_string())
+        .execute(&mut **tx)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "INSERT INTO plan_snapshot_blocks (snapshot_id, block_id, task_id, title, start_time, end_time, status, estimated_minutes)
+         SELECT ?1, tb.id, tb.task_id, tb.title, tb.start_time, tb.end_time, tb.status, COALESCE(t.estimated_minutes, 0)
+         FROM time_blocks tb
+         LEFT JOIN tasks t ON tb.task_id = t.id
+         WHERE tb.workspace_id = ?2 AND tb.start_time >= ?3 AND tb.start_time <= ?4"
+    )
+    .bind(result.last_insert_rowid())
+    .bind(workspace_id)
+    .bind(&start_of_day)
+    .bind(&end_of_day)
+    .execute(&mut **tx)
+    .await?;
     Ok(())
 }
 
@@ -563,23 +1341,19 @@ pub async fn update_task(pool: &SqlitePool, input: UpdateTaskInput) -> Result<()
     let block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = ?1").bind(input.block_id).fetch_one(&mut *tx).await?;
 
     if let Some(task_id) = block.task_id {
-        let mut project_id = None;
-        if let Some(p_name) = &input.project_name {
-            if !p_name.trim().is_empty() {
-                let existing: Option<(i64,)> = sqlx::query_as("SELECT id FROM projects WHERE name = ?1").bind(p_name).fetch_optional(&mut *tx).await?;
-                if let Some((id,)) = existing { project_id = Some(id); sqlx::query("UPDATE projects SET last_used = ?1 WHERE id = ?2").bind(Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).bind(id).execute(&mut *tx).await?; }
-                else { let res = sqlx::query("INSERT INTO projects (name, last_used) VALUES (?1, ?2)").bind(p_name).bind(Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).execute(&mut *tx).await?; project_id = Some(res.last_insert_rowid()); }
-            }
+        let project_id = resolve_project_id(&mut tx, block.workspace_id, input.project_name.as_deref()).await?;
+        let label_id = resolve_label_id(&mut tx, block.workspace_id, input.label_name.as_deref()).await?;
+        sqlx::query("UPDATE tasks SET title = ?1, planning_memo = ?2, project_id = ?3, label_id = ?4 WHERE id = ?5").bind(&input.title).bind(&input.description).bind(project_id).bind(label_id).bind(task_id).execute(&mut *tx).await?;
+        if input.due_at.is_some() {
+            let due_at = normalize_due_at(input.due_at.as_deref())?;
+            sqlx::query("UPDATE tasks SET due_at = ?1 WHERE id = ?2").bind(due_at).bind(task_id).execute(&mut *tx).await?;
         }
-        let mut label_id = None;
-        if let Some(l_name) = &input.label_name {
-            if !l_name.trim().is_empty() {
-                let existing: Option<(i64,)> = sqlx::query_as("SELECT id FROM labels WHERE name = ?1").bind(l_name).fetch_optional(&mut *tx).await?;
-                if let Some((id,)) = existing { label_id = Some(id); sqlx::query("UPDATE labels SET last_used = ?1 WHERE id = ?2").bind(Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).bind(id).execute(&mut *tx).await?; }
-                else { let res = sqlx::query("INSERT INTO labels (name, color, last_used) VALUES (?1, '#808080', ?2)").bind(l_name).bind(Local::now().format("%Y-%m-%dT%H:%M:00").to_string()).execute(&mut *tx).await?; label_id = Some(res.last_insert_rowid()); }
-            }
+        if let Some(tags) = &input.tags {
+            database::tag::set_task_tags(&mut tx, block.workspace_id, task_id, &services::tag::normalize_tags(tags)).await?;
+        }
+        if let Some(priority) = validate_priority(input.priority)? {
+            sqlx::query("UPDATE tasks SET priority = ?1 WHERE id = ?2").bind(priority).bind(task_id).execute(&mut *tx).await?;
         }
-        sqlx::query("UPDATE tasks SET title = ?1, planning_memo = ?2, project_id = ?3, label_id = ?4 WHERE id = ?5").bind(&input.title).bind(&input.description).bind(project_id).bind(label_id).bind(task_id).execute(&mut *tx).await?;
         sqlx::query("UPDATE time_blocks SET title = ?1 WHERE task_id = ?2").bind(&input.title).bind(task_id).execute(&mut *tx).await?;
     }
 
@@ -594,6 +1368,7 @@ pub async fn update_task(pool: &SqlitePool, input: UpdateTaskInput) -> Result<()
         sqlx::query("UPDATE time_blocks SET end_time = ?1 WHERE id = ?2").bind(new_end_dt.format("%Y-%m-%dT%H:%M:00").to_string()).bind(input.block_id).execute(&mut *tx).await?;
         if diff != 0 { shift_future_blocks(&mut tx, block.workspace_id, original_end_dt, diff).await?; }
     }
+    refresh_deadline_risk(&mut tx, block.workspace_id).await?;
     tx.commit().await?;
     Ok(())
 }
@@ -640,10 +1415,20 @@ mod tests {
         
         sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, core_time_start TEXT, core_time_end TEXT, role_intro TEXT)").execute(&pool).await.unwrap();
         sqlx::query("CREATE TABLE unplugged_times (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, label TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL)").execute(&pool).await.unwrap();
-        sqlx::query("CREATE TABLE projects (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
-        sqlx::query("CREATE TABLE labels (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, color TEXT NOT NULL, last_used TEXT NOT NULL)").execute(&pool).await.unwrap();
-        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, estimated_minutes INTEGER NOT NULL DEFAULT 0, project_id INTEGER REFERENCES projects(id), label_id INTEGER REFERENCES labels(id), position INTEGER NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query(crate::database::workspace::CREATE_PROJECTS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(crate::database::workspace::CREATE_PROJECTS_SCOPE_INDEX).execute(&pool).await.unwrap();
+        sqlx::query(crate::database::workspace::CREATE_LABELS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(crate::database::workspace::CREATE_LABELS_SCOPE_INDEX).execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, planning_memo TEXT, estimated_minutes INTEGER NOT NULL DEFAULT 0, project_id INTEGER REFERENCES projects(id), label_id INTEGER REFERENCES labels(id), position INTEGER NOT NULL DEFAULT 0, due_at TEXT, is_at_risk BOOLEAN NOT NULL DEFAULT 0, priority INTEGER NOT NULL DEFAULT 2)").execute(&pool).await.unwrap();
         sqlx::query("CREATE TABLE time_blocks (id INTEGER PRIMARY KEY AUTOINCREMENT, task_id INTEGER, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, start_time TEXT NOT NULL, end_time TEXT NOT NULL, status TEXT NOT NULL, review_memo TEXT, planning_memo TEXT, is_urgent BOOLEAN NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+        sqlx::query(database::timeline::CREATE_BLOCK_INTERVALS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::analytics::CREATE_PLAN_SNAPSHOTS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::analytics::CREATE_PLAN_SNAPSHOT_BLOCKS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::checklist::CREATE_TASK_CHECKLIST_ITEMS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::dependency::CREATE_TASK_DEPENDENCIES_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::tag::CREATE_TAGS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::tag::CREATE_TASK_TAGS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::interruption::CREATE_INTERRUPTIONS_TABLE).execute(&pool).await.unwrap();
 
         pool
     }
@@ -677,6 +1462,10 @@ mod tests {
             is_inbox: Some(false),
             project_name: None,
             label_name: None,
+            due_at: None,
+            priority: None,
+            tags: None,
+            target_date: None,
         };
 
         add_task_at(&pool, input, now_dt).await.unwrap();
@@ -692,6 +1481,428 @@ mod tests {
         assert_eq!(blocks[2].status, "PENDING");
         assert_eq!(blocks[3].title, "T3");
         assert_eq!(blocks[3].status, "WILL");
+
+        let interruptions = database::interruption::get_interruptions_in_range(&pool, 1, "2026-03-01T04:00:00", "2026-03-02T03:59:00").await.unwrap();
+        assert_eq!(interruptions.len(), 1);
+        assert_eq!((interruptions[0].interrupted_task_id, interruptions[0].interrupted_title.as_str()), (Some(10), "T1"));
+        assert_eq!((interruptions[0].interrupting_title.as_str(), interruptions[0].occurred_at.as_str(), interruptions[0].lost_minutes), ("T2 (Urgent)", "2026-03-01T18:10:00", 20));
+    }
+
+    #[tokio::test]
+    async fn test_day_plan_fits_inbox_into_core_time() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name, core_time_start, core_time_end) VALUES (1, 'Test', '09:00', '18:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO unplugged_times (workspace_id, label, start_time, end_time) VALUES (1, 'Lunch', '12:00', '13:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Scheduled')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (1, 1, 'Scheduled', '2026-03-01T14:00:00', '2026-03-01T15:00:00', 'WILL')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, position) VALUES (2, 1, 'Big', 240, 0)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, position) VALUES (3, 1, 'Medium', 90, 1)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, position) VALUES (4, 1, 'Small', 60, 2)").execute(&pool).await.unwrap();
+
+        let now_dt = NaiveDateTime::parse_from_str("2026-03-01T14:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let preview = preview_day_plan_at(&pool, 1, now_dt).await.unwrap();
+
+        assert_eq!(preview.capacity_minutes, 240);
+        assert_eq!(preview.scheduled_minutes, 60);
+        assert_eq!(preview.remaining_minutes, 180);
+        let planned: Vec<i64> = preview.planned.iter().map(|p| p.task_id).collect();
+        assert_eq!(planned, vec![3, 4]);
+        assert_eq!(preview.planned[1].end_time, "2026-03-01T17:30:00");
+        assert_eq!(preview.skipped.len(), 1);
+        assert_eq!(preview.skipped[0].id, 2);
+
+        apply_day_plan_at(&pool, 1, planned, now_dt).await.unwrap();
+        let blocks = database::timeline::get_timeline(&pool, 1, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(), "04:00").await.unwrap();
+        let titles: Vec<&str> = blocks.iter().filter(|b| b.status != "UNPLUGGED").map(|b| b.title.as_str()).collect();
+        assert_eq!(titles, vec!["Scheduled", "Medium", "Small"]);
+
+        // Accepting is all-or-nothing: a task that already left the inbox rolls everything back
+        let result = apply_day_plan_at(&pool, 1, vec![2, 3], now_dt).await;
+        assert!(result.is_err());
+        let big_blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE task_id = 2").fetch_all(&pool).await.unwrap();
+        assert!(big_blocks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_day_plan_respects_window_and_prerequisites() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name, core_time_start, core_time_end) VALUES (1, 'Test', '09:00', '12:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, position, priority) VALUES (1, 1, 'Deploy', 30, 0, 1)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, position, priority) VALUES (2, 1, 'Build', 60, 1, 3)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, position, priority) VALUES (3, 1, 'Review', 30, 2, 1)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, position) VALUES (4, 1, 'Later', 30, 3)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes, position) VALUES (5, 1, 'Huge', 240, 4)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO task_dependencies (task_id, blocked_by_task_id) VALUES (1, 2)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO task_dependencies (task_id, blocked_by_task_id) VALUES (3, 4)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (4, 1, 'Later', '2026-03-02T09:00:00', '2026-03-02T09:30:00', 'WILL')").execute(&pool).await.unwrap();
+
+        let now_dt = NaiveDateTime::parse_from_str("2026-03-01T09:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let preview = preview_day_plan_at(&pool, 1, now_dt).await.unwrap();
+
+        // 'Deploy' waits for 'Build'; 'Review' waits for a prerequisite only scheduled tomorrow
+        let planned: Vec<i64> = preview.planned.iter().map(|p| p.task_id).collect();
+        assert_eq!(planned, vec![2, 1]);
+        let skipped: Vec<i64> = preview.skipped.iter().map(|t| t.id).collect();
+        assert_eq!(skipped, vec![5, 3]);
+
+        let result = apply_day_plan_at(&pool, 1, vec![2, 5], now_dt).await;
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        let blocks: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE task_id IN (2, 5)").fetch_all(&pool).await.unwrap();
+        assert!(blocks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_every_insertion_keeps_prerequisites_first() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, position) VALUES (1, 1, 'Ship', 0), (2, 1, 'Test', 1), (3, 1, 'Announce', 2), (4, 1, 'Draft', 3)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO task_dependencies (task_id, blocked_by_task_id) VALUES (1, 2), (3, 4)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (10, 1, 1, 'Ship', '2026-03-01T10:00:00', '2026-03-01T10:30:00', 'WILL')").execute(&pool).await.unwrap();
+
+        // Moving the prerequisite from the inbox would land it after its scheduled dependent
+        let result = move_to_timeline(&pool, 2, 1, None).await;
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        let moved: Vec<TimeBlock> = sqlx::query_as("SELECT * FROM time_blocks WHERE task_id = 2").fetch_all(&pool).await.unwrap();
+        assert!(moved.is_empty());
+        reorder_blocks(&pool, 1, vec![10]).await.unwrap();
+
+        // The day planner schedules accepted prerequisites first, whatever order they were accepted in
+        sqlx::query("DELETE FROM time_blocks").execute(&pool).await.unwrap();
+        let now_dt = NaiveDateTime::parse_from_str("2026-03-01T09:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        apply_day_plan_at(&pool, 1, vec![3, 4], now_dt).await.unwrap();
+        let order: Vec<(i64,)> = sqlx::query_as("SELECT task_id FROM time_blocks ORDER BY start_time").fetch_all(&pool).await.unwrap();
+        assert_eq!(order, vec![(4,), (3,)]);
+
+        // Moving the whole inbox does the same with the inbox order
+        move_all_to_timeline(&pool, 1, None).await.unwrap();
+        let order: Vec<(i64,)> = sqlx::query_as("SELECT task_id FROM time_blocks WHERE task_id IN (1, 2) ORDER BY start_time").fetch_all(&pool).await.unwrap();
+        assert_eq!(order, vec![(2,), (1,)]);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_risk_follows_shifts() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Current')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (10, 1, 1, 'Current', '2026-03-01T09:00:00', '2026-03-01T10:00:00', 'NOW')").execute(&pool).await.unwrap();
+
+        let now_dt = NaiveDateTime::parse_from_str("2026-03-01T09:30:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let input = AddTaskInput {
+            workspace_id: 1,
+            title: "Report".to_string(),
+            planning_memo: None,
+            hours: 0,
+            minutes: 30,
+            is_urgent: false,
+            is_inbox: Some(false),
+            project_name: None,
+            label_name: None,
+            due_at: Some("2026-03-01T10:45".to_string()),
+            priority: None,
+            tags: None,
+            target_date: None,
+        };
+        add_task_at(&pool, input, now_dt).await.unwrap();
+        assert!(get_at_risk_tasks_at(&pool, 1, now_dt).await.unwrap().is_empty());
+
+        process_task_transition(&pool, TaskTransitionInput { block_id: 10, action: "DELAY".to_string(), extra_minutes: Some(30), review_memo: None }).await.unwrap();
+
+        let at_risk = get_at_risk_tasks_at(&pool, 1, now_dt).await.unwrap();
+        assert_eq!(at_risk.len(), 1);
+        assert_eq!(at_risk[0].title, "Report");
+        assert_eq!(at_risk[0].due_at.as_deref(), Some("2026-03-01T10:45:00"));
+
+        // Moving it back to the inbox clears the flag, and the inbox lists due tasks first
+        let report_block: (i64,) = sqlx::query_as("SELECT id FROM time_blocks WHERE title = 'Report'").fetch_one(&pool).await.unwrap();
+        move_to_inbox(&pool, report_block.0).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, position) VALUES (5, 1, 'No deadline', -1)").execute(&pool).await.unwrap();
+        assert!(get_at_risk_tasks_at(&pool, 1, now_dt).await.unwrap().is_empty());
+        let inbox = get_inbox_at(&pool, 1, None, None, now_dt).await.unwrap();
+        assert_eq!(inbox[0].title, "Report");
+        assert!(!inbox[0].is_at_risk);
+        assert_eq!(inbox[1].title, "No deadline");
+
+        // Once the due date passes, the task is at risk without any mutation refreshing the flag
+        let later = NaiveDateTime::parse_from_str("2026-03-01T11:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let at_risk = get_at_risk_tasks_at(&pool, 1, later).await.unwrap();
+        assert_eq!(at_risk.iter().map(|t| (t.title.as_str(), t.is_at_risk)).collect::<Vec<_>>(), vec![("Report", true)]);
+        assert!(get_inbox_at(&pool, 1, None, None, later).await.unwrap()[0].is_at_risk);
+    }
+
+    #[tokio::test]
+    async fn test_priority_insertion_and_inbox_sort() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, priority) VALUES (1, 1, 'Current', 2)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, priority) VALUES (2, 1, 'Normal', 2)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, priority) VALUES (3, 1, 'Someday', 3)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (1, 1, 'Current', '2026-03-01T09:00:00', '2026-03-01T10:00:00', 'NOW')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (2, 1, 'Normal', '2026-03-01T10:00:00', '2026-03-01T10:30:00', 'WILL')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (3, 1, 'Someday', '2026-03-01T10:30:00', '2026-03-01T11:00:00', 'WILL')").execute(&pool).await.unwrap();
+
+        let now_dt = NaiveDateTime::parse_from_str("2026-03-01T09:30:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let input = AddTaskInput {
+            workspace_id: 1,
+            title: "Important".to_string(),
+            planning_memo: None,
+            hours: 0,
+            minutes: 20,
+            is_urgent: false,
+            is_inbox: Some(false),
+            project_name: None,
+            label_name: None,
+            due_at: None,
+            priority: Some(1),
+            tags: None,
+            target_date: None,
+        };
+        add_task_at(&pool, input, now_dt).await.unwrap();
+
+        let blocks = database::timeline::get_timeline(&pool, 1, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(), "04:00").await.unwrap();
+        let order: Vec<(&str, &str, &str)> = blocks.iter().map(|b| (b.title.as_str(), b.status.as_str(), b.start_time.as_str())).collect();
+        assert_eq!(order, vec![
+            ("Current", "NOW", "2026-03-01T09:00:00"),
+            ("Important", "WILL", "2026-03-01T10:00:00"),
+            ("Normal", "WILL", "2026-03-01T10:20:00"),
+            ("Someday", "WILL", "2026-03-01T10:50:00"),
+        ]);
+        assert_eq!(blocks[1].priority, Some(1));
+
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, priority, position) VALUES (10, 1, 'Low first', 3, 0)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, priority, position) VALUES (11, 1, 'High second', 0, 1)").execute(&pool).await.unwrap();
+        let manual = get_inbox(&pool, 1, Some("manual".to_string()), None).await.unwrap();
+        assert_eq!(manual[0].title, "Low first");
+        let by_priority = get_inbox(&pool, 1, Some("priority".to_string()), None).await.unwrap();
+        assert_eq!(by_priority[0].title, "High second");
+        assert!(get_inbox(&pool, 1, Some("random".to_string()), None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dependencies_guard_reordering_and_inbox() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Write migration'), (2, 1, 'Deploy'), (3, 1, 'Announce')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (10, 1, 1, 'Write migration', '2026-03-01T09:00:00', '2026-03-01T10:00:00', 'WILL')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (11, 2, 1, 'Deploy', '2026-03-01T10:00:00', '2026-03-01T10:30:00', 'WILL')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO task_dependencies (task_id, blocked_by_task_id) VALUES (2, 1), (3, 2)").execute(&pool).await.unwrap();
+
+        let result = move_task_step(&pool, 1, 11, "up".to_string()).await;
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        assert!(move_task_to_priority(&pool, 1, 11).await.is_err());
+        let deploy: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = 11").fetch_one(&pool).await.unwrap();
+        assert_eq!(deploy.start_time, "2026-03-01T10:00:00");
+
+        let inbox = get_inbox(&pool, 1, None, None).await.unwrap();
+        assert_eq!(inbox.len(), 1);
+        assert!(inbox[0].is_blocked);
+
+        sqlx::query("UPDATE time_blocks SET status = 'DONE'").execute(&pool).await.unwrap();
+        assert!(!get_inbox(&pool, 1, None, None).await.unwrap()[0].is_blocked);
+    }
+
+    #[tokio::test]
+    async fn test_dependency_against_existing_order_keeps_scheduling_working() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Draft'), (2, 1, 'Review')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (10, 1, 1, 'Draft', '2026-03-01T09:00:00', '2026-03-01T09:30:00', 'WILL')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (11, 2, 1, 'Review', '2026-03-01T09:30:00', '2026-03-01T10:30:00', 'WILL')").execute(&pool).await.unwrap();
+
+        // Draft already runs first, so it cannot be made to wait for the review
+        let result = services::dependency::add_task_dependency(&pool, 1, 2).await;
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        assert!(services::dependency::get_task_dependencies(&pool, 1).await.unwrap().is_empty());
+
+        let now_dt = NaiveDateTime::parse_from_str("2026-03-01T08:30:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let input = AddTaskInput {
+            workspace_id: 1,
+            title: "Outage".to_string(),
+            planning_memo: None,
+            hours: 0,
+            minutes: 15,
+            is_urgent: false,
+            is_inbox: Some(false),
+            project_name: None,
+            label_name: None,
+            due_at: None,
+            priority: Some(0),
+            tags: None,
+            target_date: None,
+        };
+        add_task_at(&pool, input, now_dt).await.unwrap();
+        let blocks = database::timeline::get_timeline(&pool, 1, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(), "04:00").await.unwrap();
+        assert_eq!(blocks.iter().map(|b| b.title.as_str()).collect::<Vec<_>>(), vec!["Outage", "Draft", "Review"]);
+
+        // The order that matches the timeline is accepted
+        services::dependency::add_task_dependency(&pool, 2, 1).await.unwrap();
+        move_task_to_bottom(&pool, 1, 10).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_tags_are_attached_and_filterable() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        let now_dt = NaiveDateTime::parse_from_str("2026-03-01T09:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        for (title, tags) in [("Support call", vec![" support ", "+meeting", "support"]), ("Refactor", vec!["#code"])] {
+            let input = AddTaskInput {
+                workspace_id: 1,
+                title: title.to_string(),
+                planning_memo: None,
+                hours: 0,
+                minutes: 30,
+                is_urgent: false,
+                is_inbox: Some(true),
+                project_name: None,
+                label_name: None,
+                due_at: None,
+                priority: None,
+                tags: Some(tags.into_iter().map(String::from).collect()),
+                target_date: None,
+            };
+            add_task_at(&pool, input, now_dt).await.unwrap();
+        }
+
+        let inbox = get_inbox(&pool, 1, Some("manual".to_string()), None).await.unwrap();
+        assert_eq!(inbox[0].tags, vec!["meeting".to_string(), "support".to_string()]);
+        assert_eq!(inbox[1].tags, vec!["code".to_string()]);
+
+        let support_only = get_inbox(&pool, 1, None, Some("support".to_string())).await.unwrap();
+        assert_eq!(support_only.len(), 1);
+        assert_eq!(support_only[0].title, "Support call");
+
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (1, 1, 'Support call', '2026-03-01T09:00:00', '2026-03-01T09:45:00', 'DONE')").execute(&pool).await.unwrap();
+        let stats = database::tag::get_tag_stats(&pool, 1, "2026-03-01T04:00:00", "2026-03-02T03:59:00").await.unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].worked_minutes, 45);
+        assert_eq!(stats[0].task_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_projects_resolve_within_workspace_scope() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Work'), (2, 'Side')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO labels (id, workspace_id, name, color, last_used) VALUES (7, NULL, 'Focus', '#00FF00', '2026-03-01T08:00:00')").execute(&pool).await.unwrap();
+        let now_dt = NaiveDateTime::parse_from_str("2026-03-01T09:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        for workspace_id in [1, 2] {
+            let input = AddTaskInput {
+                workspace_id,
+                title: "Planning".to_string(),
+                planning_memo: None,
+                hours: 0,
+                minutes: 30,
+                is_urgent: false,
+                is_inbox: Some(true),
+                project_name: Some("Roadmap".to_string()),
+                label_name: Some("Focus".to_string()),
+                due_at: None,
+                priority: None,
+                tags: None,
+                target_date: None,
+            };
+            add_task_at(&pool, input, now_dt).await.unwrap();
+        }
+
+        let work = get_inbox(&pool, 1, None, None).await.unwrap();
+        let side = get_inbox(&pool, 2, None, None).await.unwrap();
+        assert_ne!(work[0].project_id, side[0].project_id);
+        assert_eq!(work[0].label_id, Some(7));
+        assert_eq!(side[0].label_id, Some(7));
+
+        let side_projects = database::workspace::get_projects(&pool, Some(2), false).await.unwrap();
+        assert_eq!(side_projects.len(), 1);
+        assert_eq!(side_projects[0].workspace_id, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_future_day_plan_is_kept_apart_from_today() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name, core_time_start, core_time_end) VALUES (1, 'Test', '10:00', '18:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'Tonight')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (10, 1, 1, 'Tonight', '2026-03-01T20:00:00', '2026-03-01T21:00:00', 'NOW')").execute(&pool).await.unwrap();
+
+        let now_dt = NaiveDateTime::parse_from_str("2026-03-01T20:30:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let task = |title: &str, minutes: i32, target_date: Option<&str>| AddTaskInput {
+            workspace_id: 1,
+            title: title.to_string(),
+            planning_memo: None,
+            hours: 0,
+            minutes,
+            is_urgent: false,
+            is_inbox: Some(false),
+            project_name: None,
+            label_name: None,
+            due_at: None,
+            priority: None,
+            tags: None,
+            target_date: target_date.map(String::from),
+        };
+        add_task_at(&pool, task("Plan A", 60, Some("2026-03-02")), now_dt).await.unwrap();
+        add_task_at(&pool, task("Plan B", 30, Some("2026-03-02")), now_dt).await.unwrap();
+        // Without a target date the task still joins tonight's plan, not tomorrow's.
+        add_task_at(&pool, task("Wrap up", 15, None), now_dt).await.unwrap();
+
+        let slots = |date: (i32, u32, u32)| {
+            let pool = pool.clone();
+            async move {
+                database::timeline::get_timeline(&pool, 1, NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(), "04:00").await.unwrap()
+                    .into_iter().map(|b| (b.title, b.start_time, b.status)).collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(slots((2026, 3, 2)).await, vec![
+            ("Plan A".to_string(), "2026-03-02T10:00:00".to_string(), "WILL".to_string()),
+            ("Plan B".to_string(), "2026-03-02T11:00:00".to_string(), "WILL".to_string()),
+        ]);
+        assert_eq!(slots((2026, 3, 1)).await[1], ("Wrap up".to_string(), "2026-03-01T21:00:00".to_string(), "WILL".to_string()));
+
+        // Running late tonight does not move tomorrow's plan.
+        process_task_transition(&pool, TaskTransitionInput { block_id: 10, action: "DELAY".to_string(), extra_minutes: Some(30), review_memo: None }).await.unwrap();
+        assert_eq!(slots((2026, 3, 1)).await[1].1, "2026-03-01T21:30:00");
+        assert_eq!(slots((2026, 3, 2)).await[0].1, "2026-03-02T10:00:00");
+
+        assert!(add_task_at(&pool, task("Yesterday", 30, Some("2026-02-28")), now_dt).await.is_err());
+        let urgent = AddTaskInput { is_urgent: true, ..task("Hotfix", 30, Some("2026-03-02")) };
+        assert!(add_task_at(&pool, urgent, now_dt).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_timeline_range_groups_by_logical_day() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO unplugged_times (workspace_id, label, start_time, end_time) VALUES (1, 'Lunch', '12:00', '13:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO projects (id, name, last_used) VALUES (1, 'will-done', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, project_id) VALUES (1, 1, 'Code', 1), (2, 1, 'Late night', NULL)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (task_id, workspace_id, title, start_time, end_time, status) VALUES (1, 1, 'Code', '2026-03-02T09:00:00', '2026-03-02T10:30:00', 'DONE'), (2, 1, 'Late night', '2026-03-03T02:00:00', '2026-03-03T02:30:00', 'DONE'), (1, 1, 'Code', '2026-03-04T09:00:00', '2026-03-04T09:45:00', 'WILL')").execute(&pool).await.unwrap();
+
+        let days = get_timeline_range(&pool, 1, "2026-03-01", "2026-03-04", None).await.unwrap();
+        assert_eq!(days.len(), 4);
+        assert_eq!(days[0].logical_date, "2026-03-01");
+        assert_eq!(days[0].blocks.len(), 1);
+        assert_eq!(days[0].blocks[0].status, "UNPLUGGED");
+
+        // 02:00 on the 3rd still belongs to the logical 2nd (day starts at 04:00).
+        assert_eq!(days[1].blocks.iter().map(|b| b.title.as_str()).collect::<Vec<_>>(), vec!["Code", "Lunch", "Late night"]);
+        assert_eq!(days[1].totals.by_status.get("DONE"), Some(&120));
+        assert_eq!(days[1].totals.by_status.get("UNPLUGGED"), Some(&60));
+        assert_eq!(days[1].totals.by_project.get("will-done"), Some(&90));
+        assert_eq!(days[3].totals.by_status.get("WILL"), Some(&45));
+
+        assert!(get_timeline_range(&pool, 1, "2026-03-04", "2026-03-01", None).await.is_err());
+        assert!(get_timeline_range(&pool, 1, "2026-01-01", "2026-03-31", None).await.is_err());
+    }
+
+    #[test]
+    fn test_logical_range_bounds_and_day_index() {
+        let range = logical_range("2026-03-01", "2026-03-02", 2, "04:00").unwrap();
+        assert_eq!((range.start_bound.as_str(), range.end_bound.as_str()), ("2026-03-01T04:00:00", "2026-03-03T03:59:00"));
+        assert_eq!(range.days().count(), 2);
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+        assert_eq!(range.day_index(at("2026-03-01T03:59:00")), None);
+        assert_eq!(range.day_index(at("2026-03-03T03:00:00")), Some(1));
+        assert_eq!(range.day_index(at("2026-03-03T04:00:00")), None);
+
+        assert!(matches!(logical_range("2026-03-01", "2026-03-03", 2, "04:00"), Err(AppError::InvalidInput(_))));
+        assert!(matches!(logical_range("2026-03-02", "2026-03-01", 2, "04:00"), Err(AppError::InvalidInput(_))));
+        assert!(matches!(logical_range("03/01/2026", "2026-03-01", 2, "04:00"), Err(AppError::DateParse(_))));
     }
 
     #[tokio::test]
@@ -753,6 +1964,9 @@ mod tests {
             review_memo: None,
             project_name: None,
             label_name: None,
+            due_at: None,
+            priority: None,
+            tags: None,
         };
         update_task(&pool, input).await.unwrap();
         let block_a: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = 10").fetch_one(&pool).await.unwrap();
@@ -834,6 +2048,69 @@ mod tests {
         assert_eq!(dates[1], "2020-01-03");
     }
 
+    #[tokio::test]
+    async fn test_pause_resume_pushes_following_blocks() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (1, 1, 'T1')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title) VALUES (2, 1, 'T2')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (10, 1, 1, 'T1', '2026-03-01T09:00:00', '2026-03-01T10:00:00', 'NOW')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (11, 2, 1, 'T2', '2026-03-01T10:00:00', '2026-03-01T11:00:00', 'WILL')").execute(&pool).await.unwrap();
+
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+
+        // Resuming a running block and pausing twice are rejected
+        assert!(resume_block_at(&pool, 10, at("2026-03-01T09:10:00")).await.is_err());
+        pause_block_at(&pool, 10, at("2026-03-01T09:20:00")).await.unwrap();
+        assert!(pause_block_at(&pool, 10, at("2026-03-01T09:25:00")).await.is_err());
+        resume_block_at(&pool, 10, at("2026-03-01T09:35:00")).await.unwrap();
+
+        let now_block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = 10").fetch_one(&pool).await.unwrap();
+        let next_block: TimeBlock = sqlx::query_as("SELECT * FROM time_blocks WHERE id = 11").fetch_one(&pool).await.unwrap();
+        assert_eq!(now_block.end_time, "2026-03-01T10:15:00");
+        assert_eq!(next_block.start_time, "2026-03-01T10:15:00");
+        assert_eq!(next_block.end_time, "2026-03-01T11:15:00");
+
+        let intervals: Vec<BlockInterval> = sqlx::query_as("SELECT * FROM block_intervals WHERE block_id = 10 ORDER BY started_at").fetch_all(&pool).await.unwrap();
+        assert_eq!(intervals.len(), 2);
+        assert_eq!(intervals[0].started_at, "2026-03-01T09:00:00");
+        assert_eq!(intervals[0].ended_at.as_deref(), Some("2026-03-01T09:20:00"));
+        assert!(intervals[1].ended_at.is_none());
+
+        // Paused again at 09:50 and viewed at 10:00: 15 + 10 minutes paused
+        pause_block_at(&pool, 10, at("2026-03-01T09:50:00")).await.unwrap();
+        let mut blocks = database::timeline::get_timeline(&pool, 1, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(), "04:00").await.unwrap();
+        let intervals = database::timeline::get_block_intervals_in_range(&pool, 1, "2026-03-01T04:00:00", "2026-03-02T03:59:00").await.unwrap();
+        apply_interval_stats(&mut blocks, &intervals, at("2026-03-01T10:00:00"));
+        assert_eq!(blocks[0].paused_minutes, 25);
+        assert!(blocks[0].is_paused);
+        assert_eq!(blocks[1].paused_minutes, 0);
+        assert!(!blocks[1].is_paused);
+    }
+
+    #[tokio::test]
+    async fn test_first_now_takes_plan_snapshot_once() {
+        let pool = setup_db().await;
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Test')").execute(&pool).await.unwrap();
+
+        let today = logical_date_of(Local::now().naive_local(), "04:00").format("%Y-%m-%d").to_string();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes) VALUES (1, 1, 'T1', 30)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, workspace_id, title, estimated_minutes) VALUES (2, 1, 'T2', 60)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (10, 1, 1, 'T1', ?1, ?2, 'WILL')")
+            .bind(format!("{}T10:00:00", today)).bind(format!("{}T10:30:00", today)).execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO time_blocks (id, task_id, workspace_id, title, start_time, end_time, status) VALUES (11, 2, 1, 'T2', ?1, ?2, 'WILL')")
+            .bind(format!("{}T10:30:00", today)).bind(format!("{}T11:30:00", today)).execute(&pool).await.unwrap();
+
+        update_block_status(&pool, 10, "NOW".to_string()).await.unwrap();
+        update_block_status(&pool, 11, "NOW".to_string()).await.unwrap();
+
+        let snapshots: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM plan_snapshots WHERE workspace_id = 1 AND logical_date = ?1").bind(&today).fetch_one(&pool).await.unwrap();
+        assert_eq!(snapshots.0, 1);
+
+        let planned: Vec<(i64, String, i64)> = sqlx::query_as("SELECT block_id, status, estimated_minutes FROM plan_snapshot_blocks ORDER BY block_id").fetch_all(&pool).await.unwrap();
+        assert_eq!(planned, vec![(10, "WILL".to_string(), 30), (11, "WILL".to_string(), 60)]);
+    }
+
     #[tokio::test]
     async fn test_past_task_completion_no_auto_promotion() {
         let pool = setup_db().await;
@@ -1,12 +1,19 @@
 use sqlx::SqlitePool;
-use crate::domain::{Workspace, UnpluggedTime, CreateWorkspaceInput, Project, Label, ProjectInput, LabelInput};
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use crate::domain::{Workspace, UnpluggedTime, CreateWorkspaceInput, Project, Label, ProjectInput, LabelInput, TaskOccurrence, TaskSuggestion, ProjectDetail, DuplicateGroup, PROJECT_STATUSES, ROLLOVER_POLICIES, OVERRUN_POLICIES, MAX_OVERRUN_EXTEND_MINUTES};
 use crate::database;
-use crate::domain::Result;
+use crate::database::workspace::Catalog;
+use crate::domain::{AppError, Result};
+
+/// Most recent matching tasks considered when ranking title suggestions.
+const SUGGESTION_SCAN_LIMIT: i64 = 200;
 
 pub async fn create_workspace(
     pool: &SqlitePool,
     input: CreateWorkspaceInput,
 ) -> Result<i64> {
+    let input = validate_workspace_input(input)?;
     database::workspace::create_workspace(pool, input).await
 }
 
@@ -27,9 +34,30 @@ pub async fn update_workspace(
     id: i64,
     input: CreateWorkspaceInput,
 ) -> Result<()> {
+    let input = validate_workspace_input(input)?;
     database::workspace::update_workspace(pool, id, input).await
 }
 
+/// Upper-cases the rollover and overrun policies and rejects unknown ones.
+fn validate_workspace_input(mut input: CreateWorkspaceInput) -> Result<CreateWorkspaceInput> {
+    if let Some(policy) = input.rollover_policy.as_mut() {
+        *policy = policy.trim().to_uppercase();
+        if !ROLLOVER_POLICIES.contains(&policy.as_str()) {
+            return Err(AppError::InvalidInput(format!("Unknown rollover policy: {}", policy)));
+        }
+    }
+    if let Some(policy) = input.overrun_policy.as_mut() {
+        *policy = policy.trim().to_uppercase();
+        if !OVERRUN_POLICIES.contains(&policy.as_str()) {
+            return Err(AppError::InvalidInput(format!("Unknown overrun policy: {}", policy)));
+        }
+    }
+    if input.overrun_extend_minutes.is_some_and(|m| !(1..=MAX_OVERRUN_EXTEND_MINUTES).contains(&m)) {
+        return Err(AppError::InvalidInput(format!("Overrun extension must be between 1 and {} minutes", MAX_OVERRUN_EXTEND_MINUTES)));
+    }
+    Ok(input)
+}
+
 pub async fn delete_workspace(pool: &SqlitePool, id: i64) -> Result<()> {
     database::workspace::delete_workspace(pool, id).await
 }
@@ -39,28 +67,104 @@ pub async fn suggest_task_titles(
     workspace_id: i64,
     query: &str,
     limit: i64,
-) -> Result<Vec<String>> {
-    database::workspace::search_task_titles(pool, workspace_id, query, limit).await
+) -> Result<Vec<TaskSuggestion>> {
+    let occurrences = database::workspace::search_task_occurrences(pool, workspace_id, query, SUGGESTION_SCAN_LIMIT).await?;
+    let mut suggestions = build_task_suggestions(occurrences);
+    suggestions.truncate(limit.max(0) as usize);
+    Ok(suggestions)
 }
 
-pub async fn get_projects(pool: &SqlitePool) -> Result<Vec<Project>> {
-    database::workspace::get_projects(pool).await
+/// Groups occurrences (newest first) by title. The most recent occurrence provides the
+/// project/label, and the most recent non-empty memo is reused as the planning memo.
+/// Ranked by frequency, then recency.
+fn build_task_suggestions(occurrences: Vec<TaskOccurrence>) -> Vec<TaskSuggestion> {
+    let mut order: Vec<String> = Vec::new();
+    let mut groups: HashMap<String, Vec<TaskOccurrence>> = HashMap::new();
+    for occurrence in occurrences {
+        if !groups.contains_key(&occurrence.title) {
+            order.push(occurrence.title.clone());
+        }
+        groups.entry(occurrence.title.clone()).or_default().push(occurrence);
+    }
+
+    let mut suggestions: Vec<TaskSuggestion> = order.into_iter().map(|title| {
+        let list = groups.remove(&title).unwrap_or_default();
+        let mut durations: Vec<i64> = list.iter().filter_map(|o| o.actual_minutes).filter(|&m| m > 0).collect();
+        durations.sort_unstable();
+        let median_minutes = match durations.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(durations[n / 2]),
+            n => Some((durations[n / 2 - 1] + durations[n / 2]) / 2),
+        };
+        let latest = &list[0];
+        TaskSuggestion {
+            title,
+            occurrences: list.len() as i64,
+            median_minutes,
+            project_name: latest.project_name.clone(),
+            label_name: latest.label_name.clone(),
+            planning_memo: list.iter().filter_map(|o| o.planning_memo.clone()).find(|m| !m.trim().is_empty()),
+            last_used: list.iter().filter_map(|o| o.last_used.clone()).max(),
+        }
+    }).collect();
+
+    // Stable sort keeps the recency order among equally frequent titles
+    suggestions.sort_by(|a, b| b.occurrences.cmp(&a.occurrences).then_with(|| b.last_used.cmp(&a.last_used)));
+    suggestions
+}
+
+
+pub async fn get_projects(pool: &SqlitePool, workspace_id: Option<i64>, include_archived: bool) -> Result<Vec<Project>> {
+    database::workspace::get_projects(pool, workspace_id, include_archived).await
 }
 
 pub async fn create_project(pool: &SqlitePool, input: ProjectInput) -> Result<i64> {
-    database::workspace::create_project(pool, input).await
+    database::workspace::create_project(pool, validate_project_input(input)?).await
 }
 
 pub async fn update_project(pool: &SqlitePool, id: i64, input: ProjectInput) -> Result<()> {
-    database::workspace::update_project(pool, id, input).await
+    database::workspace::update_project(pool, id, validate_project_input(input)?).await
+}
+
+pub async fn get_project_detail(pool: &SqlitePool, id: i64) -> Result<ProjectDetail> {
+    let project = database::workspace::get_project(pool, id).await?.ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+    let (total_minutes, task_count, first_activity, last_activity) = database::workspace::get_project_totals(pool, id).await?;
+    Ok(ProjectDetail { project, total_minutes, task_count, first_activity, last_activity })
+}
+
+/// Upper-cases the status and checks it and the `YYYY-MM-DD` tenure dates.
+/// Empty strings pass through since they clear the field on update.
+fn validate_project_input(mut input: ProjectInput) -> Result<ProjectInput> {
+    if let Some(status) = input.status.as_mut() {
+        *status = status.trim().to_uppercase();
+        if !PROJECT_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::InvalidInput(format!("Unknown project status: {}", status)));
+        }
+    }
+    let parse = |date: &Option<String>| -> Result<Option<NaiveDate>> {
+        match date.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+            Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d").map(Some).map_err(|_| AppError::DateParse(format!("Invalid project date: {}", d))),
+            None => Ok(None),
+        }
+    };
+    if let (Some(start), Some(end)) = (parse(&input.start_date)?, parse(&input.end_date)?) {
+        if end < start {
+            return Err(AppError::InvalidInput("Project end date is before its start date.".to_string()));
+        }
+    }
+    Ok(input)
+}
+
+pub async fn set_project_scope(pool: &SqlitePool, id: i64, workspace_id: Option<i64>) -> Result<()> {
+    set_catalog_scope(pool, Catalog::Project, id, workspace_id).await
 }
 
 pub async fn delete_project(pool: &SqlitePool, id: i64) -> Result<()> {
     database::workspace::delete_project(pool, id).await
 }
 
-pub async fn get_labels(pool: &SqlitePool) -> Result<Vec<Label>> {
-    database::workspace::get_labels(pool).await
+pub async fn get_labels(pool: &SqlitePool, workspace_id: Option<i64>) -> Result<Vec<Label>> {
+    database::workspace::get_labels(pool, workspace_id).await
 }
 
 pub async fn create_label(pool: &SqlitePool, input: LabelInput) -> Result<i64> {
@@ -71,6 +175,209 @@ pub async fn update_label(pool: &SqlitePool, id: i64, input: LabelInput) -> Resu
     database::workspace::update_label(pool, id, input).await
 }
 
+pub async fn set_label_scope(pool: &SqlitePool, id: i64, workspace_id: Option<i64>) -> Result<()> {
+    set_catalog_scope(pool, Catalog::Label, id, workspace_id).await
+}
+
 pub async fn delete_label(pool: &SqlitePool, id: i64) -> Result<()> {
     database::workspace::delete_label(pool, id).await
 }
+
+pub async fn find_duplicate_projects(pool: &SqlitePool, workspace_id: Option<i64>) -> Result<Vec<DuplicateGroup<Project>>> {
+    let projects = database::workspace::get_projects(pool, workspace_id, true).await?;
+    Ok(group_duplicates(projects, |p| &p.name))
+}
+
+pub async fn find_duplicate_labels(pool: &SqlitePool, workspace_id: Option<i64>) -> Result<Vec<DuplicateGroup<Label>>> {
+    let labels = database::workspace::get_labels(pool, workspace_id).await?;
+    Ok(group_duplicates(labels, |l| &l.name))
+}
+
+pub async fn merge_projects(pool: &SqlitePool, source_id: i64, target_id: i64) -> Result<()> {
+    merge_catalog(pool, Catalog::Project, source_id, target_id).await
+}
+
+pub async fn merge_labels(pool: &SqlitePool, source_id: i64, target_id: i64) -> Result<()> {
+    merge_catalog(pool, Catalog::Label, source_id, target_id).await
+}
+
+pub async fn reassign_tasks_project(pool: &SqlitePool, task_ids: Vec<i64>, project_id: Option<i64>) -> Result<()> {
+    reassign_tasks(pool, Catalog::Project, task_ids, project_id).await
+}
+
+pub async fn reassign_tasks_label(pool: &SqlitePool, task_ids: Vec<i64>, label_id: Option<i64>) -> Result<()> {
+    reassign_tasks(pool, Catalog::Label, task_ids, label_id).await
+}
+
+/// Scoping a row to one workspace is only allowed while no other workspace's tasks use it,
+/// and the name must be free in the target scope.
+async fn set_catalog_scope(pool: &SqlitePool, catalog: Catalog, id: i64, workspace_id: Option<i64>) -> Result<()> {
+    database::workspace::get_catalog_scope(pool, catalog, id).await?.ok_or_else(|| AppError::NotFound(format!("{:?} {} not found", catalog, id)))?;
+    if let Some(workspace_id) = workspace_id {
+        if database::workspace::count_catalog_tasks_outside(pool, catalog, id, workspace_id).await? > 0 {
+            return Err(AppError::InvalidInput("Tasks in other workspaces still use this entry.".to_string()));
+        }
+    }
+    if database::workspace::is_catalog_name_taken(pool, catalog, id, workspace_id).await? {
+        return Err(AppError::InvalidInput("The target scope already has an entry with this name.".to_string()));
+    }
+    database::workspace::set_catalog_scope(pool, catalog, id, workspace_id).await
+}
+
+/// Merging into a workspace-local row is only allowed when every affected task
+/// belongs to that workspace; a shared target accepts anything.
+async fn merge_catalog(pool: &SqlitePool, catalog: Catalog, source_id: i64, target_id: i64) -> Result<()> {
+    if source_id == target_id {
+        return Err(AppError::InvalidInput("Cannot merge an entry into itself.".to_string()));
+    }
+    database::workspace::get_catalog_scope(pool, catalog, source_id).await?.ok_or_else(|| AppError::NotFound(format!("{:?} {} not found", catalog, source_id)))?;
+    let target_scope = database::workspace::get_catalog_scope(pool, catalog, target_id).await?.ok_or_else(|| AppError::NotFound(format!("{:?} {} not found", catalog, target_id)))?;
+    if let Some(workspace_id) = target_scope {
+        if database::workspace::count_catalog_tasks_outside(pool, catalog, source_id, workspace_id).await? > 0 {
+            return Err(AppError::InvalidInput("The merge target belongs to another workspace than some of the source's tasks.".to_string()));
+        }
+    }
+    database::workspace::merge_catalog(pool, catalog, source_id, target_id).await
+}
+
+async fn reassign_tasks(pool: &SqlitePool, catalog: Catalog, task_ids: Vec<i64>, target_id: Option<i64>) -> Result<()> {
+    if let Some(target_id) = target_id {
+        let target_scope = database::workspace::get_catalog_scope(pool, catalog, target_id).await?.ok_or_else(|| AppError::NotFound(format!("{:?} {} not found", catalog, target_id)))?;
+        if let Some(workspace_id) = target_scope {
+            if database::workspace::count_tasks_outside(pool, &task_ids, workspace_id).await? > 0 {
+                return Err(AppError::InvalidInput("Some tasks belong to another workspace than the target.".to_string()));
+            }
+        }
+    }
+    database::workspace::reassign_tasks(pool, catalog, &task_ids, target_id).await
+}
+
+/// Case, whitespace and punctuation-insensitive key, so `will-done`,
+/// `Will Done` and `willdone` collide.
+fn duplicate_key(name: &str) -> String {
+    let key: String = name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect();
+    if key.is_empty() { name.trim().to_lowercase() } else { key }
+}
+
+/// Groups with more than one member, ordered by key; members keep their input order.
+fn group_duplicates<T>(items: Vec<T>, name: impl Fn(&T) -> &str) -> Vec<DuplicateGroup<T>> {
+    let mut groups: Vec<DuplicateGroup<T>> = Vec::new();
+    for item in items {
+        let key = duplicate_key(name(&item));
+        match groups.iter_mut().find(|g| g.key == key) {
+            Some(group) => group.items.push(item),
+            None => groups.push(DuplicateGroup { key, items: vec![item] }),
+        }
+    }
+    groups.retain(|g| g.items.len() > 1);
+    groups.sort_by(|a, b| a.key.cmp(&b.key));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occurrence(task_id: i64, title: &str, actual_minutes: Option<i64>, last_used: Option<&str>) -> TaskOccurrence {
+        TaskOccurrence {
+            task_id,
+            title: title.to_string(),
+            planning_memo: None,
+            project_name: None,
+            label_name: None,
+            actual_minutes,
+            last_used: last_used.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_build_task_suggestions_ranking_and_median() {
+        let mut latest_review = occurrence(5, "Code review", Some(40), Some("2026-03-05T09:00:00"));
+        latest_review.project_name = Some("will-done".to_string());
+        let mut older_review = occurrence(2, "Code review", Some(20), Some("2026-03-02T09:00:00"));
+        older_review.planning_memo = Some("Check the migration PR".to_string());
+
+        let suggestions = build_task_suggestions(vec![
+            occurrence(6, "Standup", Some(15), Some("2026-03-06T09:00:00")),
+            latest_review,
+            occurrence(4, "Code review", None, None),
+            occurrence(3, "Code review", Some(90), Some("2026-03-03T09:00:00")),
+            older_review,
+        ]);
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].title, "Code review");
+        assert_eq!(suggestions[0].occurrences, 4);
+        assert_eq!(suggestions[0].median_minutes, Some(40));
+        assert_eq!(suggestions[0].project_name.as_deref(), Some("will-done"));
+        assert_eq!(suggestions[0].planning_memo.as_deref(), Some("Check the migration PR"));
+        assert_eq!(suggestions[0].last_used.as_deref(), Some("2026-03-05T09:00:00"));
+        assert_eq!(suggestions[1].title, "Standup");
+        assert_eq!(suggestions[1].median_minutes, Some(15));
+    }
+
+    #[test]
+    fn test_validate_project_input() {
+        let input = ProjectInput { name: "will-done".to_string(), status: Some(" archived ".to_string()), start_date: Some("2026-01-01".to_string()), end_date: Some("".to_string()), ..Default::default() };
+        assert_eq!(validate_project_input(input).unwrap().status.as_deref(), Some("ARCHIVED"));
+
+        let bad_status = ProjectInput { name: "x".to_string(), status: Some("paused".to_string()), ..Default::default() };
+        assert!(validate_project_input(bad_status).is_err());
+
+        let reversed = ProjectInput { name: "x".to_string(), start_date: Some("2026-05-01".to_string()), end_date: Some("2026-04-01".to_string()), ..Default::default() };
+        assert!(validate_project_input(reversed).is_err());
+    }
+
+    #[test]
+    fn test_validate_workspace_input_bounds_overrun_extension() {
+        let input = |minutes: i64| CreateWorkspaceInput {
+            name: "Work".to_string(),
+            core_time_start: None,
+            core_time_end: None,
+            role_intro: None,
+            unplugged_times: Vec::new(),
+            rollover_policy: None,
+            overrun_policy: Some(" extend ".to_string()),
+            overrun_extend_minutes: Some(minutes),
+        };
+        assert_eq!(validate_workspace_input(input(MAX_OVERRUN_EXTEND_MINUTES)).unwrap().overrun_policy.as_deref(), Some("EXTEND"));
+        for minutes in [0, MAX_OVERRUN_EXTEND_MINUTES + 1, i64::MAX] {
+            assert!(validate_workspace_input(input(minutes)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_group_duplicates_ignores_case_spacing_and_punctuation() {
+        let names = vec!["will-done", "Side", "Will Done", "willdone", "side ", "Reading"];
+        let groups = group_duplicates(names, |n| n);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].key, "side");
+        assert_eq!(groups[0].items, vec!["Side", "side "]);
+        assert_eq!(groups[1].key, "willdone");
+        assert_eq!(groups[1].items, vec!["will-done", "Will Done", "willdone"]);
+    }
+
+    #[tokio::test]
+    async fn test_set_catalog_scope_guards_other_workspaces_and_names() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE workspaces (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL)").execute(&pool).await.unwrap();
+        sqlx::query(database::workspace::CREATE_LABELS_TABLE).execute(&pool).await.unwrap();
+        sqlx::query(database::workspace::CREATE_LABELS_SCOPE_INDEX).execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, workspace_id INTEGER NOT NULL, title TEXT NOT NULL, project_id INTEGER, label_id INTEGER)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name) VALUES (1, 'Work'), (2, 'Side')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO labels (id, workspace_id, name, color, last_used) VALUES (1, NULL, 'Bug', '#f00', '2026-03-01T09:00:00'), (2, 1, 'Meeting', '#00f', '2026-03-01T09:00:00'), (3, NULL, 'Meeting', '#0f0', '2026-03-01T09:00:00')").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (workspace_id, title, label_id) VALUES (1, 'A', 1), (2, 'B', 1)").execute(&pool).await.unwrap();
+
+        // Workspace 2 still has a task labelled "Bug"
+        assert!(matches!(set_label_scope(&pool, 1, Some(1)).await, Err(AppError::InvalidInput(_))));
+        // A shared "Meeting" already exists
+        assert!(matches!(set_label_scope(&pool, 2, None).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(set_label_scope(&pool, 9, None).await, Err(AppError::NotFound(_))));
+
+        sqlx::query("UPDATE tasks SET label_id = NULL WHERE workspace_id = 2").execute(&pool).await.unwrap();
+        set_label_scope(&pool, 1, Some(1)).await.unwrap();
+        set_label_scope(&pool, 2, Some(2)).await.unwrap();
+        let scopes: Vec<(i64, Option<i64>)> = sqlx::query_as("SELECT id, workspace_id FROM labels ORDER BY id").fetch_all(&pool).await.unwrap();
+        assert_eq!(scopes, vec![(1, Some(1)), (2, Some(2)), (3, None)]);
+    }
+}
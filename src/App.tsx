@@ -68,6 +68,7 @@ function AppContent() {
     logicalDate,
     selectedDate,
     setSelectedDate,
+    plannedDate,
     transitionBlock,
     setTransitionBlock,
     achievementOpen,
@@ -102,7 +103,7 @@ function AppContent() {
     })
   );
 
-  const isPastView = !!selectedDate && selectedDate.toDateString() !== logicalDate.toDateString();
+  const isPastView = !!selectedDate && !plannedDate && selectedDate.toDateString() !== logicalDate.toDateString();
 
   if (view === "loading") {
     return (
@@ -206,13 +207,13 @@ function AppContent() {
               }}
               onMoveAllToTimeline={async () => {
                 if (activeWorkspaceId) {
-                  await workspaceApi.moveAllToTimeline(activeWorkspaceId);
+                  await workspaceApi.moveAllToTimeline(activeWorkspaceId, plannedDate ?? undefined);
                   fetchMainData();
                 }
               }}
               onMoveToTimeline={async (taskId) => {
                 if (activeWorkspaceId) {
-                  await workspaceApi.moveToTimeline(taskId, activeWorkspaceId);
+                  await workspaceApi.moveToTimeline(taskId, activeWorkspaceId, plannedDate ?? undefined);
                   fetchMainData();
                 }
               }}
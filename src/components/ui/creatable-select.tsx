@@ -9,6 +9,7 @@ export function CreatableSelect({
   onChange,
   placeholder,
   fetchCommand,
+  fetchArgs,
   className,
   noneLabel = "None",
   createLabel = (val) => `Create "${val}"`
@@ -17,6 +18,7 @@ export function CreatableSelect({
   onChange: (val: string) => void;
   placeholder: string;
   fetchCommand: string;
+  fetchArgs?: Record<string, unknown>;
   className?: string;
   noneLabel?: string;
   createLabel?: (val: string) => string;
@@ -28,7 +30,7 @@ export function CreatableSelect({
   useEffect(() => {
     const fetchOptions = async () => {
       try {
-        const data = await invoke<any[]>(fetchCommand);
+        const data = await invoke<any[]>(fetchCommand, fetchArgs);
         setOptions(data);
       } catch (err) {
         console.error("Failed to fetch options", err);
@@ -38,7 +40,7 @@ export function CreatableSelect({
       setSearchTerm("");
       fetchOptions();
     }
-  }, [isOpen, fetchCommand]);
+  }, [isOpen, fetchCommand, fetchArgs?.workspaceId]);
 
   const handleSelect = (val: string) => {
     onChange(val);
@@ -11,6 +11,12 @@ export interface GenerateAchievementParams {
   forceRetry: boolean;
   overwrite: boolean;
   targetModel: string | null;
+  options?: AchievementOptions | null;
+}
+
+export interface AchievementOptions {
+  tags?: string[] | null;
+  includeRetrospectives?: boolean; // Adds the period's retrospective notes as context
 }
 
 export const achievementApi = {
@@ -0,0 +1,47 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface TaskEstimate {
+  taskId: number;
+  title: string;
+  projectName: string | null;
+  labelName: string | null;
+  estimatedMinutes: number;
+  actualMinutes: number;
+  lastStartTime: string;
+  logicalDate: string;
+  ratio: number;
+  isOverrun: boolean;
+}
+
+export interface EstimateGroup {
+  name: string | null;
+  taskCount: number;
+  estimatedMinutes: number;
+  actualMinutes: number;
+  ratio: number;
+  overrunCount: number;
+}
+
+export interface DailyAccuracy {
+  logicalDate: string;
+  plannedMinutes: number | null;
+  plannedTaskCount: number;
+  completedPlannedTaskCount: number;
+  actualMinutes: number;
+  estimatedMinutes: number;
+  ratio: number;
+  overrunCount: number;
+}
+
+export interface EstimationAccuracyReport {
+  overall: EstimateGroup;
+  tasks: TaskEstimate[];
+  byProject: EstimateGroup[];
+  byLabel: EstimateGroup[];
+  daily: DailyAccuracy[];
+}
+
+export const analyticsApi = {
+  getEstimationAccuracy: (workspaceId: number, startDate: string, endDate: string) =>
+    invoke<EstimationAccuracyReport>("get_estimation_accuracy", { workspaceId, startDate, endDate }),
+};
@@ -0,0 +1,43 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ChecklistItem {
+  id: number;
+  taskId: number;
+  title: string;
+  position: number;
+  isChecked: boolean;
+  estimatedMinutes: number | null;
+  checkedAt: string | null;
+}
+
+export interface ChecklistItemInput {
+  taskId: number;
+  title: string;
+  estimatedMinutes: number | null;
+}
+
+export interface UpdateChecklistItemInput {
+  id: number;
+  title: string;
+  estimatedMinutes: number | null;
+}
+
+export const checklistApi = {
+  getChecklist: (taskId: number) =>
+    invoke<ChecklistItem[]>("get_checklist", { taskId }),
+
+  addChecklistItem: (input: ChecklistItemInput) =>
+    invoke<ChecklistItem>("add_checklist_item", { input }),
+
+  updateChecklistItem: (input: UpdateChecklistItemInput) =>
+    invoke<void>("update_checklist_item", { input }),
+
+  toggleChecklistItem: (id: number, checked: boolean) =>
+    invoke<void>("toggle_checklist_item", { id, checked }),
+
+  deleteChecklistItem: (id: number) =>
+    invoke<void>("delete_checklist_item", { id }),
+
+  reorderChecklist: (taskId: number, itemIds: number[]) =>
+    invoke<void>("reorder_checklist", { taskId, itemIds }),
+};
@@ -0,0 +1,17 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface TaskDependency {
+  taskId: number;
+  blockedByTaskId: number;
+}
+
+export const dependencyApi = {
+  getTaskDependencies: (workspaceId: number) =>
+    invoke<TaskDependency[]>("get_task_dependencies", { workspaceId }),
+
+  addTaskDependency: (taskId: number, blockedByTaskId: number) =>
+    invoke<void>("add_task_dependency", { taskId, blockedByTaskId }),
+
+  removeTaskDependency: (taskId: number, blockedByTaskId: number) =>
+    invoke<void>("remove_task_dependency", { taskId, blockedByTaskId }),
+};
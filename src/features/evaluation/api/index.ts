@@ -0,0 +1,15 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Achievement, Competency, CompetencyInput, SelfEvaluationInput } from "@/types";
+
+export const evaluationApi = {
+  getCompetencies: (workspaceId: number) =>
+    invoke<Competency[]>("get_competencies", { workspaceId }),
+
+  /** Replaces the whole framework, in the given order. */
+  saveCompetencies: (workspaceId: number, competencies: CompetencyInput[]) =>
+    invoke<Competency[]>("save_competencies", { workspaceId, competencies }),
+
+  /** Saved as an achievement of type SELF_EVALUATION under `dateLabel`. */
+  generateSelfEvaluation: (input: SelfEvaluationInput) =>
+    invoke<Achievement>("generate_self_evaluation", { input }),
+};
@@ -0,0 +1,20 @@
+import { invoke } from "@tauri-apps/api/core";
+import { FocusSession, StartFocusInput, FocusDayStats } from "@/types";
+
+export const focusApi = {
+  /** Starts a session inside the NOW block; the backend closes it and inserts the break. */
+  startFocusSession: (input: StartFocusInput) =>
+    invoke<FocusSession>("start_focus_session", { input }),
+
+  getActiveFocusSession: (workspaceId: number) =>
+    invoke<FocusSession | null>("get_active_focus_session", { workspaceId }),
+
+  interruptFocusSession: (id: number) =>
+    invoke<FocusSession>("interrupt_focus_session", { id }),
+
+  cancelFocusSession: (id: number) =>
+    invoke<FocusSession>("cancel_focus_session", { id }),
+
+  getFocusStats: (workspaceId: number, startDate: string, endDate: string) =>
+    invoke<FocusDayStats[]>("get_focus_stats", { workspaceId, startDate, endDate }),
+};
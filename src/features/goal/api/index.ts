@@ -0,0 +1,42 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Goal, GoalInput, KeyResultInput, KeyResultCheckIn, CheckInInput } from "@/types";
+
+export const goalApi = {
+  /** Goals with their key results and computed progress. */
+  getGoals: (workspaceId: number) =>
+    invoke<Goal[]>("get_goals", { workspaceId }),
+
+  getGoal: (id: number) =>
+    invoke<Goal>("get_goal", { id }),
+
+  createGoal: (input: GoalInput) =>
+    invoke<number>("create_goal", { input }),
+
+  updateGoal: (id: number, input: GoalInput) =>
+    invoke<void>("update_goal", { id, input }),
+
+  deleteGoal: (id: number) =>
+    invoke<void>("delete_goal", { id }),
+
+  createKeyResult: (input: KeyResultInput) =>
+    invoke<number>("create_key_result", { input }),
+
+  updateKeyResult: (id: number, input: KeyResultInput) =>
+    invoke<void>("update_key_result", { id, input }),
+
+  deleteKeyResult: (id: number) =>
+    invoke<void>("delete_key_result", { id }),
+
+  addCheckIn: (input: CheckInInput) =>
+    invoke<number>("add_key_result_check_in", { input }),
+
+  getCheckIns: (keyResultId: number) =>
+    invoke<KeyResultCheckIn[]>("get_key_result_check_ins", { keyResultId }),
+
+  deleteCheckIn: (id: number) =>
+    invoke<void>("delete_key_result_check_in", { id }),
+
+  /** Pass null to unlink the task. */
+  setTaskKeyResult: (taskId: number, keyResultId: number | null) =>
+    invoke<void>("set_task_key_result", { taskId, keyResultId }),
+};
@@ -0,0 +1,8 @@
+import { invoke } from "@tauri-apps/api/core";
+import { InterruptionReport } from "@/types";
+
+export const interruptionApi = {
+  /** Interruptions are recorded by the backend whenever an urgent task cuts the NOW block. */
+  getInterruptionReport: (workspaceId: number, startDate: string, endDate: string) =>
+    invoke<InterruptionReport>("get_interruption_report", { workspaceId, startDate, endDate }),
+};
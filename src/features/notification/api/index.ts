@@ -0,0 +1,11 @@
+import { invoke } from "@tauri-apps/api/core";
+import { NotificationSettings, NotificationSettingsInput } from "@/types";
+
+export const notificationApi = {
+  /** Defaults apply until the workspace saves its own settings. */
+  getNotificationSettings: (workspaceId: number) =>
+    invoke<NotificationSettings>("get_notification_settings", { workspaceId }),
+
+  updateNotificationSettings: (workspaceId: number, input: NotificationSettingsInput) =>
+    invoke<NotificationSettings>("update_notification_settings", { workspaceId, input }),
+};
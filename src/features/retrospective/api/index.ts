@@ -0,0 +1,17 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Retrospective, SaveRetrospectiveInput } from "@/types";
+
+export const retrospectiveApi = {
+  /** Creates or replaces the entry of the given logical day. */
+  saveRetrospective: (input: SaveRetrospectiveInput) =>
+    invoke<Retrospective>("save_retrospective", { input }),
+
+  getRetrospective: (workspaceId: number, logicalDate: string) =>
+    invoke<Retrospective | null>("get_retrospective", { workspaceId, logicalDate }),
+
+  getRetrospectives: (workspaceId: number, startDate: string, endDate: string) =>
+    invoke<Retrospective[]>("get_retrospectives", { workspaceId, startDate, endDate }),
+
+  deleteRetrospective: (workspaceId: number, logicalDate: string) =>
+    invoke<void>("delete_retrospective", { workspaceId, logicalDate }),
+};
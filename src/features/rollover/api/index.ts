@@ -0,0 +1,14 @@
+import { invoke } from "@tauri-apps/api/core";
+import { RolloverReport } from "@/types";
+
+export const rolloverApi = {
+  /** Applies the workspace's rollover policy once per logical day; null when there was nothing to roll over. */
+  runRollover: (workspaceId: number) =>
+    invoke<RolloverReport | null>("run_rollover", { workspaceId }),
+
+  getRolloverReports: (workspaceId: number, pendingOnly?: boolean) =>
+    invoke<RolloverReport[]>("get_rollover_reports", { workspaceId, pendingOnly: pendingOnly ?? null }),
+
+  acknowledgeRolloverReport: (id: number) =>
+    invoke<void>("acknowledge_rollover_report", { id }),
+};
@@ -0,0 +1,34 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type SearchKind = "TASK" | "REVIEW" | "ACHIEVEMENT";
+
+export interface SearchQuery {
+  text: string;
+  workspaceId?: number | null;
+  projectId?: number | null;
+  labelId?: number | null;
+  startDate?: string | null; // "YYYY-MM-DD" (logical day)
+  endDate?: string | null;   // "YYYY-MM-DD" (logical day)
+  kinds?: SearchKind[] | null;
+  limit?: number | null;
+}
+
+export interface SearchHit {
+  kind: SearchKind;
+  refId: number;
+  workspaceId: number;
+  taskId: number | null;
+  /** Matches are wrapped in <mark>…</mark>. */
+  title: string;
+  /** Matches are wrapped in <mark>…</mark>. */
+  snippet: string;
+  occurredAt: string | null;
+  projectName: string | null;
+  labelName: string | null;
+  score: number;
+}
+
+export const searchApi = {
+  search: (query: SearchQuery) =>
+    invoke<SearchHit[]>("search", { query }),
+};
@@ -0,0 +1,8 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Standup, StandupInput } from "@/types";
+
+export const standupApi = {
+  /** Returns both a Markdown and a Slack-style text version, ready to copy. */
+  generateStandup: (input: StandupInput) =>
+    invoke<Standup>("generate_standup", { input }),
+};
@@ -0,0 +1,22 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface Tag {
+  id: number;
+  workspaceId: number;
+  name: string;
+  lastUsed: string;
+}
+
+export interface TagStat {
+  name: string;
+  workedMinutes: number;
+  taskCount: number;
+}
+
+export const tagApi = {
+  getTags: (workspaceId: number) =>
+    invoke<Tag[]>("get_tags", { workspaceId }),
+
+  getTagStats: (workspaceId: number, startDate: string, endDate: string) =>
+    invoke<TagStat[]>("get_tag_stats", { workspaceId, startDate, endDate }),
+};
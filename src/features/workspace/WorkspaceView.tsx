@@ -59,7 +59,7 @@ export const WorkspaceView = ({
   } = useWorkspace({ t, user, currentTime, logicalDate, selectedDate, onDateChange, timeline, onTaskSubmit, onEditTaskSubmit });
 
   const dailyProgress = calculateProgress();
-  const isPastView = !!selectedDate && format(selectedDate, "yyyy-MM-dd") !== format(logicalDate, "yyyy-MM-dd");
+  const isPastView = !!selectedDate && format(selectedDate, "yyyy-MM-dd") < format(logicalDate, "yyyy-MM-dd");
 
   if (workspacesCount === 0) return <WorkspaceEmptyState t={t} onCreateWorkspace={onCreateWorkspace} />;
 
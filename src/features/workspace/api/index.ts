@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import { Workspace, TimeBlock, Task } from "@/types";
+import { Workspace, TimeBlock, TimelineDay, Task, Project, ProjectDetail, ProjectStatus, Label } from "@/types";
 
 export interface AddTaskInput {
   workspaceId: number;
@@ -11,6 +11,54 @@ export interface AddTaskInput {
   isInbox: boolean | null;
   projectName: string | null;
   labelName: string | null;
+  dueAt?: string | null;
+  priority?: number | null;
+  tags?: string[] | null;
+  /** Logical date "YYYY-MM-DD" to plan the task on; today when omitted. */
+  targetDate?: string | null;
+}
+
+/** On update, omitted fields are kept and empty strings clear them. */
+export interface ProjectInput {
+  name: string;
+  workspaceId?: number | null;
+  description?: string | null;
+  role?: string | null;
+  techStack?: string | null;
+  link?: string | null;
+  startDate?: string | null;
+  endDate?: string | null;
+  status?: ProjectStatus | null;
+}
+
+export interface DuplicateGroup<T> {
+  key: string;
+  items: T[];
+}
+
+export type InboxSort = "due" | "priority" | "manual";
+
+export interface QuickAddResult {
+  input: AddTaskInput;
+  diagnostics: string[];
+}
+
+export interface PlannedTask {
+  taskId: number;
+  title: string;
+  minutes: number;
+  startTime: string;
+  endTime: string;
+}
+
+export interface DayPlanPreview {
+  windowStart: string;
+  windowEnd: string;
+  capacityMinutes: number;
+  scheduledMinutes: number;
+  remainingMinutes: number;
+  planned: PlannedTask[];
+  skipped: Task[];
 }
 
 export interface TaskTransitionInput {
@@ -29,6 +77,9 @@ export interface UpdateTaskInput {
   reviewMemo: string | null;
   projectName: string | null;
   labelName: string | null;
+  dueAt?: string | null;
+  priority?: number | null;
+  tags?: string[] | null;
 }
 
 export const workspaceApi = {
@@ -45,11 +96,14 @@ export const workspaceApi = {
   getGreeting: (workspaceId: number, lang: string) =>
     invoke<string>("get_greeting", { workspaceId, lang }),
 
-  getTimeline: (workspaceId: number, date?: string) =>
-    invoke<TimeBlock[]>("get_timeline", { workspaceId, date }),
+  getTimeline: (workspaceId: number, date?: string, tag?: string) =>
+    invoke<TimeBlock[]>("get_timeline", { workspaceId, date, tag: tag ?? null }),
+
+  getTimelineRange: (workspaceId: number, startDate: string, endDate: string, tag?: string) =>
+    invoke<TimelineDay[]>("get_timeline_range", { workspaceId, startDate, endDate, tag: tag ?? null }),
 
-  getInbox: (workspaceId: number) =>
-    invoke<Task[]>("get_inbox", { workspaceId }),
+  getInbox: (workspaceId: number, sortBy?: InboxSort, tag?: string) =>
+    invoke<Task[]>("get_inbox", { workspaceId, sortBy: sortBy ?? null, tag: tag ?? null }),
 
   getTodayCompletedDuration: (workspaceId: number) =>
     invoke<number>("get_today_completed_duration", { workspaceId }),
@@ -57,11 +111,14 @@ export const workspaceApi = {
   checkUnfinishedPastTasks: (workspaceId: number) =>
     invoke<string[]>("check_unfinished_past_tasks", { workspaceId }),
 
-  getProjects: () =>
-    invoke<any[]>("get_projects"),
+  getProjects: (workspaceId?: number, includeArchived?: boolean) =>
+    invoke<Project[]>("get_projects", { workspaceId: workspaceId ?? null, includeArchived: includeArchived ?? null }),
+
+  getProjectDetail: (id: number) =>
+    invoke<ProjectDetail>("get_project_detail", { id }),
 
-  getLabels: () =>
-    invoke<any[]>("get_labels"),
+  getLabels: (workspaceId?: number) =>
+    invoke<Label[]>("get_labels", { workspaceId: workspaceId ?? null }),
 
   // Actions
   createWorkspace: (input: any) =>
@@ -73,8 +130,14 @@ export const workspaceApi = {
   updateBlockStatus: (blockId: number, status: string) =>
     invoke<void>("update_block_status", { blockId, status }),
 
-  moveToTimeline: (taskId: number, workspaceId: number) =>
-    invoke<void>("move_to_timeline", { taskId, workspaceId }),
+  pauseBlock: (blockId: number) =>
+    invoke<void>("pause_block", { blockId }),
+
+  resumeBlock: (blockId: number) =>
+    invoke<void>("resume_block", { blockId }),
+
+  moveToTimeline: (taskId: number, workspaceId: number, targetDate?: string) =>
+    invoke<void>("move_to_timeline", { taskId, workspaceId, targetDate: targetDate ?? null }),
 
   moveToInbox: (blockId: number) =>
     invoke<void>("move_to_inbox", { blockId }),
@@ -85,9 +148,21 @@ export const workspaceApi = {
   reorderBlocks: (workspaceId: number, blockIds: number[]) =>
     invoke<void>("reorder_blocks", { workspaceId, blockIds }),
 
+  previewDayPlan: (workspaceId: number) =>
+    invoke<DayPlanPreview>("preview_day_plan", { workspaceId }),
+
+  applyDayPlan: (workspaceId: number, taskIds: number[]) =>
+    invoke<void>("apply_day_plan", { workspaceId, taskIds }),
+
+  getAtRiskTasks: (workspaceId: number) =>
+    invoke<Task[]>("get_at_risk_tasks", { workspaceId }),
+
   addTask: (input: AddTaskInput) => 
     invoke<void>("add_task", { input }),
 
+  quickAddTask: (workspaceId: number, text: string) =>
+    invoke<QuickAddResult>("quick_add_task", { workspaceId, text }),
+
   updateTask: (input: UpdateTaskInput) => 
     invoke<void>("update_task", { input }),
 
@@ -106,30 +181,54 @@ export const workspaceApi = {
   deleteWorkspace: (id: number) =>
     invoke<void>("delete_workspace", { id }),
 
-  createLabel: (input: { name: string; color: string }) =>
+  createLabel: (input: { name: string; color: string; workspaceId?: number | null }) =>
     invoke<void>("create_label", { input }),
 
   updateLabel: (id: number, input: { name: string; color: string }) =>
     invoke<void>("update_label", { id, input }),
 
+  setLabelScope: (id: number, workspaceId: number | null) =>
+    invoke<void>("set_label_scope", { id, workspaceId }),
+
   deleteLabel: (id: number) =>
     invoke<void>("delete_label", { id }),
 
-  createProject: (input: { name: string }) =>
+  createProject: (input: ProjectInput) =>
     invoke<void>("create_project", { input }),
 
-  updateProject: (id: number, input: { name: string }) =>
+  updateProject: (id: number, input: ProjectInput) =>
     invoke<void>("update_project", { id, input }),
 
+  setProjectScope: (id: number, workspaceId: number | null) =>
+    invoke<void>("set_project_scope", { id, workspaceId }),
+
   deleteProject: (id: number) =>
     invoke<void>("delete_project", { id }),
 
+  findDuplicateProjects: (workspaceId?: number) =>
+    invoke<DuplicateGroup<Project>[]>("find_duplicate_projects", { workspaceId: workspaceId ?? null }),
+
+  findDuplicateLabels: (workspaceId?: number) =>
+    invoke<DuplicateGroup<Label>[]>("find_duplicate_labels", { workspaceId: workspaceId ?? null }),
+
+  mergeProjects: (sourceId: number, targetId: number) =>
+    invoke<void>("merge_projects", { sourceId, targetId }),
+
+  mergeLabels: (sourceId: number, targetId: number) =>
+    invoke<void>("merge_labels", { sourceId, targetId }),
+
+  reassignTasksProject: (taskIds: number[], projectId: number | null) =>
+    invoke<void>("reassign_tasks_project", { taskIds, projectId }),
+
+  reassignTasksLabel: (taskIds: number[], labelId: number | null) =>
+    invoke<void>("reassign_tasks_label", { taskIds, labelId }),
+
   deleteTask: (id: number) =>
     invoke<void>("delete_task", { id }),
 
   handleSplitTaskDeletion: (taskId: number, keepPast: boolean) =>
     invoke<void>("handle_split_task_deletion", { taskId, keepPast }),
 
-  moveAllToTimeline: (workspaceId: number) =>
-    invoke<void>("move_all_to_timeline", { workspaceId }),
+  moveAllToTimeline: (workspaceId: number, targetDate?: string) =>
+    invoke<void>("move_all_to_timeline", { workspaceId, targetDate: targetDate ?? null }),
 };
@@ -10,6 +10,7 @@ import { cn } from "@/lib/utils";
 import { useOnClickOutside } from "@/hooks/useOnClickOutside";
 import { useDebounce } from "@/hooks/useDebounce";
 import { invoke } from "@tauri-apps/api/core";
+import { TaskSuggestion } from "@/types";
 
 interface TaskFormProps {
   t: any;
@@ -21,7 +22,7 @@ interface TaskFormProps {
 
 export const TaskForm = ({ t, taskForm, onSubmit, onError, workspaceId }: TaskFormProps) => {
   const [isExpanded, setIsExpanded] = useState(false);
-  const [suggestions, setSuggestions] = useState<string[]>([]);
+  const [suggestions, setSuggestions] = useState<TaskSuggestion[]>([]);
   const [showSuggestions, setShowSuggestions] = useState(false);
   
   const formRef = useRef<HTMLDivElement>(null);
@@ -37,7 +38,7 @@ export const TaskForm = ({ t, taskForm, onSubmit, onError, workspaceId }: TaskFo
         return;
       }
       try {
-        const results = await invoke<string[]>("suggest_task_titles", {
+        const results = await invoke<TaskSuggestion[]>("suggest_task_titles", {
           workspaceId,
           query: debouncedTitle.trim(),
           limit: 5,
@@ -87,8 +88,17 @@ export const TaskForm = ({ t, taskForm, onSubmit, onError, workspaceId }: TaskFo
     setIsExpanded(false);
   };
 
-  const handleSuggestionClick = (suggestion: string) => {
-    taskForm.setValue("title", suggestion, { shouldValidate: true, shouldDirty: true });
+  const handleSuggestionClick = (suggestion: TaskSuggestion) => {
+    taskForm.setValue("title", suggestion.title, { shouldValidate: true, shouldDirty: true });
+    if (suggestion.medianMinutes) {
+      taskForm.setValue("hours", Math.floor(suggestion.medianMinutes / 60));
+      taskForm.setValue("minutes", suggestion.medianMinutes % 60);
+    }
+    if (suggestion.projectName) taskForm.setValue("projectName", suggestion.projectName);
+    if (suggestion.labelName) taskForm.setValue("labelName", suggestion.labelName);
+    if (suggestion.planningMemo && !taskForm.getValues("planningMemo")) {
+      taskForm.setValue("planningMemo", suggestion.planningMemo);
+    }
     setShowSuggestions(false);
     titleInputRef.current?.focus();
   };
@@ -167,9 +177,14 @@ export const TaskForm = ({ t, taskForm, onSubmit, onError, workspaceId }: TaskFo
                           <li
                             key={index}
                             onClick={() => handleSuggestionClick(suggestion)}
-                            className="px-4 py-2 hover:bg-surface cursor-pointer text-sm font-medium text-text-primary truncate"
+                            className="px-4 py-2 hover:bg-surface cursor-pointer text-sm font-medium text-text-primary flex items-center justify-between gap-2"
                           >
-                            {suggestion}
+                            <span className="truncate">{suggestion.title}</span>
+                            {suggestion.medianMinutes && (
+                              <span className="shrink-0 text-xs text-text-muted">
+                                ~{suggestion.medianMinutes}m
+                              </span>
+                            )}
                           </li>
                         ))}
                       </motion.ul>
@@ -191,6 +206,7 @@ export const TaskForm = ({ t, taskForm, onSubmit, onError, workspaceId }: TaskFo
                     onChange={(val: string) => taskForm.setValue("projectName", val)}
                     placeholder={t.project_label.project_placeholder}
                     fetchCommand="get_projects"
+                    fetchArgs={{ workspaceId: workspaceId ?? null }}
                     className="flex-1"
                     noneLabel={t.project_label.none}
                     createLabel={(val) => t.project_label.create.replace('{name}', val)}
@@ -200,6 +216,7 @@ export const TaskForm = ({ t, taskForm, onSubmit, onError, workspaceId }: TaskFo
                     onChange={(val: string) => taskForm.setValue("labelName", val)}
                     placeholder={t.project_label.label_placeholder}
                     fetchCommand="get_labels"
+                    fetchArgs={{ workspaceId: workspaceId ?? null }}
                     className="flex-1"
                     noneLabel={t.project_label.none}
                     createLabel={(val) => t.project_label.create.replace('{name}', val)}
@@ -70,11 +70,10 @@ export const WorkspaceHeader = ({
   }, [activeWorkspaceId]);
 
   const disabledDays = (date: Date) => {
-    // Disable future dates beyond today
-    if (date > logicalDate && !isSameDay(date, logicalDate)) return true;
+    // Today and future dates are always open for planning
+    if (isSameDay(date, logicalDate) || date > logicalDate) return false;
 
-    // If not today, only allow dates in activeDates
-    if (isSameDay(date, logicalDate)) return false;
+    // Past dates are only allowed when they have activity
 
     const dateStr = format(date, "yyyy-MM-dd");
     return !activeDates.includes(dateStr);
@@ -129,6 +129,7 @@ export const EditTaskModal = ({
                                 onChange={(val: string) => form.setValue("projectName", val)}
                                 placeholder={t.project_label.project_placeholder}
                                 fetchCommand="get_projects"
+                                fetchArgs={{ workspaceId: editTaskBlock?.workspaceId ?? null }}
                                 noneLabel={t.project_label.none}
                                 createLabel={(val) => t.project_label.create.replace('{name}', val)}
                             />
@@ -140,6 +141,7 @@ export const EditTaskModal = ({
                                 onChange={(val: string) => form.setValue("labelName", val)}
                                 placeholder={t.project_label.label_placeholder}
                                 fetchCommand="get_labels"
+                                fetchArgs={{ workspaceId: editTaskBlock?.workspaceId ?? null }}
                                 noneLabel={t.project_label.none}
                                 createLabel={(val) => t.project_label.create.replace('{name}', val)}
                             />
@@ -15,7 +15,7 @@ export const ProjectManagementTab = ({ t }: { t: any }) => {
 
   const fetchProjects = async () => {
     try {
-      const data = await workspaceApi.getProjects();
+      const data = await workspaceApi.getProjects(undefined, true);
       setProjects(data);
     } catch (error: any) {
       showToast(error.toString(), "error");
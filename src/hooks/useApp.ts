@@ -1,9 +1,5 @@
 import { useState, useEffect, useMemo, useCallback, useRef } from "react";
 import { listen } from "@tauri-apps/api/event";
-import {
-  isPermissionGranted,
-  sendNotification,
-} from "@tauri-apps/plugin-notification";
 import {
   DragEndEvent,
   DragStartEvent,
@@ -15,10 +11,11 @@ import {
 import { format } from "date-fns";
 import { useToast } from "@/providers/ToastProvider";
 import { translations, getLang, type Lang } from "@/lib/i18n";
-import { TimeBlock, Task, User, Workspace, Achievement } from "@/types";
+import { TimeBlock, Task, User, Workspace, Achievement, RolloverReport } from "@/types";
 import { validateDropPosition } from "@/features/workspace/utils/dndValidation";
 
 import { workspaceApi } from "@/features/workspace/api";
+import { rolloverApi } from "@/features/rollover/api";
 import { onboardingApi } from "@/features/onboarding/api";
 
 export type ViewState = "loading" | "onboarding" | "workspace_setup" | "main" | "achievement" | "workspace_settings";
@@ -49,6 +46,12 @@ export function useApp() {
   }, [currentTime, user]);
 
   const [selectedDate, setSelectedDate] = useState<Date | null>(null);
+  // Logical date of the future day being viewed, which new and moved tasks are planned for
+  const plannedDate = useMemo(() => {
+    if (!selectedDate) return null;
+    const dateStr = format(selectedDate, "yyyy-MM-dd");
+    return dateStr > format(logicalDate, "yyyy-MM-dd") ? dateStr : null;
+  }, [selectedDate, logicalDate]);
   const [transitionBlock, setTransitionBlock] = useState<TimeBlock | null>(null);
   const [achievementOpen, setAchievementOpen] = useState(false);
   const [activeAchievement, setActiveAchievement] = useState<Achievement | null>(null);
@@ -58,7 +61,7 @@ export function useApp() {
   const [todayCompletedDuration, setTodayCompletedDuration] = useState<number>(0);
   const [unfinishedPastDates, setUnfinishedPastDates] = useState<string[]>([]);
   const [dismissedBlockId, setDismissedBlockId] = useState<number | null>(null);
-  const lastNotifiedBlockId = useRef<number | null>(null);
+  const [rolloverReport, setRolloverReport] = useState<RolloverReport | null>(null);
 
   const { showToast } = useToast();
   const isFetchingRef = useRef(false);
@@ -73,6 +76,10 @@ export function useApp() {
       const g = await workspaceApi.getGreeting(activeWorkspaceId, lang);
       setGreeting(g);
 
+      // Resolve leftovers of past days before today's timeline is loaded
+      const report = await rolloverApi.runRollover(activeWorkspaceId);
+      if (report) setRolloverReport(report);
+
       const targetDate = selectedDate || logicalDate;
       const dateStr = format(targetDate, "yyyy-MM-dd");
 
@@ -108,20 +115,8 @@ export function useApp() {
             // Re-fetch list after silent transition
             list = await workspaceApi.getTimeline(activeWorkspaceId);
           } else {
+            // The native notification for this block comes from the backend notifier
             setTransitionBlock(active);
-
-            // Send Native Notification if enabled
-            if (user?.isNotificationEnabled && lastNotifiedBlockId.current !== active.id) {
-              const granted = await isPermissionGranted();
-              if (granted) {
-                sendNotification({
-                  id: active.id,
-                  title: t.achievement.task_notification_title,
-                  body: t.achievement.task_notification_body.replace("{title}", active.title),
-                });
-                lastNotifiedBlockId.current = active.id;
-              }
-            }
           }
         }
 
@@ -142,7 +137,7 @@ export function useApp() {
     } finally {
       isFetchingRef.current = false;
     }
-  }, [activeWorkspaceId, lang, selectedDate, transitionBlock, logicalDate, user?.isNotificationEnabled, t.achievement.task_notification_body, t.achievement.task_notification_title]);
+  }, [activeWorkspaceId, lang, selectedDate, transitionBlock, logicalDate]);
 
   const init = useCallback(async () => {
     try {
@@ -180,6 +175,16 @@ export function useApp() {
     }
   }, [activeWorkspaceId, view, fetchMainData]);
 
+  useEffect(() => {
+    // Emitted after the backend applied an overrun policy to a NOW block
+    const unlistenPromise = listen<number>("timeline-updated", (event) => {
+      if (event.payload === activeWorkspaceId) fetchMainData();
+    });
+    return () => {
+      unlistenPromise.then(unlisten => unlisten());
+    };
+  }, [activeWorkspaceId, fetchMainData]);
+
   useEffect(() => {
     const unlistenPromise = listen<number>("open-transition-modal", (event) => {
       const blockId = event.payload;
@@ -223,7 +228,7 @@ export function useApp() {
     if (activeId.includes("inbox") && !overId.includes("inbox")) {
       const taskId = parseInt(activeId.replace("inbox-", ""));
       if (activeWorkspaceId) {
-        await workspaceApi.moveToTimeline(taskId, activeWorkspaceId);
+        await workspaceApi.moveToTimeline(taskId, activeWorkspaceId, plannedDate ?? undefined);
         fetchMainData();
       }
       return;
@@ -298,7 +303,8 @@ export function useApp() {
         workspaceId: activeWorkspaceId,
         ...data,
         planningMemo: data.planningMemo || null,
-        isInbox
+        isInbox,
+        targetDate: plannedDate,
       });
       await fetchMainData();
     } catch (error) {
@@ -359,6 +365,16 @@ export function useApp() {
     setTransitionBlock(null);
   }, []);
 
+  const onAcknowledgeRollover = async () => {
+    if (!rolloverReport) return;
+    try {
+      await rolloverApi.acknowledgeRolloverReport(rolloverReport.id);
+      setRolloverReport(null);
+    } catch (error) {
+      console.error("Acknowledge rollover failed:", error);
+    }
+  };
+
   const onMoveTaskStep = async (blockId: number, direction: "up" | "down") => {
     if (!activeWorkspaceId) return;
     try {
@@ -407,6 +423,7 @@ export function useApp() {
     logicalDate,
     selectedDate,
     setSelectedDate,
+    plannedDate,
     transitionBlock,
     setTransitionBlock,
     achievementOpen,
@@ -432,5 +449,7 @@ export function useApp() {
     onMoveTaskToPriority,
     onMoveTaskToBottom,
     unfinishedPastDates,
+    rolloverReport,
+    onAcknowledgeRollover,
   };
 }
@@ -5,13 +5,33 @@ export interface TimeBlock {
   title: string;
   startTime: string;
   endTime: string;
-  status: "DONE" | "NOW" | "WILL" | "UNPLUGGED" | "PENDING" | "CONTINUED";
+  status: "DONE" | "NOW" | "WILL" | "UNPLUGGED" | "PENDING" | "CONTINUED" | "BREAK";
   reviewMemo: string | null;
   planningMemo: string | null;
   isUrgent: boolean;
   projectName: string | null;
   labelName: string | null;
   labelColor: string | null;
+  pausedMinutes: number;
+  isPaused: boolean;
+  overrunMinutes: number; // Minutes run or extended past the planned end
+  priority: number | null;
+  checklistProgress: number | null;
+  tags: string[];
+}
+
+/** Block minutes (duration minus paused time) of one logical day. */
+export interface DayTotals {
+  byStatus: Record<string, number>;
+  byProject: Record<string, number>;
+  byLabel: Record<string, number>;
+  overrunMinutes: number;
+}
+
+export interface TimelineDay {
+  logicalDate: string; // "YYYY-MM-DD"
+  blocks: TimeBlock[];
+  totals: DayTotals;
 }
 
 export interface Task {
@@ -22,16 +42,50 @@ export interface Task {
   estimatedMinutes: number;
   projectId: number | null;
   labelId: number | null;
+  dueAt: string | null;
+  isAtRisk: boolean;
+  priority: number;
+  isBlocked: boolean;
+  keyResultId: number | null;
+  tags: string[];
 }
 
+export interface TaskSuggestion {
+  title: string;
+  occurrences: number;
+  medianMinutes: number | null;
+  projectName: string | null;
+  labelName: string | null;
+  planningMemo: string | null;
+  lastUsed: string | null;
+}
+
+export type ProjectStatus = "ACTIVE" | "FINISHED" | "ARCHIVED";
+
 export interface Project {
   id: number;
+  workspaceId: number | null;
   name: string;
   lastUsed: string;
+  description: string | null;
+  role: string | null;
+  techStack: string | null;
+  link: string | null;
+  startDate: string | null;
+  endDate: string | null;
+  status: ProjectStatus;
+}
+
+export interface ProjectDetail extends Project {
+  totalMinutes: number;
+  taskCount: number;
+  firstActivity: string | null;
+  lastActivity: string | null;
 }
 
 export interface Label {
   id: number;
+  workspaceId: number | null;
   name: string;
   color: string;
   lastUsed: string;
@@ -50,7 +104,7 @@ export interface User {
 export interface Achievement {
   id: number;
   workspaceId: number;
-  achievementType: "DAILY";
+  achievementType: "DAILY" | "SELF_EVALUATION";
   content: string;
   dateLabel: string;
   createdAt: string;
@@ -63,4 +117,249 @@ export interface Workspace {
   coreTimeStart: string | null;
   coreTimeEnd: string | null;
   roleIntro: string | null;
+  rolloverPolicy: RolloverPolicy;
+  overrunPolicy: OverrunPolicy;
+  overrunExtendMinutes: number;
+}
+
+export type OverrunPolicy = "EXTEND" | "COMPLETE" | "FLAG";
+
+export type RolloverPolicy = "INBOX" | "CARRY" | "COMPLETE";
+
+export interface RolloverItem {
+  id: number;
+  reportId: number;
+  taskId: number | null;
+  title: string;
+  fromDate: string; // "YYYY-MM-DD"
+  minutes: number;
+  action: RolloverPolicy;
+  newStart: string | null;
+}
+
+export interface RolloverReport {
+  id: number;
+  workspaceId: number;
+  logicalDate: string; // "YYYY-MM-DD"
+  policy: RolloverPolicy;
+  createdAt: string;
+  acknowledgedAt: string | null;
+  items: RolloverItem[];
+}
+
+export interface NotificationSettings {
+  workspaceId: number;
+  notifyAtStart: boolean;
+  leadMinutes: number; // 0 disables the before-end reminder
+  notifyAtEnd: boolean;
+  overrunIntervalMinutes: number; // 0 disables overrun reminders
+  quietStart: string | null; // "HH:MM"
+  quietEnd: string | null; // "HH:MM"
+}
+
+/** Omitted fields keep their value; "" clears the quiet hours. */
+export type NotificationSettingsInput = Partial<Omit<NotificationSettings, "workspaceId" | "quietStart" | "quietEnd">> & {
+  quietStart?: string;
+  quietEnd?: string;
+};
+
+export interface FocusSession {
+  id: number;
+  workspaceId: number;
+  blockId: number | null;
+  taskId: number | null;
+  title: string;
+  startedAt: string;
+  endedAt: string | null;
+  workMinutes: number;
+  breakMinutes: number;
+  status: "RUNNING" | "COMPLETED" | "CANCELLED";
+  interruptions: number;
+}
+
+export interface StartFocusInput {
+  blockId: number;
+  workMinutes?: number; // 25 by default
+  breakMinutes?: number; // 5 by default; 0 skips the break
+}
+
+export interface FocusTaskStats {
+  taskId: number | null;
+  title: string;
+  completedSessions: number;
+  focusMinutes: number;
+  interruptions: number;
+}
+
+export interface FocusDayStats {
+  logicalDate: string; // "YYYY-MM-DD"
+  completedSessions: number;
+  cancelledSessions: number;
+  focusMinutes: number;
+  interruptions: number;
+  tasks: FocusTaskStats[];
+}
+
+export interface Interruption {
+  id: number;
+  workspaceId: number;
+  interruptingTaskId: number | null;
+  interruptingTitle: string;
+  interruptedTaskId: number | null;
+  interruptedTitle: string;
+  occurredAt: string;
+  lostMinutes: number;
+  projectName: string | null; // of the interrupting task
+  labelName: string | null;
+}
+
+export interface InterruptionGroup {
+  key: string; // "YYYY-MM-DD", project or label name
+  count: number;
+  lostMinutes: number;
+}
+
+export interface InterruptionReport {
+  totalCount: number;
+  totalLostMinutes: number;
+  byDay: InterruptionGroup[];
+  byProject: InterruptionGroup[];
+  byLabel: InterruptionGroup[];
+  items: Interruption[];
+}
+
+export interface Retrospective {
+  id: number;
+  workspaceId: number;
+  logicalDate: string; // "YYYY-MM-DD"
+  wentWell: string | null;
+  blockers: string | null;
+  moodScore: number | null; // 1-5
+  energyScore: number | null; // 1-5
+  learnings: string | null;
+  tomorrowFocus: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface SaveRetrospectiveInput {
+  workspaceId: number;
+  logicalDate: string;
+  wentWell?: string | null;
+  blockers?: string | null;
+  moodScore?: number | null;
+  energyScore?: number | null;
+  learnings?: string | null;
+  tomorrowFocus?: string | null;
+}
+
+export type KeyResultMetric = "TIME" | "NUMBER";
+
+export interface KeyResult {
+  id: number;
+  goalId: number;
+  title: string;
+  metric: KeyResultMetric;
+  targetValue: number; // minutes for TIME
+  unit: string | null;
+  position: number;
+  linkedMinutes: number;
+  linkedTaskCount: number;
+  currentValue: number;
+  progressPercent: number; // may exceed 100
+}
+
+export interface Goal {
+  id: number;
+  workspaceId: number;
+  title: string;
+  description: string | null;
+  periodStart: string; // "YYYY-MM-DD"
+  periodEnd: string;
+  createdAt: string;
+  keyResults: KeyResult[];
+  progressPercent: number;
+}
+
+export interface KeyResultCheckIn {
+  id: number;
+  keyResultId: number;
+  value: number; // logged minutes for TIME, the new value for NUMBER
+  note: string | null;
+  checkedAt: string;
+}
+
+export interface GoalInput {
+  workspaceId: number; // ignored on update
+  title: string;
+  description?: string | null;
+  periodStart: string;
+  periodEnd: string;
+}
+
+export interface KeyResultInput {
+  goalId: number; // ignored on update
+  title: string;
+  metric: KeyResultMetric;
+  targetValue: number;
+  unit?: string | null;
+}
+
+export interface CheckInInput {
+  keyResultId: number;
+  value: number;
+  note?: string | null;
+}
+
+export type StandupMode = "TEMPLATE" | "AI";
+
+export interface StandupInput {
+  workspaceId: number;
+  logicalDate?: string | null; // defaults to today
+  mode?: StandupMode | null; // defaults to TEMPLATE
+  targetModel?: string | null;
+  forceRetry?: boolean;
+}
+
+export interface StandupItem {
+  taskId: number | null;
+  title: string;
+  status: string; // block status, or "BLOCKED"
+  minutes: number;
+  projectName: string | null;
+  note: string | null; // review memo, or the prerequisites a blocked task waits on
+}
+
+export interface Standup {
+  logicalDate: string;
+  previousDate: string; // last earlier day with finished work
+  yesterday: StandupItem[];
+  today: StandupItem[];
+  blocked: StandupItem[];
+  markdown: string;
+  plainText: string; // Slack-style
+  usedModel: string | null;
+}
+
+export interface Competency {
+  id: number;
+  workspaceId: number;
+  name: string;
+  description: string | null;
+  position: number;
+}
+
+export interface CompetencyInput {
+  name: string;
+  description?: string | null;
+}
+
+export interface SelfEvaluationInput {
+  workspaceId: number;
+  startDate: string; // "YYYY-MM-DD"
+  endDate: string;
+  dateLabel: string; // e.g. "2026 H1"
+  overwrite?: boolean;
+  forceRetry?: boolean;
+  targetModel?: string | null;
 }